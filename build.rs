@@ -0,0 +1,31 @@
+// Compiles the bundled test programs ahead of time into OUT_DIR, where src/programs/compiled
+// includes them from. The generator only needs the standard library, so it and the list of
+// programs are shared with the crate as they are.
+mod recompiler {
+    pub mod generator {
+        include!("src/recompiler/generator.rs");
+    }
+}
+
+mod programs {
+    pub mod bundled {
+        include!("src/programs/bundled.rs");
+    }
+}
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/recompiler/generator.rs");
+    println!("cargo:rerun-if-changed=src/programs/bundled.rs");
+    println!("cargo:rerun-if-changed=src/binaries");
+
+    let directory: PathBuf = PathBuf::from(env::var("OUT_DIR").unwrap());
+    for (module, image) in programs::bundled::bundled().iter() {
+        let path: PathBuf = directory.join(format!("{}.rs", module));
+        fs::write(&path, recompiler::generator::recompile(image)).unwrap();
+    }
+}
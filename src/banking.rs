@@ -0,0 +1,153 @@
+use crate::map::*;
+use crate::mem::Decay;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A backing store larger than the window the processor sees it through, one bank at a time.
+/// Map it over the window; a BankRegister, or the host, picks the bank.
+#[derive(Debug, Clone)]
+pub struct Banked {
+    pub data: Vec<u8>,
+    pub size: u32, // Bytes in one bank, the size of the window
+    pub writable: bool,
+    bank: u16,
+}
+
+impl Banked {
+    pub fn rom(image: &[u8], size: u32) -> Banked {
+        /* A partial last bank reads as open bus past the end of the image */
+        assert!(size > 0, "banks must hold at least one byte");
+        assert!(
+            (image.len() as u64).div_ceil(size as u64) <= u16::MAX as u64,
+            "more than {} banks",
+            u16::MAX
+        );
+        return Banked {
+            data: image.to_vec(),
+            size,
+            writable: false,
+            bank: 0,
+        };
+    }
+
+    pub fn ram(banks: u16, size: u32) -> Banked {
+        assert!(size > 0, "banks must hold at least one byte");
+        return Banked {
+            data: vec![0; banks as usize * size as usize],
+            size,
+            writable: true,
+            bank: 0,
+        };
+    }
+
+    pub fn banks(&self) -> u16 {
+        return (self.data.len() as u32).div_ceil(self.size) as u16;
+    }
+
+    pub fn selected(&self) -> u16 {
+        return self.bank;
+    }
+
+    pub fn select(&mut self, bank: u16) -> () {
+        // Select lines past the size of the store are not connected, so the banks repeat
+        self.bank = bank % self.banks().max(1);
+    }
+
+    fn index(&self, offset: u32) -> usize {
+        return self.bank as usize * self.size as usize + (offset % self.size) as usize;
+    }
+}
+
+impl Device for Banked {
+    fn read(&mut self, offset: u32) -> u8 {
+        return self.peek(offset);
+    }
+
+    fn write(&mut self, offset: u32, data: u8) -> () {
+        if self.writable {
+            self.poke(offset, data);
+        }
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        return *self.data.get(self.index(offset)).unwrap_or(&0xFF);
+    }
+
+    fn peek_is_authoritative(&self, offset: u32) -> bool {
+        return self.index(offset) < self.data.len();
+    }
+
+    fn poke(&mut self, offset: u32, data: u8) -> () {
+        // Loaders can fill a ROM bank too
+        let index: usize = self.index(offset);
+        if let Some(byte) = self.data.get_mut(index) {
+            *byte = data;
+        }
+    }
+
+    fn bank(&self, _offset: u32) -> Option<u16> {
+        return Some(self.bank);
+    }
+
+    fn floating(&self, offset: u32) -> Option<Decay> {
+        match self.index(offset) >= self.data.len() {
+            true => return Some(Decay::Hold),
+            false => return None,
+        }
+    }
+}
+
+/// Where a write to a bank register takes the bank number from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Select {
+    Data { mask: u8, shift: u8 }, // (data & mask) >> shift, the usual cartridge latch
+    Offset, // Which register was written, whatever the data, like soft switches
+}
+
+/// A control register that switches the banks of one or more windows when it is written.
+pub struct BankRegister {
+    windows: Vec<(Rc<RefCell<Banked>>, Select)>,
+    value: u8, // Last data written, read back by registers that are not write-only
+}
+
+impl BankRegister {
+    pub fn new() -> BankRegister {
+        return BankRegister {
+            windows: Vec::new(),
+            value: 0,
+        };
+    }
+
+    pub fn controls(&mut self, window: Rc<RefCell<Banked>>, select: Select) -> () {
+        if let Select::Data { shift, .. } = select {
+            assert!(shift < 8, "shift of {} leaves no data bits", shift);
+        }
+        self.windows.push((window, select));
+    }
+}
+
+impl Device for BankRegister {
+    fn read(&mut self, offset: u32) -> u8 {
+        return self.peek(offset);
+    }
+
+    fn write(&mut self, offset: u32, data: u8) -> () {
+        self.value = data;
+        for (window, select) in self.windows.iter() {
+            let bank: u16 = match *select {
+                Select::Data { mask, shift } => ((data & mask) >> shift) as u16,
+                Select::Offset => offset as u16,
+            };
+            window.borrow_mut().select(bank);
+        }
+    }
+
+    fn peek(&self, _offset: u32) -> u8 {
+        return self.value;
+    }
+
+    fn peek_is_authoritative(&self, _offset: u32) -> bool {
+        return true;
+    }
+}
@@ -0,0 +1,226 @@
+use crate::cpu;
+use crate::mem::*;
+use crate::programs::bundled::*;
+use crate::programs::compiled::functional_test::FunctionalTest;
+use crate::programs::FUNCTIONAL_TEST_SUCCESS;
+
+use cpu::blocks::Engine;
+use cpu::compiled::CompiledExecution;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+const FUNCTIONAL_TEST_LIMIT: u64 = 200_000_000; // Well past the ~96 million cycles a passing run takes
+const TIGHT_LOOP_CYCLES: u64 = 50_000_000;
+const SLICE_CYCLES: u32 = 1_000_000; // Budget handed to each call of execute()
+
+/// What one workload ran and how long the host took over it.
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub name: String,
+    pub cycles: u64,
+    pub instructions: u64,
+    pub elapsed: Duration,
+    pub stop: StopReason,
+    pub fingerprint: u64, // Hash of the registers and memory the run finished with
+}
+
+impl BenchmarkResult {
+    pub fn megahertz(&self) -> f64 {
+        return self.cycles as f64 / self.elapsed.as_secs_f64() / 1_000_000.0;
+    }
+
+    pub fn instructions_per_second(&self) -> f64 {
+        return self.instructions as f64 / self.elapsed.as_secs_f64();
+    }
+}
+
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{:<40} {:>12} cycles {:>12} instructions {:>8.3}s {:>9.2} MHz {:>14.0} instructions/s",
+            self.name,
+            self.cycles,
+            self.instructions,
+            self.elapsed.as_secs_f64(),
+            self.megahertz(),
+            self.instructions_per_second()
+        );
+    }
+}
+
+fn fingerprint(processor: &Processor, memory: &Memory) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    processor.program_counter.hash(&mut hasher);
+    processor.stack_pointer.hash(&mut hasher);
+    processor.accumulator.hash(&mut hasher);
+    processor.register_x.hash(&mut hasher);
+    processor.register_y.hash(&mut hasher);
+    processor.status.hash(&mut hasher);
+    processor.clock.hash(&mut hasher);
+    memory.data.hash(&mut hasher);
+    return hasher.finish();
+}
+
+fn measure<R>(
+    name: String,
+    processor: &mut Processor,
+    memory: &mut Memory,
+    limit: u64,
+    mut run: R,
+) -> BenchmarkResult
+where
+    R: FnMut(&mut Processor, &mut Memory) -> ExecutionResult,
+{
+    /*
+        Runs execute() in fixed slices until the limit or until something other than the budget
+        stops it, so the numbers include the per-call overhead a host loop would pay.
+    */
+    let mut cycles: u64 = 0;
+    let mut instructions: u64 = 0;
+    let mut stop: StopReason = StopReason::BudgetExhausted;
+
+    let started: Instant = Instant::now();
+    while cycles < limit && stop == StopReason::BudgetExhausted {
+        processor.cycles = SLICE_CYCLES.min((limit - cycles) as u32);
+        let result: ExecutionResult = run(processor, memory);
+        cycles += result.cycles as u64;
+        instructions += result.instructions;
+        stop = result.stop;
+    }
+
+    let elapsed: Duration = started.elapsed();
+    return BenchmarkResult {
+        name,
+        cycles,
+        instructions,
+        elapsed,
+        stop,
+        fingerprint: fingerprint(processor, memory),
+    };
+}
+
+fn setup(variant: Variant, engine: Engine, start: u16) -> (Memory, Processor) {
+    let mut memory = Memory::new();
+    let mut processor = Processor::new(variant);
+    processor.engine = engine;
+    processor.reset(&mut memory);
+    processor.program_counter = start;
+
+    return (memory, processor);
+}
+
+pub fn tight_loop(variant: Variant, engine: Engine) -> BenchmarkResult {
+    /* INX / BNE back to it, with a JMP around once X wraps, nothing touches memory past the fetches */
+    let (mut memory, mut processor) = setup(variant, engine, 0x0200);
+    let program: [u8; 6] = [INX, BNE, 0xFD, JMP_ABSOLUTE, 0x00, 0x02];
+    for (offset, byte) in program.iter().enumerate() {
+        memory.poke(0x0200 + offset as u32, *byte);
+    }
+
+    let name: String = format!("Tight loop ({:?}), {:?}", variant, engine);
+    return measure(
+        name,
+        &mut processor,
+        &mut memory,
+        TIGHT_LOOP_CYCLES,
+        execute,
+    );
+}
+
+fn execute(processor: &mut Processor, memory: &mut Memory) -> ExecutionResult {
+    return processor.execute(memory);
+}
+
+fn functional_setup(variant: Variant, engine: Engine) -> (Memory, Processor) {
+    /* Klaus Dormann's functional test, stopped by a breakpoint on its success trap */
+    let (mut memory, mut processor) = setup(variant, engine, FUNCTIONAL_TEST_START);
+    for (offset, byte) in FUNCTIONAL_TEST.iter().enumerate() {
+        memory.poke((FUNCTIONAL_TEST_OFFSET + offset) as u32, *byte);
+    }
+    processor
+        .debugger
+        .breakpoints
+        .insert(FUNCTIONAL_TEST_SUCCESS as u32);
+
+    return (memory, processor);
+}
+
+pub fn functional_test(variant: Variant, engine: Engine) -> BenchmarkResult {
+    let (mut memory, mut processor) = functional_setup(variant, engine);
+    let name: String = format!("Functional test ({:?}), {:?}", variant, engine);
+    return measure(
+        name,
+        &mut processor,
+        &mut memory,
+        FUNCTIONAL_TEST_LIMIT,
+        execute,
+    );
+}
+
+pub fn compiled_functional_test() -> BenchmarkResult {
+    /* The same run through the recompiled test, which only exists for the NMOS part */
+    let (mut memory, mut processor) = functional_setup(Variant::Nmos6502, Engine::Interpreter);
+    let name: String = String::from("Functional test (Nmos6502), Compiled");
+    return measure(
+        name,
+        &mut processor,
+        &mut memory,
+        FUNCTIONAL_TEST_LIMIT,
+        |processor, memory| processor.execute_compiled::<FunctionalTest, Memory>(memory),
+    );
+}
+
+fn compare(interpreted: &BenchmarkResult, faster: &BenchmarkResult, engine: &str) -> () {
+    /* Neither the block cache nor compiled code is worth having if it can be told apart from the interpreter */
+    println!("{}", faster);
+
+    let same: bool = interpreted.cycles == faster.cycles
+        && interpreted.instructions == faster.instructions
+        && interpreted.stop == faster.stop
+        && interpreted.fingerprint == faster.fingerprint;
+    match same {
+        true => println!(
+            "  engines agree, {} ran {:.2}x as fast",
+            engine,
+            interpreted.elapsed.as_secs_f64() / faster.elapsed.as_secs_f64()
+        ),
+        false => println!("  ENGINES DISAGREE"),
+    }
+}
+
+pub fn run() -> () {
+    println!("6502 BENCHMARK");
+    let variants: [Variant; 2] = [Variant::Nmos6502, Variant::Wdc65C02];
+
+    for variant in variants.iter() {
+        let interpreted: BenchmarkResult = tight_loop(*variant, Engine::Interpreter);
+        let cached: BenchmarkResult = tight_loop(*variant, Engine::Blocks);
+        println!("{}", interpreted);
+        compare(&interpreted, &cached, "blocks");
+    }
+
+    for variant in variants.iter() {
+        let interpreted: BenchmarkResult = functional_test(*variant, Engine::Interpreter);
+        let cached: BenchmarkResult = functional_test(*variant, Engine::Blocks);
+        println!("{}", interpreted);
+        compare(&interpreted, &cached, "blocks");
+        if *variant == Variant::Nmos6502 {
+            compare(&interpreted, &compiled_functional_test(), "compiled code");
+        }
+
+        // Anything but the success trap means the test failed, or never finished, and its timing means little
+        match interpreted.stop {
+            StopReason::Breakpoint { .. } => println!("  functional test PASSED"),
+            _ => println!(
+                "  functional test did not reach {:#06X}: {:?}",
+                FUNCTIONAL_TEST_SUCCESS, interpreted.stop
+            ),
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::decode::execute_instruction;
+use cpu::disassembler::{mnemonic, Operand};
+use cpu::functions::byte::*;
+use cpu::instructions::system::System;
+use cpu::native::registers::WideRegisters;
+use cpu::processor::*;
+use cpu::stepper::CyclePhase;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+const MAX_BLOCK_INSTRUCTIONS: usize = 64;
+
+/// How execute() gets at each opcode. Both engines give the same registers, memory and cycle counts,
+/// as long as a host that changes code in memory itself calls BlockCache::clear() afterwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Engine {
+    Interpreter, // Every opcode is fetched through the bus
+    Blocks,      // Opcodes come out of the block cache whenever nothing could tell the difference
+}
+
+/// A straight run of instructions decoded once, ending at the first one that can leave it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub start: u32,                   // Bank byte included on the 65C816
+    pub end: u32,                     // One past the last byte of the last instruction
+    pub instructions: Vec<(u32, u8)>, // Address and opcode
+    pub bank: Option<u16>,            // Switched in under the start when it was decoded
+}
+
+impl Block {
+    pub fn contains(&self, address: u32) -> bool {
+        return address >= self.start && address < self.end;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BlockStatistics {
+    pub decoded: u64,     // Blocks built from memory
+    pub entered: u64,     // Blocks started from the cache
+    pub invalidated: u64, // Blocks thrown away by a write into them or a bank switch under them
+}
+
+/// Decoded blocks by start address, plus which pages hold them so writes can be checked quickly.
+pub struct BlockCache {
+    blocks: HashMap<u32, Rc<Block>>,
+    entries: Vec<Option<Rc<Block>>>, // By the low 16 bits of the start, looked at before the map
+    pages: HashMap<u32, Vec<u32>>,   // Page number to the starts of the blocks with bytes on it
+    code_pages: Vec<u16>, // How many blocks touch each page, so writes elsewhere cost one index
+    current: Option<Rc<Block>>, // The block run_block() is partway through, dropped by a write into it
+    pub statistics: BlockStatistics,
+}
+
+impl fmt::Debug for BlockCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockCache")
+            .field("blocks", &self.blocks.len())
+            .field("statistics", &self.statistics)
+            .finish()
+    }
+}
+
+impl BlockCache {
+    pub fn new() -> BlockCache {
+        return BlockCache {
+            blocks: HashMap::new(),
+            entries: Vec::new(),
+            pages: HashMap::new(),
+            code_pages: Vec::new(),
+            current: None,
+            statistics: BlockStatistics::default(),
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.blocks.len();
+    }
+
+    pub fn block(&self, start: u32) -> Option<&Block> {
+        return self.blocks.get(&start).map(|block| block.as_ref());
+    }
+
+    pub fn clear(&mut self) -> () {
+        /* For the host after it changes memory behind the processor's back, with poke() or a bank switch */
+        self.blocks.clear();
+        self.entries.clear();
+        self.pages.clear();
+        self.code_pages.clear();
+        self.current = None;
+    }
+
+    pub fn invalidate(&mut self, address: u32, mask: u32) -> () {
+        /*
+            Blocks are kept by the address the program counter ran them from, which on a 6507 can be
+            any of the mirrors of the address the write went to on the bus
+        */
+        let space: u32 = (mask | 0xFFFF) + 1;
+        for mirror in (address & mask..space).step_by(mask as usize + 1) {
+            self.invalidate_at(mirror);
+        }
+    }
+
+    fn invalidate_at(&mut self, address: u32) -> () {
+        /* Called for every write, so the common case of a page with no code on it has to stay cheap */
+        match self.code_pages.get((address >> 8) as usize) {
+            Some(count) if *count > 0 => {}
+            _ => return,
+        }
+        let starts: Vec<u32> = match self.pages.get(&(address >> 8)) {
+            Some(starts) => starts.clone(),
+            None => return,
+        };
+
+        for start in starts {
+            let hit: bool = match self.blocks.get(&start) {
+                Some(block) => block.contains(address),
+                None => false,
+            };
+            if hit {
+                self.remove(start);
+            }
+        }
+
+        // A write into the running block ends it, the next opcode is decoded afresh
+        if let Some(block) = self.current.as_ref() {
+            if block.contains(address) {
+                self.current = None;
+            }
+        }
+    }
+
+    fn remove(&mut self, start: u32) -> () {
+        let block: Rc<Block> = match self.blocks.remove(&start) {
+            Some(block) => block,
+            None => return,
+        };
+        self.statistics.invalidated += 1;
+
+        let entry: usize = (start & 0xFFFF) as usize;
+        if let Some(Some(cached)) = self.entries.get(entry) {
+            if cached.start == start {
+                self.entries[entry] = None;
+            }
+        }
+
+        for page in (block.start >> 8)..=((block.end - 1) >> 8) {
+            self.code_pages[page as usize] -= 1;
+            if let Some(starts) = self.pages.get_mut(&page) {
+                starts.retain(|other| *other != start);
+                if starts.is_empty() {
+                    self.pages.remove(&page);
+                }
+            }
+        }
+    }
+
+    pub fn switched<B: Bus>(&mut self, memory: &B, mask: u32) -> () {
+        /* After a write that may have switched banks, the running block ends if its bank went away */
+        if let Some(block) = self.current.as_ref() {
+            if memory.bank(block.start & mask) != block.bank {
+                self.current = None;
+            }
+        }
+    }
+
+    fn lookup(&mut self, start: u32) -> Option<Rc<Block>> {
+        let entry: usize = (start & 0xFFFF) as usize;
+        if let Some(Some(cached)) = self.entries.get(entry) {
+            if cached.start == start {
+                return Some(cached.clone());
+            }
+        }
+
+        // Another bank's block had the slot, the map still knows this one
+        let block: Rc<Block> = self.blocks.get(&start)?.clone();
+        self.entries[entry] = Some(block.clone());
+        return Some(block);
+    }
+
+    fn insert(&mut self, block: Block) -> Rc<Block> {
+        if self.entries.is_empty() {
+            self.entries.resize(0x1_0000, None);
+            self.code_pages.resize(0x1_0000, 0);
+        }
+
+        for page in (block.start >> 8)..=((block.end - 1) >> 8) {
+            self.code_pages[page as usize] += 1;
+            self.pages.entry(page).or_default().push(block.start);
+        }
+
+        let block: Rc<Block> = Rc::new(block);
+        self.blocks.insert(block.start, block.clone());
+        self.entries[(block.start & 0xFFFF) as usize] = Some(block.clone());
+        self.statistics.decoded += 1;
+        return block;
+    }
+}
+
+fn ends_block(name: &str, operand: Operand) -> bool {
+    match operand {
+        Operand::Relative | Operand::RelativeLong | Operand::ZeroPageRelative => return true,
+        _ => {}
+    }
+
+    // Anything that jumps, returns, parks the processor or can change the 65C816 register widths
+    match name {
+        "JMP" | "JML" | "JSR" | "JSL" | "RTS" | "RTL" | "RTI" | "BRK" | "COP" => return true,
+        "WAI" | "STP" | "JAM" | "REP" | "SEP" | "XCE" | "PLP" => return true,
+        _ => return false,
+    }
+}
+
+pub fn decode_block<B: Bus>(processor: &Processor, memory: &B, start: u32) -> Option<Block> {
+    /*
+        Reads ahead with peek(), so devices see nothing. Stops before anything the on-chip port
+        answers for, before an opcode the bus cannot peek at and before an instruction that would
+        wrap around the end of its bank.
+    */
+    let mask: u32 = processor.variant.address_mask();
+    let mut instructions: Vec<(u32, u8)> = Vec::new();
+    let mut address: u32 = start;
+
+    while instructions.len() < MAX_BLOCK_INSTRUCTIONS {
+        if let Some(port) = processor.io_port.as_ref() {
+            if port.handles(address as u16) {
+                break;
+            }
+        }
+
+        if memory.peek_is_authoritative(address & mask) == false {
+            break;
+        }
+
+        let opcode: u8 = memory.peek(address & mask);
+        let (name, operand) = mnemonic(processor.variant, opcode);
+        let length: u32 =
+            operand.length(processor.accumulator_is_wide(), processor.index_is_wide()) as u32;
+        if (address & 0xFFFF) + length > 0x1_0000 {
+            break;
+        }
+
+        instructions.push((address, opcode));
+        address += length;
+        if ends_block(name, operand) {
+            break;
+        }
+    }
+
+    if instructions.is_empty() {
+        return None;
+    }
+    return Some(Block {
+        start,
+        end: address,
+        instructions,
+        bank: memory.bank(start & mask),
+    });
+}
+
+pub fn unobserved(processor: &Processor, start: u32, end: u32) -> bool {
+    /* Cached opcodes skip the bus read and the per-instruction checks, only allowed while nothing would notice */
+    return processor.bus_observer.is_none()
+        && processor.tracer.is_none()
+        && processor.stepper.phase() == CyclePhase::Direct
+        && processor.control.ready()
+        && processor.debugger.passes(start, end)
+        && processor.regions.passes();
+}
+
+fn interrupted(processor: &Processor, expected: u32, deadline: u64) -> bool {
+    /* The checks execute() makes between instructions that can still come out differently inside a block */
+    let address: u32 =
+        ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32;
+    return address != expected
+        || processor.clock >= deadline
+        || processor.fault.is_some()
+        || processor.state != ExecutionState::Running
+        || processor.debugger.halt_pending();
+}
+
+pub fn next_instruction<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    expected: u32,
+    deadline: u64,
+) -> bool {
+    /* Between two instructions of a block, false wherever execute() would not just fetch the one at `expected` */
+    if interrupted(processor, expected, deadline) {
+        return false;
+    }
+    processor.sync_bus(memory);
+    processor.apply_set_overflow();
+    return processor.poll_interrupts(memory) == false;
+}
+
+pub trait BlockExecution {
+    fn run_block<B: Bus>(&mut self, memory: &mut B, deadline: u64) -> u64;
+}
+
+impl BlockExecution for Processor {
+    fn run_block<B: Bus>(&mut self, memory: &mut B, deadline: u64) -> u64 {
+        /*
+            Runs from the program counter to the end of its block and returns how many instructions ran.
+            execute() has made its checks for the first one, the rest stop the block early wherever
+            execute() would have done something other than fetch the next opcode. Anything the cache
+            cannot vouch for runs as one instruction through the interpreter.
+        */
+        let address: u32 = ((self.native.program_bank as u32) << 16) | self.program_counter as u32;
+        let mask: u32 = self.variant.address_mask();
+        let block: Option<Rc<Block>> = match self.blocks.lookup(address) {
+            Some(block) if block.bank != memory.bank(address & mask) => {
+                // Decoded from a bank that is no longer switched in
+                self.blocks.remove(address);
+                match decode_block(self, memory, address) {
+                    Some(block) => Some(self.blocks.insert(block)),
+                    None => None,
+                }
+            }
+            Some(block) => {
+                self.blocks.statistics.entered += 1;
+                Some(block)
+            }
+            None => match decode_block(self, memory, address) {
+                Some(block) => Some(self.blocks.insert(block)),
+                None => None,
+            },
+        };
+
+        let block: Rc<Block> = match block {
+            Some(block) if unobserved(self, block.start, block.end) => block,
+            _ => {
+                let instruction: u8 = self.fetch_opcode(memory);
+                execute_instruction(self, memory, instruction);
+                return 1;
+            }
+        };
+
+        self.blocks.current = Some(block.clone());
+        let mut executed: u64 = 0;
+        for (expected, opcode) in block.instructions.iter() {
+            if executed > 0
+                && (self.blocks.current.is_none()
+                    || next_instruction(self, memory, *expected, deadline) == false)
+            {
+                break;
+            }
+
+            fetch_cached(self, *opcode);
+            execute_instruction(self, memory, *opcode);
+            executed += 1;
+        }
+
+        self.blocks.current = None;
+        return executed;
+    }
+}
@@ -0,0 +1,130 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::blocks::{next_instruction, unobserved};
+use cpu::functions::byte::{fetch_cached, read_unobserved, write_unobserved};
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+/// A binary the recompiler turned into Rust. Generated modules implement this, nothing else should.
+pub trait CompiledProgram {
+    const VARIANT: Variant; // The part it was compiled for, on any other every address is interpreted
+
+    /// Runs compiled code from the address for as long as it can and returns how many instructions
+    /// ran, None when there is no block there or it cannot be trusted right now.
+    fn run_block<B: Bus>(
+        processor: &mut Processor,
+        memory: &mut B,
+        address: u32,
+        deadline: u64,
+    ) -> Option<u64>;
+}
+
+/// Runs the compiled block at an address, carrying on the count of instructions the call has run.
+/// Ok once the whole block has run, Err with the count where it stopped early or never started.
+pub type Dispatch<B> = fn(&mut Processor, &mut B, u32, u64, u64) -> Result<u64, u64>;
+
+pub fn enter<P: CompiledProgram>(processor: &Processor, start: u32, end: u32) -> bool {
+    /* The same conditions the block cache runs under, anything watching the bus or the debugger gets the interpreter */
+    return processor.variant == P::VARIANT && unobserved(processor, start, end);
+}
+
+pub fn step<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    address: u32,
+    bytes: &[u8],
+    deadline: u64,
+    executed: u64,
+) -> bool {
+    /*
+        What execute() does before each compiled instruction: its checks for every one after the
+        first, then the opcode fetch cycle. An instruction whose bytes are no longer in memory means
+        the code was changed since it was compiled, so it and everything after it go back to the
+        interpreter, as do bytes a bus cannot show without reading them or the on-chip port answers for.
+    */
+    let mask: u32 = processor.variant.address_mask();
+    for (offset, byte) in bytes.iter().enumerate() {
+        let physical: u32 = (address + offset as u32) & mask;
+        if let Some(port) = processor.io_port.as_ref() {
+            if port.handles(physical as u16) {
+                return false;
+            }
+        }
+        if memory.peek_is_authoritative(physical) == false || memory.peek(physical) != *byte {
+            return false;
+        }
+    }
+    if executed > 0 && next_instruction(processor, memory, address, deadline) == false {
+        return false;
+    }
+
+    fetch_cached(processor, bytes[0]);
+    return true;
+}
+
+pub fn set_zero_negative(processor: &mut Processor, value: u8) -> () {
+    processor.set_status(ZeroFlag, value == 0);
+    processor.set_status(NegativeFlag, fetch_bit(value, 7));
+}
+
+pub fn push<B: Bus>(processor: &mut Processor, memory: &mut B, value: u8) -> () {
+    let stack_address: u16 = 0x100 | processor.stack_pointer as u16;
+    write_unobserved(processor, memory, value, stack_address);
+    processor.stack_pointer = processor.stack_pointer.wrapping_sub(1);
+}
+
+pub fn push_program_counter<B: Bus>(processor: &mut Processor, memory: &mut B) -> () {
+    let bytes: [u8; 2] = processor.program_counter.to_le_bytes();
+    push(processor, memory, bytes[1]);
+    push(processor, memory, bytes[0]);
+}
+
+pub fn pull<B: Bus>(processor: &mut Processor, memory: &mut B) -> u8 {
+    processor.stack_pointer = processor.stack_pointer.wrapping_add(1);
+    let stack_address: u16 = 0x100 | processor.stack_pointer as u16;
+    return read_unobserved(processor, memory, stack_address);
+}
+
+pub fn pull_dummy_reads<B: Bus>(processor: &mut Processor, memory: &mut B) -> () {
+    /* stack_dummy_reads() for compiled code, the byte after the opcode then the stack before SP moves */
+    let next: u16 = processor.program_counter;
+    read_unobserved(processor, memory, next);
+    let stack_address: u16 = 0x100 | processor.stack_pointer as u16;
+    read_unobserved(processor, memory, stack_address);
+}
+
+pub fn chain<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    address: u32,
+    deadline: u64,
+    dispatch: Dispatch<B>,
+) -> Option<u64> {
+    /*
+        Goes straight from each block that runs to its end into the one at the new program counter,
+        the checks between blocks being the ones made between instructions inside a block.
+    */
+    let mut address: u32 = address;
+    let mut executed: u64 = 0;
+    loop {
+        match dispatch(processor, memory, address, deadline, executed) {
+            Ok(total) => executed = total,
+            Err(0) => return None, // Nothing could start, the interpreter takes the instruction
+            Err(total) => return Some(total),
+        }
+        address = ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32;
+    }
+}
+
+pub trait CompiledExecution {
+    fn execute_compiled<P: CompiledProgram, B: Bus>(&mut self, memory: &mut B) -> ExecutionResult;
+}
+
+impl CompiledExecution for Processor {
+    fn execute_compiled<P: CompiledProgram, B: Bus>(&mut self, memory: &mut B) -> ExecutionResult {
+        /* execute() with the program's blocks tried first at every instruction boundary */
+        return execute_with(self, memory, P::run_block::<B>);
+    }
+}
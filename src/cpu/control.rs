@@ -0,0 +1,47 @@
+/// The RDY and SO inputs, held by the host alongside the interrupt lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlLines {
+    ready: bool,             // Pulled low by DMA, the processor holds on its next read cycle
+    overflow_asserted: bool, // SO, active low like the interrupt pins
+    overflow_pending: bool, // Latched on the falling edge and applied at the next instruction boundary
+}
+
+impl ControlLines {
+    pub fn new() -> ControlLines {
+        return ControlLines {
+            ready: true,
+            overflow_asserted: false,
+            overflow_pending: false,
+        };
+    }
+
+    pub fn set_ready(&mut self, ready: bool) -> () {
+        self.ready = ready;
+    }
+
+    pub fn ready(&self) -> bool {
+        return self.ready;
+    }
+
+    pub fn assert_set_overflow(&mut self) -> () {
+        if self.overflow_asserted == false {
+            self.overflow_pending = true;
+        }
+        self.overflow_asserted = true;
+    }
+
+    pub fn release_set_overflow(&mut self) -> () {
+        self.overflow_asserted = false;
+    }
+
+    pub fn set_overflow_asserted(&self) -> bool {
+        return self.overflow_asserted;
+    }
+
+    pub fn take_set_overflow(&mut self) -> bool {
+        /* Consumes the latched edge, holding SO low only sets V once */
+        let pending: bool = self.overflow_pending;
+        self.overflow_pending = false;
+        return pending;
+    }
+}
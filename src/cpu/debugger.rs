@@ -0,0 +1,170 @@
+use crate::cpu;
+
+use cpu::observer::*;
+use cpu::processor::StopReason;
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A range of addresses that stops execute() once an instruction touches it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Watchpoint {
+    pub start: u32,
+    pub end: u32, // Inclusive
+    pub operation: BusOperation,
+}
+
+/// An address with the bank switched in under it, written `bank:addr` as in `03:8000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BankedAddress {
+    pub bank: u16,
+    pub address: u32,
+}
+
+impl BankedAddress {
+    pub fn parse(text: &str) -> Option<BankedAddress> {
+        /* Both halves in hex, each with an optional $ */
+        let (bank, address) = text.trim().split_once(':')?;
+        let hex = |part: &str| -> Option<u32> {
+            let digits: &str = part.trim().trim_start_matches('$');
+            return u32::from_str_radix(digits, 16).ok();
+        };
+        let bank: u32 = hex(bank)?;
+        if bank > u16::MAX as u32 {
+            return None;
+        }
+        return Some(BankedAddress {
+            bank: bank as u16,
+            address: hex(address)?,
+        });
+    }
+}
+
+impl fmt::Display for BankedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{:02X}:{:04X}", self.bank, self.address);
+    }
+}
+
+/// Lets the host stop execute() from elsewhere, a device callback or another thread.
+#[derive(Debug, Clone)]
+pub struct HaltHandle {
+    requested: Arc<AtomicBool>,
+}
+
+impl HaltHandle {
+    pub fn request(&self) -> () {
+        self.requested.store(true, Ordering::SeqCst);
+    }
+}
+
+/// The conditions the host has asked execute() to stop on.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    pub breakpoints: BTreeSet<u32>, // Bank byte included on the 65C816
+    pub banked_breakpoints: BTreeSet<BankedAddress>, // Only hit while that bank is switched in
+    pub watchpoints: Vec<Watchpoint>,
+    pub trap_break: bool, // Stop in front of BRK instead of running it
+    halt: HaltHandle,
+    resume_address: Option<u32>, // Where the last breakpoint or trap stopped, passed over once on the way out
+    watch_hit: Option<BusAccess>,
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        return Debugger {
+            breakpoints: BTreeSet::new(),
+            banked_breakpoints: BTreeSet::new(),
+            watchpoints: Vec::new(),
+            trap_break: false,
+            halt: HaltHandle {
+                requested: Arc::new(AtomicBool::new(false)),
+            },
+            resume_address: None,
+            watch_hit: None,
+        };
+    }
+
+    pub fn watch(&mut self, start: u32, end: u32, operation: BusOperation) -> () {
+        self.watchpoints.push(Watchpoint {
+            start,
+            end,
+            operation,
+        });
+    }
+
+    pub fn halt_handle(&self) -> HaltHandle {
+        return self.halt.clone();
+    }
+
+    pub fn observe(&mut self, access: BusAccess) -> () {
+        /* Only the first hit of an instruction is kept, it is reported once the instruction finishes */
+        if self.watch_hit.is_some() {
+            return;
+        }
+
+        let watched: bool = self.watchpoints.iter().any(|watchpoint| {
+            watchpoint.operation == access.operation
+                && access.address >= watchpoint.start
+                && access.address <= watchpoint.end
+        });
+        if watched {
+            self.watch_hit = Some(access);
+        }
+    }
+
+    pub fn passes(&self, start: u32, end: u32) -> bool {
+        /* Whether check() could let every instruction from start up to end go by, a halt request aside */
+        return self.watchpoints.is_empty()
+            && self.trap_break == false
+            && self.breakpoints.range(start..end).next().is_none()
+            && self
+                .banked_breakpoints
+                .iter()
+                .all(|breakpoint| breakpoint.address < start || breakpoint.address >= end);
+    }
+
+    pub fn halt_pending(&self) -> bool {
+        return self.halt.requested.load(Ordering::Relaxed);
+    }
+
+    pub fn begin(&mut self) -> () {
+        // Hits from cycles run through tick() belong to no call of execute()
+        self.watch_hit = None;
+    }
+
+    pub fn check(&mut self, address: u32, bank: Option<u16>, opcode: u8) -> Option<StopReason> {
+        /* Looked at before every instruction, the instruction at the address has not run yet */
+        if let Some(access) = self.watch_hit.take() {
+            return Some(StopReason::Watchpoint { access });
+        }
+
+        if self.halt.requested.swap(false, Ordering::SeqCst) {
+            return Some(StopReason::HaltRequested);
+        }
+
+        if self.resume_address.take() == Some(address) {
+            return None;
+        }
+
+        let banked: bool = match bank {
+            Some(bank) => self
+                .banked_breakpoints
+                .contains(&BankedAddress { bank, address }),
+            None => false,
+        };
+        if self.breakpoints.contains(&address) || banked {
+            self.resume_address = Some(address);
+            return Some(StopReason::Breakpoint { address });
+        }
+
+        if self.trap_break && opcode == 0x00 {
+            self.resume_address = Some(address);
+            return Some(StopReason::BreakTrap { address });
+        }
+
+        return None;
+    }
+}
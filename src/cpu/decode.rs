@@ -0,0 +1,559 @@
+use super::instructions;
+use super::instructions::arithmetic::add::AddWithCarry;
+use super::instructions::arithmetic::compare::Compare;
+use super::instructions::arithmetic::subtract::SubtractWithCarry;
+use super::instructions::bits::BitManipulation;
+use super::instructions::branches::Branches;
+use super::instructions::shifts::Shifts;
+use super::instructions::system::System;
+use super::instructions::undocumented::Undocumented;
+use crate::cpu;
+use crate::mem::*;
+use std::marker::PhantomData;
+
+use instructions::decrement::*; // dec, dex, dey
+use instructions::increment::*; // inc, inx, iny
+use instructions::jumps::*; // jsr, rts, jmp
+use instructions::logical::*; // eor, or, and
+use instructions::registers::load::*; // lda, ldx, ldy
+use instructions::registers::store::*; // sta, stx, sty
+use instructions::stackops::*; // tsx, txs, pha, php, pla, plp
+use instructions::transfers::*; // tax, tay, txa, tya
+
+use cpu::functions::byte::*;
+use cpu::functions::word::*;
+use cpu::native::decode::NativeInstructions;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::LogicalOperations::*;
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::InstructionSet;
+
+/// What a decoded opcode runs. The opcode is passed along for handlers shared by a column of them.
+pub type Handler<B> = fn(&mut Processor, &mut B, u8) -> ();
+
+struct DecodeTable<B>(PhantomData<B>);
+
+impl<B: Bus> DecodeTable<B> {
+    // Built once per bus type at compile time
+    const NMOS: [Handler<B>; 256] = nmos_table::<B>();
+    const CMOS: [Handler<B>; 256] = cmos_table::<B>();
+}
+
+pub fn execute_instruction<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    instruction: u8,
+) -> () {
+    /*
+        Every variant shares the instruction traits; the variant only decides which table
+        the opcode is looked up in. The 65C816 uses the CMOS table for whatever it runs
+        exactly as a 65C02 would and its own decoder for the rest.
+    */
+    let table: &[Handler<B>; 256] = match processor.variant.instruction_set() {
+        InstructionSet::Nmos => &DecodeTable::<B>::NMOS,
+        InstructionSet::Cmos => &DecodeTable::<B>::CMOS,
+        InstructionSet::Wdc65C816 => {
+            if runs_as_65c02(processor, instruction) == false {
+                return processor.execute_native(memory, instruction);
+            }
+            &DecodeTable::<B>::CMOS
+        }
+    };
+
+    table[instruction as usize](processor, memory, instruction);
+}
+
+fn runs_as_65c02(processor: &Processor, instruction: u8) -> bool {
+    /*
+        In emulation mode with every bank and the direct page at zero, the 65C816 behaves like a
+        65C02 for the opcodes the two share, so those go through the shared decoders. Anything
+        that can see a bank, a relocated direct page or a 16-bit register goes to the native one.
+    */
+    let native = &processor.native;
+    if native.emulation == false
+        || native.direct_page != 0
+        || native.data_bank != 0
+        || native.program_bank != 0
+    {
+        return false;
+    }
+
+    match instruction {
+        MVP
+        | MVN
+        | PEI
+        | PEA
+        | JML_ABSOLUTE_LONG
+        | JML_ABSOLUTE_INDIRECT_LONG
+        | JSR_ABSOLUTE_INDEXED_INDIRECT => return false,
+        LDX_IMMEDIATE => return true,
+        _ => {}
+    }
+
+    match instruction & 0x0F {
+        0x00 | 0x01 | 0x04 | 0x05 | 0x06 | 0x08 | 0x09 | 0x0A | 0x0C | 0x0D | 0x0E => return true,
+        0x02 => return instruction & 0x10 != 0, // The (zp) column, the rest of it is new on the 65C816
+        _ => return false,
+    }
+}
+
+const fn nmos_table<B: Bus>() -> [Handler<B>; 256] {
+    /* Documented opcodes, anything else goes to the undocumented decoder */
+    let mut table: [Handler<B>; 256] = [undocumented::<B>; 256];
+
+    table[LDA_IMMEDIATE as usize] =
+        |processor, memory, _| processor.load_immediate(memory, Accumulator);
+    table[LDA_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, Accumulator, None);
+    table[LDA_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, Accumulator, Some(RegisterX));
+    table[LDA_ABSOLUTE as usize] =
+        |processor, memory, _| processor.load_absolute(memory, Accumulator, None);
+    table[LDA_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.load_absolute(memory, Accumulator, Some(RegisterX));
+    table[LDA_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.load_absolute(memory, Accumulator, Some(RegisterY));
+    table[LDA_INDIRECT_X as usize] = |processor, memory, _| processor.load_indirect_x(memory);
+    table[LDA_INDIRECT_Y as usize] = |processor, memory, _| processor.load_indirect_y(memory);
+
+    table[LDX_IMMEDIATE as usize] =
+        |processor, memory, _| processor.load_immediate(memory, RegisterX);
+    table[LDX_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, RegisterX, None);
+    table[LDX_ZERO_PAGE_Y as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, RegisterX, Some(RegisterY));
+    table[LDX_ABSOLUTE as usize] =
+        |processor, memory, _| processor.load_absolute(memory, RegisterX, None);
+    table[LDX_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.load_absolute(memory, RegisterX, Some(RegisterY));
+
+    table[LDY_IMMEDIATE as usize] =
+        |processor, memory, _| processor.load_immediate(memory, RegisterY);
+    table[LDY_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, RegisterY, None);
+    table[LDY_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.load_zero_page(memory, RegisterY, Some(RegisterX));
+    table[LDY_ABSOLUTE as usize] =
+        |processor, memory, _| processor.load_absolute(memory, RegisterY, None);
+    table[LDY_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.load_absolute(memory, RegisterY, Some(RegisterX));
+
+    table[STA_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, Accumulator, None);
+    table[STA_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, Accumulator, Some(RegisterX));
+    table[STA_ABSOLUTE as usize] =
+        |processor, memory, _| processor.store_absolute(memory, Accumulator, None);
+    table[STA_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.store_absolute(memory, Accumulator, Some(RegisterX));
+    table[STA_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.store_absolute(memory, Accumulator, Some(RegisterY));
+    table[STA_INDIRECT_X as usize] = |processor, memory, _| processor.store_indirect_x(memory);
+    table[STA_INDIRECT_Y as usize] = |processor, memory, _| processor.store_indirect_y(memory);
+
+    table[STX_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, RegisterX, None);
+    table[STX_ZERO_PAGE_Y as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, RegisterX, Some(RegisterY));
+    table[STX_ABSOLUTE as usize] =
+        |processor, memory, _| processor.store_absolute(memory, RegisterX, None);
+
+    table[STY_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, RegisterY, None);
+    table[STY_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.store_zero_page(memory, RegisterY, Some(RegisterX));
+    table[STY_ABSOLUTE as usize] =
+        |processor, memory, _| processor.store_absolute(memory, RegisterY, None);
+
+    table[JSR as usize] = |processor, memory, _| processor.jsr(memory);
+    table[RTS as usize] = |processor, memory, _| processor.rts(memory);
+    table[JMP_ABSOLUTE as usize] = |processor, memory, _| processor.jump_absolute(memory);
+    table[JMP_INDIRECT as usize] = |processor, memory, _| processor.jump_indirect(memory);
+
+    table[TSX as usize] = |processor, _, _| processor.tsx();
+    table[TXS as usize] = |processor, _, _| processor.txs();
+    table[PHA as usize] = |processor, memory, _| processor.pha(memory);
+    table[PHP as usize] = |processor, memory, _| processor.php(memory);
+    table[PLA as usize] = |processor, memory, _| processor.pla(memory);
+    table[PLP as usize] = |processor, memory, _| processor.plp(memory);
+
+    table[AND_IMMEDIATE as usize] = |processor, memory, _| processor.logic_immediate(memory, And);
+    table[AND_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, And, None);
+    table[AND_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, And, Some(RegisterX));
+    table[AND_ABSOLUTE as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, And, None);
+    table[AND_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, And, Some(RegisterX));
+    table[AND_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, And, Some(RegisterY));
+    table[AND_INDIRECT_X as usize] = |processor, memory, _| processor.logic_indirect_x(memory, And);
+    table[AND_INDIRECT_Y as usize] = |processor, memory, _| processor.logic_indirect_y(memory, And);
+
+    table[OR_IMMEDIATE as usize] = |processor, memory, _| processor.logic_immediate(memory, Or);
+    table[OR_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, Or, None);
+    table[OR_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, Or, Some(RegisterX));
+    table[OR_ABSOLUTE as usize] = |processor, memory, _| processor.logic_absolute(memory, Or, None);
+    table[OR_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, Or, Some(RegisterX));
+    table[OR_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, Or, Some(RegisterY));
+    table[OR_INDIRECT_X as usize] = |processor, memory, _| processor.logic_indirect_x(memory, Or);
+    table[OR_INDIRECT_Y as usize] = |processor, memory, _| processor.logic_indirect_y(memory, Or);
+
+    table[EOR_IMMEDIATE as usize] =
+        |processor, memory, _| processor.logic_immediate(memory, ExclusiveOr);
+    table[EOR_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, ExclusiveOr, None);
+    table[EOR_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.logic_zero_page(memory, ExclusiveOr, Some(RegisterX));
+    table[EOR_ABSOLUTE as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, ExclusiveOr, None);
+    table[EOR_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, ExclusiveOr, Some(RegisterX));
+    table[EOR_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.logic_absolute(memory, ExclusiveOr, Some(RegisterY));
+    table[EOR_INDIRECT_X as usize] =
+        |processor, memory, _| processor.logic_indirect_x(memory, ExclusiveOr);
+    table[EOR_INDIRECT_Y as usize] =
+        |processor, memory, _| processor.logic_indirect_y(memory, ExclusiveOr);
+
+    table[BIT_ZERO_PAGE as usize] = |processor, memory, _| processor.bit_zero_page(memory, None);
+    table[BIT_ABSOLUTE as usize] = |processor, memory, _| processor.bit_absolute(memory, None);
+
+    table[TAX as usize] = |processor, _, _| processor.transfer_accumulator_to_x();
+    table[TAY as usize] = |processor, _, _| processor.transfer_accumulator_to_y();
+    table[TXA as usize] = |processor, _, _| processor.transfer_x_to_accumulator();
+    table[TYA as usize] = |processor, _, _| processor.transfer_y_to_accumulator();
+
+    table[INX as usize] = |processor, _, _| processor.increment_x();
+    table[INY as usize] = |processor, _, _| processor.increment_y();
+    table[INC_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.increment_memory_zero_page(memory, None);
+    table[INC_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.increment_memory_zero_page(memory, Some(RegisterX));
+    table[INC_ABSOLUTE as usize] =
+        |processor, memory, _| processor.increment_memory_absolute(memory, None);
+    table[INC_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.increment_memory_absolute(memory, Some(RegisterX));
+
+    table[DEX as usize] = |processor, _, _| processor.decrement_x();
+    table[DEY as usize] = |processor, _, _| processor.decrement_y();
+    table[DEC_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.decrement_memory_zero_page(memory, None);
+    table[DEC_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.decrement_memory_zero_page(memory, Some(RegisterX));
+    table[DEC_ABSOLUTE as usize] =
+        |processor, memory, _| processor.decrement_memory_absolute(memory, None);
+    table[DEC_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.decrement_memory_absolute(memory, Some(RegisterX));
+
+    table[BEQ as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(ZeroFlag));
+    table[BNE as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(ZeroFlag) == false);
+    table[BCS as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(CarryFlag));
+    table[BCC as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(CarryFlag) == false);
+    table[BMI as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(NegativeFlag));
+    table[BPL as usize] = |processor, memory, _| {
+        processor.branch(memory, processor.fetch_status(NegativeFlag) == false)
+    };
+    table[BVS as usize] =
+        |processor, memory, _| processor.branch(memory, processor.fetch_status(OverflowFlag));
+    table[BVC as usize] = |processor, memory, _| {
+        processor.branch(memory, processor.fetch_status(OverflowFlag) == false)
+    };
+
+    table[NOP as usize] = |processor, _, _| processor.decrement_cycles(1);
+    table[CLC as usize] = |processor, _, _| {
+        processor.set_status(CarryFlag, false);
+        processor.decrement_cycles(1);
+    };
+    table[CLD as usize] = |processor, _, _| {
+        processor.set_status(DecimalMode, false);
+        processor.decrement_cycles(1);
+    };
+    table[CLI as usize] = |processor, _, _| {
+        processor.latch_interrupt_disable();
+        processor.set_status(InterruptDisable, false);
+        processor.decrement_cycles(1);
+    };
+    table[CLV as usize] = |processor, _, _| {
+        processor.set_status(OverflowFlag, false);
+        processor.decrement_cycles(1);
+    };
+    table[SEC as usize] = |processor, _, _| {
+        processor.set_status(CarryFlag, true);
+        processor.decrement_cycles(1);
+    };
+    table[SED as usize] = |processor, _, _| {
+        processor.set_status(DecimalMode, true);
+        processor.decrement_cycles(1);
+    };
+    table[SEI as usize] = |processor, _, _| {
+        processor.latch_interrupt_disable();
+        processor.set_status(InterruptDisable, true);
+        processor.decrement_cycles(1);
+    };
+
+    table[ADC_IMMEDIATE as usize] = |processor, memory, _| processor.adc_immediate(memory);
+    table[ADC_ABSOLUTE as usize] = |processor, memory, _| processor.adc_absolute(memory, None);
+    table[ADC_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.adc_absolute(memory, Some(RegisterX));
+    table[ADC_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.adc_absolute(memory, Some(RegisterY));
+    table[ADC_ZERO_PAGE as usize] = |processor, memory, _| processor.adc_zero_page(memory, None);
+    table[ADC_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.adc_zero_page(memory, Some(RegisterX));
+    table[ADC_INDIRECT_X as usize] = |processor, memory, _| processor.adc_indirect_x(memory);
+    table[ADC_INDIRECT_Y as usize] = |processor, memory, _| processor.adc_indirect_y(memory);
+
+    table[CMP_IMMEDIATE as usize] =
+        |processor, memory, _| processor.cmp_immediate(memory, Accumulator);
+    table[CMP_ABSOLUTE as usize] =
+        |processor, memory, _| processor.cmp_absolute(memory, Accumulator, None);
+    table[CMP_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.cmp_absolute(memory, Accumulator, Some(RegisterX));
+    table[CMP_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.cmp_absolute(memory, Accumulator, Some(RegisterY));
+    table[CMP_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.cmp_zero_page(memory, Accumulator, None);
+    table[CMP_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.cmp_zero_page(memory, Accumulator, Some(RegisterX));
+    table[CMP_INDIRECT_X as usize] =
+        |processor, memory, _| processor.cmp_indirect_x(memory, Accumulator);
+    table[CMP_INDIRECT_Y as usize] =
+        |processor, memory, _| processor.cmp_indirect_y(memory, Accumulator);
+
+    table[CPX_IMMEDIATE as usize] =
+        |processor, memory, _| processor.cmp_immediate(memory, RegisterX);
+    table[CPX_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.cmp_zero_page(memory, RegisterX, None);
+    table[CPX_ABSOLUTE as usize] =
+        |processor, memory, _| processor.cmp_absolute(memory, RegisterX, None);
+
+    table[CPY_IMMEDIATE as usize] =
+        |processor, memory, _| processor.cmp_immediate(memory, RegisterY);
+    table[CPY_ZERO_PAGE as usize] =
+        |processor, memory, _| processor.cmp_zero_page(memory, RegisterY, None);
+    table[CPY_ABSOLUTE as usize] =
+        |processor, memory, _| processor.cmp_absolute(memory, RegisterY, None);
+
+    table[SBC_IMMEDIATE as usize] = |processor, memory, _| processor.sbc_immediate(memory);
+    table[SBC_ABSOLUTE as usize] = |processor, memory, _| processor.sbc_absolute(memory, None);
+    table[SBC_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.sbc_absolute(memory, Some(RegisterX));
+    table[SBC_ABSOLUTE_Y as usize] =
+        |processor, memory, _| processor.sbc_absolute(memory, Some(RegisterY));
+    table[SBC_ZERO_PAGE as usize] = |processor, memory, _| processor.sbc_zero_page(memory, None);
+    table[SBC_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.sbc_zero_page(memory, Some(RegisterX));
+    table[SBC_INDIRECT_X as usize] = |processor, memory, _| processor.sbc_indirect_x(memory);
+    table[SBC_INDIRECT_Y as usize] = |processor, memory, _| processor.sbc_indirect_y(memory);
+
+    table[ASL_ACCUMULATOR as usize] =
+        |processor, memory, opcode| processor.shift_left(memory, opcode, Implied);
+    table[ASL_ZERO_PAGE as usize] =
+        |processor, memory, opcode| processor.shift_left(memory, opcode, ZeroPage);
+    table[ASL_ZERO_PAGE_X as usize] =
+        |processor, memory, opcode| processor.shift_left(memory, opcode, ZeroPageX);
+    table[ASL_ABSOLUTE as usize] =
+        |processor, memory, opcode| processor.shift_left(memory, opcode, Absolute);
+    table[ASL_ABSOLUTE_X as usize] =
+        |processor, memory, opcode| processor.shift_left(memory, opcode, AbsoluteX);
+
+    table[LSR_ACCUMULATOR as usize] =
+        |processor, memory, opcode| processor.shift_right(memory, opcode, Implied);
+    table[LSR_ZERO_PAGE as usize] =
+        |processor, memory, opcode| processor.shift_right(memory, opcode, ZeroPage);
+    table[LSR_ZERO_PAGE_X as usize] =
+        |processor, memory, opcode| processor.shift_right(memory, opcode, ZeroPageX);
+    table[LSR_ABSOLUTE as usize] =
+        |processor, memory, opcode| processor.shift_right(memory, opcode, Absolute);
+    table[LSR_ABSOLUTE_X as usize] =
+        |processor, memory, opcode| processor.shift_right(memory, opcode, AbsoluteX);
+
+    table[ROL_ACCUMULATOR as usize] =
+        |processor, memory, opcode| processor.rotate_left(memory, opcode, Implied);
+    table[ROL_ZERO_PAGE as usize] =
+        |processor, memory, opcode| processor.rotate_left(memory, opcode, ZeroPage);
+    table[ROL_ZERO_PAGE_X as usize] =
+        |processor, memory, opcode| processor.rotate_left(memory, opcode, ZeroPageX);
+    table[ROL_ABSOLUTE as usize] =
+        |processor, memory, opcode| processor.rotate_left(memory, opcode, Absolute);
+    table[ROL_ABSOLUTE_X as usize] =
+        |processor, memory, opcode| processor.rotate_left(memory, opcode, AbsoluteX);
+
+    table[ROR_ACCUMULATOR as usize] =
+        |processor, memory, opcode| processor.rotate_right(memory, opcode, Implied);
+    table[ROR_ZERO_PAGE as usize] =
+        |processor, memory, opcode| processor.rotate_right(memory, opcode, ZeroPage);
+    table[ROR_ZERO_PAGE_X as usize] =
+        |processor, memory, opcode| processor.rotate_right(memory, opcode, ZeroPageX);
+    table[ROR_ABSOLUTE as usize] =
+        |processor, memory, opcode| processor.rotate_right(memory, opcode, Absolute);
+    table[ROR_ABSOLUTE_X as usize] =
+        |processor, memory, opcode| processor.rotate_right(memory, opcode, AbsoluteX);
+
+    table[BRK as usize] = |processor, memory, _| processor.force_interrupt(memory);
+    table[RTI as usize] = |processor, memory, _| processor.return_from_interrupt(memory);
+
+    return table;
+}
+
+fn undocumented<B: Bus>(processor: &mut Processor, memory: &mut B, instruction: u8) -> () {
+    processor.undocumented(memory, instruction);
+}
+
+const fn cmos_table<B: Bus>() -> [Handler<B>; 256] {
+    /*
+        Starts from the NMOS table, as the two agree on all documented instructions,
+        then lays the opcodes the 65C02 added or redefined over it.
+    */
+    let mut table: [Handler<B>; 256] = nmos_table::<B>();
+
+    let mut opcode: usize = 0;
+    while opcode < 256 {
+        if is_cmos_nop(opcode as u8) {
+            table[opcode] = cmos_nop::<B>;
+        }
+        opcode += 1;
+    }
+
+    table[BRA as usize] = |processor, memory, _| processor.branch(memory, true);
+    table[PHX as usize] = |processor, memory, _| processor.phx(memory);
+    table[PHY as usize] = |processor, memory, _| processor.phy(memory);
+    table[PLX as usize] = |processor, memory, _| processor.plx(memory);
+    table[PLY as usize] = |processor, memory, _| processor.ply(memory);
+
+    table[STZ_ZERO_PAGE as usize] = |processor, memory, _| processor.stz_zero_page(memory, None);
+    table[STZ_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.stz_zero_page(memory, Some(RegisterX));
+    table[STZ_ABSOLUTE as usize] = |processor, memory, _| processor.stz_absolute(memory, None);
+    table[STZ_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.stz_absolute(memory, Some(RegisterX));
+
+    table[TRB_ZERO_PAGE as usize] = |processor, memory, _| processor.trb_zero_page(memory);
+    table[TRB_ABSOLUTE as usize] = |processor, memory, _| processor.trb_absolute(memory);
+    table[TSB_ZERO_PAGE as usize] = |processor, memory, _| processor.tsb_zero_page(memory);
+    table[TSB_ABSOLUTE as usize] = |processor, memory, _| processor.tsb_absolute(memory);
+
+    table[OR_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.logic_zero_page_indirect(memory, Or);
+    table[AND_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.logic_zero_page_indirect(memory, And);
+    table[EOR_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.logic_zero_page_indirect(memory, ExclusiveOr);
+    table[ADC_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.adc_zero_page_indirect(memory);
+    table[STA_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.store_zero_page_indirect(memory);
+    table[LDA_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.load_zero_page_indirect(memory);
+    table[CMP_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.cmp_zero_page_indirect(memory, Accumulator);
+    table[SBC_ZERO_PAGE_INDIRECT as usize] =
+        |processor, memory, _| processor.sbc_zero_page_indirect(memory);
+
+    table[BIT_IMMEDIATE as usize] = |processor, memory, _| processor.bit_immediate(memory);
+    table[BIT_ZERO_PAGE_X as usize] =
+        |processor, memory, _| processor.bit_zero_page(memory, Some(RegisterX));
+    table[BIT_ABSOLUTE_X as usize] =
+        |processor, memory, _| processor.bit_absolute(memory, Some(RegisterX));
+
+    table[INC_ACCUMULATOR as usize] = |processor, _, _| processor.increment_accumulator();
+    table[DEC_ACCUMULATOR as usize] = |processor, _, _| processor.decrement_accumulator();
+
+    table[JMP_ABSOLUTE_INDEXED_INDIRECT as usize] =
+        |processor, memory, _| processor.jump_indexed_indirect(memory);
+
+    // Each of these is a column of eight, the bit number is in the high nibble
+    let resets: [u8; 8] = [RMB0, RMB1, RMB2, RMB3, RMB4, RMB5, RMB6, RMB7];
+    let sets: [u8; 8] = [SMB0, SMB1, SMB2, SMB3, SMB4, SMB5, SMB6, SMB7];
+    let branches_reset: [u8; 8] = [BBR0, BBR1, BBR2, BBR3, BBR4, BBR5, BBR6, BBR7];
+    let branches_set: [u8; 8] = [BBS0, BBS1, BBS2, BBS3, BBS4, BBS5, BBS6, BBS7];
+    let mut row: usize = 0;
+    while row < 8 {
+        table[resets[row] as usize] = |processor, memory, instruction| {
+            if processor.variant.has_bit_instructions() {
+                processor.reset_memory_bit(memory, instruction >> 4)
+            }
+        };
+        table[sets[row] as usize] = |processor, memory, instruction| {
+            if processor.variant.has_bit_instructions() {
+                processor.set_memory_bit(memory, (instruction >> 4) & 0x07)
+            }
+        };
+        table[branches_reset[row] as usize] = |processor, memory, instruction| {
+            if processor.variant.has_bit_instructions() {
+                processor.branch_on_bit_reset(memory, instruction >> 4)
+            }
+        };
+        table[branches_set[row] as usize] = |processor, memory, instruction| {
+            if processor.variant.has_bit_instructions() {
+                processor.branch_on_bit_set(memory, (instruction >> 4) & 0x07)
+            }
+        };
+        row += 1;
+    }
+
+    table[WAI as usize] = |processor, _, _| {
+        if processor.variant.has_wait_and_stop() {
+            processor.wait_for_interrupt()
+        }
+    };
+    table[STP as usize] = |processor, _, _| {
+        if processor.variant.has_wait_and_stop() {
+            processor.stop()
+        }
+    };
+
+    return table;
+}
+
+const fn is_cmos_nop(instruction: u8) -> bool {
+    match instruction {
+        0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => return true,
+        0x44 | 0x54 | 0xD4 | 0xF4 | 0x5C | 0xDC | 0xFC => return true,
+        _ => return matches!(instruction & 0x0F, 0x03 | 0x0B | 0x07 | 0x0F),
+    }
+}
+
+fn cmos_nop<B: Bus>(processor: &mut Processor, memory: &mut B, instruction: u8) -> () {
+    /* Every opcode the 65C02 leaves undefined is a NOP with a fixed length and cycle count */
+    match instruction {
+        0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => {
+            processor.fetch_byte(memory);
+        }
+        0x44 => {
+            let zero_page_addr = processor.fetch_byte(memory);
+            processor.read_byte(memory, zero_page_addr as u16);
+        }
+        0x54 | 0xD4 | 0xF4 => {
+            let zero_page_addr = processor.fetch_byte(memory);
+            processor.read_byte(memory, zero_page_addr as u16);
+            processor.read_byte(
+                memory,
+                zero_page_addr.wrapping_add(processor.register_x) as u16,
+            );
+        }
+        0x5C => {
+            processor.fetch_word(memory);
+            processor.decrement_cycles(5);
+        }
+        0xDC | 0xFC => {
+            let absolute_addr = processor.fetch_word(memory);
+            processor.read_byte(memory, absolute_addr);
+        }
+        _ => {} // Single byte opcodes, and the bit instructions on variants without them
+    }
+}
@@ -0,0 +1,320 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::native::registers::WideRegisters;
+use cpu::processor::Processor;
+use cpu::variant::{InstructionSet, Variant};
+use std::fmt;
+
+/// How the bytes that follow an opcode are read and written out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    Implied,
+    Accumulator,
+    Immediate,
+    ImmediateAccumulator, // Two bytes while the 65C816 accumulator is 16 bits wide
+    ImmediateIndex,       // Two bytes while the 65C816 index registers are 16 bits wide
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,
+    IndirectX,
+    IndirectY,
+    ZeroPageIndirect,
+    AbsoluteIndexedIndirect,
+    Relative,
+    RelativeLong,
+    ZeroPageRelative, // BBR and BBS, a zero page address then a branch offset
+    DirectIndirectLong,
+    DirectIndirectLongY,
+    AbsoluteLong,
+    AbsoluteLongX,
+    AbsoluteIndirectLong,
+    StackRelative,
+    StackRelativeIndirectY,
+    BlockMove,
+}
+
+use Operand::*;
+
+// Short names keep the operand tables lined up with the mnemonic tables
+const IMP: Operand = Implied;
+const ACC: Operand = Accumulator;
+const IMM: Operand = Immediate;
+const IMA: Operand = ImmediateAccumulator;
+const IMX: Operand = ImmediateIndex;
+const ZPG: Operand = ZeroPage;
+const ZPX: Operand = ZeroPageX;
+const ZPY: Operand = ZeroPageY;
+const ABS: Operand = Absolute;
+const ABX: Operand = AbsoluteX;
+const ABY: Operand = AbsoluteY;
+const IND: Operand = Indirect;
+const IZX: Operand = IndirectX;
+const IZY: Operand = IndirectY;
+const ZPI: Operand = ZeroPageIndirect;
+const AIX: Operand = AbsoluteIndexedIndirect;
+const REL: Operand = Relative;
+const RLL: Operand = RelativeLong;
+const ZPR: Operand = ZeroPageRelative;
+const DIL: Operand = DirectIndirectLong;
+const DLY: Operand = DirectIndirectLongY;
+const LNG: Operand = AbsoluteLong;
+const LGX: Operand = AbsoluteLongX;
+const AIL: Operand = AbsoluteIndirectLong;
+const SRL: Operand = StackRelative;
+const SRY: Operand = StackRelativeIndirectY;
+const BLK: Operand = BlockMove;
+
+#[rustfmt::skip]
+const NMOS_MNEMONICS: [&str; 256] = [
+//   0      1      2      3      4      5      6      7      8      9      A      B      C      D      E      F
+    "BRK", "ORA", "JAM", "SLO", "NOP", "ORA", "ASL", "SLO", "PHP", "ORA", "ASL", "ANC", "NOP", "ORA", "ASL", "SLO", // 0
+    "BPL", "ORA", "JAM", "SLO", "NOP", "ORA", "ASL", "SLO", "CLC", "ORA", "NOP", "SLO", "NOP", "ORA", "ASL", "SLO", // 1
+    "JSR", "AND", "JAM", "RLA", "BIT", "AND", "ROL", "RLA", "PLP", "AND", "ROL", "ANC", "BIT", "AND", "ROL", "RLA", // 2
+    "BMI", "AND", "JAM", "RLA", "NOP", "AND", "ROL", "RLA", "SEC", "AND", "NOP", "RLA", "NOP", "AND", "ROL", "RLA", // 3
+    "RTI", "EOR", "JAM", "SRE", "NOP", "EOR", "LSR", "SRE", "PHA", "EOR", "LSR", "ALR", "JMP", "EOR", "LSR", "SRE", // 4
+    "BVC", "EOR", "JAM", "SRE", "NOP", "EOR", "LSR", "SRE", "CLI", "EOR", "NOP", "SRE", "NOP", "EOR", "LSR", "SRE", // 5
+    "RTS", "ADC", "JAM", "RRA", "NOP", "ADC", "ROR", "RRA", "PLA", "ADC", "ROR", "ARR", "JMP", "ADC", "ROR", "RRA", // 6
+    "BVS", "ADC", "JAM", "RRA", "NOP", "ADC", "ROR", "RRA", "SEI", "ADC", "NOP", "RRA", "NOP", "ADC", "ROR", "RRA", // 7
+    "NOP", "STA", "NOP", "SAX", "STY", "STA", "STX", "SAX", "DEY", "NOP", "TXA", "XAA", "STY", "STA", "STX", "SAX", // 8
+    "BCC", "STA", "JAM", "AHX", "STY", "STA", "STX", "SAX", "TYA", "STA", "TXS", "TAS", "SHY", "STA", "SHX", "AHX", // 9
+    "LDY", "LDA", "LDX", "LAX", "LDY", "LDA", "LDX", "LAX", "TAY", "LDA", "TAX", "LXA", "LDY", "LDA", "LDX", "LAX", // A
+    "BCS", "LDA", "JAM", "LAX", "LDY", "LDA", "LDX", "LAX", "CLV", "LDA", "TSX", "LAS", "LDY", "LDA", "LDX", "LAX", // B
+    "CPY", "CMP", "NOP", "DCP", "CPY", "CMP", "DEC", "DCP", "INY", "CMP", "DEX", "SBX", "CPY", "CMP", "DEC", "DCP", // C
+    "BNE", "CMP", "JAM", "DCP", "NOP", "CMP", "DEC", "DCP", "CLD", "CMP", "NOP", "DCP", "NOP", "CMP", "DEC", "DCP", // D
+    "CPX", "SBC", "NOP", "ISC", "CPX", "SBC", "INC", "ISC", "INX", "SBC", "NOP", "SBC", "CPX", "SBC", "INC", "ISC", // E
+    "BEQ", "SBC", "JAM", "ISC", "NOP", "SBC", "INC", "ISC", "SED", "SBC", "NOP", "ISC", "NOP", "SBC", "INC", "ISC", // F
+];
+
+#[rustfmt::skip]
+const NMOS_OPERANDS: [Operand; 256] = [
+//   0    1    2    3    4    5    6    7    8    9    A    B    C    D    E    F
+    IMP, IZX, IMP, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMM, ABS, ABS, ABS, ABS, // 0
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // 1
+    ABS, IZX, IMP, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMM, ABS, ABS, ABS, ABS, // 2
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // 3
+    IMP, IZX, IMP, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMM, ABS, ABS, ABS, ABS, // 4
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // 5
+    IMP, IZX, IMP, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMM, IND, ABS, ABS, ABS, // 6
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // 7
+    IMM, IZX, IMM, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMM, ABS, ABS, ABS, ABS, // 8
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPY, ZPY, IMP, ABY, IMP, ABY, ABX, ABX, ABY, ABY, // 9
+    IMM, IZX, IMM, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMM, ABS, ABS, ABS, ABS, // A
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPY, ZPY, IMP, ABY, IMP, ABY, ABX, ABX, ABY, ABY, // B
+    IMM, IZX, IMM, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMM, ABS, ABS, ABS, ABS, // C
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // D
+    IMM, IZX, IMM, IZX, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMM, ABS, ABS, ABS, ABS, // E
+    REL, IZY, IMP, IZY, ZPX, ZPX, ZPX, ZPX, IMP, ABY, IMP, ABY, ABX, ABX, ABX, ABX, // F
+];
+
+#[rustfmt::skip]
+const CMOS_MNEMONICS: [&str; 256] = [
+//   0      1      2      3      4      5      6      7       8      9      A      B      C      D      E      F
+    "BRK", "ORA", "NOP", "NOP", "TSB", "ORA", "ASL", "RMB0", "PHP", "ORA", "ASL", "NOP", "TSB", "ORA", "ASL", "BBR0", // 0
+    "BPL", "ORA", "ORA", "NOP", "TRB", "ORA", "ASL", "RMB1", "CLC", "ORA", "INC", "NOP", "TRB", "ORA", "ASL", "BBR1", // 1
+    "JSR", "AND", "NOP", "NOP", "BIT", "AND", "ROL", "RMB2", "PLP", "AND", "ROL", "NOP", "BIT", "AND", "ROL", "BBR2", // 2
+    "BMI", "AND", "AND", "NOP", "BIT", "AND", "ROL", "RMB3", "SEC", "AND", "DEC", "NOP", "BIT", "AND", "ROL", "BBR3", // 3
+    "RTI", "EOR", "NOP", "NOP", "NOP", "EOR", "LSR", "RMB4", "PHA", "EOR", "LSR", "NOP", "JMP", "EOR", "LSR", "BBR4", // 4
+    "BVC", "EOR", "EOR", "NOP", "NOP", "EOR", "LSR", "RMB5", "CLI", "EOR", "PHY", "NOP", "NOP", "EOR", "LSR", "BBR5", // 5
+    "RTS", "ADC", "NOP", "NOP", "STZ", "ADC", "ROR", "RMB6", "PLA", "ADC", "ROR", "NOP", "JMP", "ADC", "ROR", "BBR6", // 6
+    "BVS", "ADC", "ADC", "NOP", "STZ", "ADC", "ROR", "RMB7", "SEI", "ADC", "PLY", "NOP", "JMP", "ADC", "ROR", "BBR7", // 7
+    "BRA", "STA", "NOP", "NOP", "STY", "STA", "STX", "SMB0", "DEY", "BIT", "TXA", "NOP", "STY", "STA", "STX", "BBS0", // 8
+    "BCC", "STA", "STA", "NOP", "STY", "STA", "STX", "SMB1", "TYA", "STA", "TXS", "NOP", "STZ", "STA", "STZ", "BBS1", // 9
+    "LDY", "LDA", "LDX", "NOP", "LDY", "LDA", "LDX", "SMB2", "TAY", "LDA", "TAX", "NOP", "LDY", "LDA", "LDX", "BBS2", // A
+    "BCS", "LDA", "LDA", "NOP", "LDY", "LDA", "LDX", "SMB3", "CLV", "LDA", "TSX", "NOP", "LDY", "LDA", "LDX", "BBS3", // B
+    "CPY", "CMP", "NOP", "NOP", "CPY", "CMP", "DEC", "SMB4", "INY", "CMP", "DEX", "WAI", "CPY", "CMP", "DEC", "BBS4", // C
+    "BNE", "CMP", "CMP", "NOP", "NOP", "CMP", "DEC", "SMB5", "CLD", "CMP", "PHX", "STP", "NOP", "CMP", "DEC", "BBS5", // D
+    "CPX", "SBC", "NOP", "NOP", "CPX", "SBC", "INC", "SMB6", "INX", "SBC", "NOP", "NOP", "CPX", "SBC", "INC", "BBS6", // E
+    "BEQ", "SBC", "SBC", "NOP", "NOP", "SBC", "INC", "SMB7", "SED", "SBC", "PLX", "NOP", "NOP", "SBC", "INC", "BBS7", // F
+];
+
+#[rustfmt::skip]
+const CMOS_OPERANDS: [Operand; 256] = [
+//   0    1    2    3    4    5    6    7    8    9    A    B    C    D    E    F
+    IMP, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMP, ABS, ABS, ABS, ZPR, // 0
+    REL, IZY, ZPI, IMP, ZPG, ZPX, ZPX, ZPG, IMP, ABY, ACC, IMP, ABS, ABX, ABX, ZPR, // 1
+    ABS, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMP, ABS, ABS, ABS, ZPR, // 2
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPX, ZPG, IMP, ABY, ACC, IMP, ABX, ABX, ABX, ZPR, // 3
+    IMP, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMP, ABS, ABS, ABS, ZPR, // 4
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPX, ZPG, IMP, ABY, IMP, IMP, ABS, ABX, ABX, ZPR, // 5
+    IMP, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, ACC, IMP, IND, ABS, ABS, ZPR, // 6
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPX, ZPG, IMP, ABY, IMP, IMP, AIX, ABX, ABX, ZPR, // 7
+    REL, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMP, ABS, ABS, ABS, ZPR, // 8
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPY, ZPG, IMP, ABY, IMP, IMP, ABS, ABX, ABX, ZPR, // 9
+    IMM, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMP, ABS, ABS, ABS, ZPR, // A
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPY, ZPG, IMP, ABY, IMP, IMP, ABX, ABX, ABY, ZPR, // B
+    IMM, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMP, ABS, ABS, ABS, ZPR, // C
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPX, ZPG, IMP, ABY, IMP, IMP, ABS, ABX, ABX, ZPR, // D
+    IMM, IZX, IMM, IMP, ZPG, ZPG, ZPG, ZPG, IMP, IMM, IMP, IMP, ABS, ABS, ABS, ZPR, // E
+    REL, IZY, ZPI, IMP, ZPX, ZPX, ZPX, ZPG, IMP, ABY, IMP, IMP, ABS, ABX, ABX, ZPR, // F
+];
+
+#[rustfmt::skip]
+const W65C816_MNEMONICS: [&str; 256] = [
+//   0      1      2      3      4      5      6      7      8      9      A      B      C      D      E      F
+    "BRK", "ORA", "COP", "ORA", "TSB", "ORA", "ASL", "ORA", "PHP", "ORA", "ASL", "PHD", "TSB", "ORA", "ASL", "ORA", // 0
+    "BPL", "ORA", "ORA", "ORA", "TRB", "ORA", "ASL", "ORA", "CLC", "ORA", "INC", "TCS", "TRB", "ORA", "ASL", "ORA", // 1
+    "JSR", "AND", "JSL", "AND", "BIT", "AND", "ROL", "AND", "PLP", "AND", "ROL", "PLD", "BIT", "AND", "ROL", "AND", // 2
+    "BMI", "AND", "AND", "AND", "BIT", "AND", "ROL", "AND", "SEC", "AND", "DEC", "TSC", "BIT", "AND", "ROL", "AND", // 3
+    "RTI", "EOR", "WDM", "EOR", "MVP", "EOR", "LSR", "EOR", "PHA", "EOR", "LSR", "PHK", "JMP", "EOR", "LSR", "EOR", // 4
+    "BVC", "EOR", "EOR", "EOR", "MVN", "EOR", "LSR", "EOR", "CLI", "EOR", "PHY", "TCD", "JML", "EOR", "LSR", "EOR", // 5
+    "RTS", "ADC", "PER", "ADC", "STZ", "ADC", "ROR", "ADC", "PLA", "ADC", "ROR", "RTL", "JMP", "ADC", "ROR", "ADC", // 6
+    "BVS", "ADC", "ADC", "ADC", "STZ", "ADC", "ROR", "ADC", "SEI", "ADC", "PLY", "TDC", "JMP", "ADC", "ROR", "ADC", // 7
+    "BRA", "STA", "BRL", "STA", "STY", "STA", "STX", "STA", "DEY", "BIT", "TXA", "PHB", "STY", "STA", "STX", "STA", // 8
+    "BCC", "STA", "STA", "STA", "STY", "STA", "STX", "STA", "TYA", "STA", "TXS", "TXY", "STZ", "STA", "STZ", "STA", // 9
+    "LDY", "LDA", "LDX", "LDA", "LDY", "LDA", "LDX", "LDA", "TAY", "LDA", "TAX", "PLB", "LDY", "LDA", "LDX", "LDA", // A
+    "BCS", "LDA", "LDA", "LDA", "LDY", "LDA", "LDX", "LDA", "CLV", "LDA", "TSX", "TYX", "LDY", "LDA", "LDX", "LDA", // B
+    "CPY", "CMP", "REP", "CMP", "CPY", "CMP", "DEC", "CMP", "INY", "CMP", "DEX", "WAI", "CPY", "CMP", "DEC", "CMP", // C
+    "BNE", "CMP", "CMP", "CMP", "PEI", "CMP", "DEC", "CMP", "CLD", "CMP", "PHX", "STP", "JML", "CMP", "DEC", "CMP", // D
+    "CPX", "SBC", "SEP", "SBC", "CPX", "SBC", "INC", "SBC", "INX", "SBC", "NOP", "XBA", "CPX", "SBC", "INC", "SBC", // E
+    "BEQ", "SBC", "SBC", "SBC", "PEA", "SBC", "INC", "SBC", "SED", "SBC", "PLX", "XCE", "JSR", "SBC", "INC", "SBC", // F
+];
+
+#[rustfmt::skip]
+const W65C816_OPERANDS: [Operand; 256] = [
+//   0    1    2    3    4    5    6    7    8    9    A    B    C    D    E    F
+    IMM, IZX, IMM, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, ACC, IMP, ABS, ABS, ABS, LNG, // 0
+    REL, IZY, ZPI, SRY, ZPG, ZPX, ZPX, DLY, IMP, ABY, ACC, IMP, ABS, ABX, ABX, LGX, // 1
+    ABS, IZX, LNG, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, ACC, IMP, ABS, ABS, ABS, LNG, // 2
+    REL, IZY, ZPI, SRY, ZPX, ZPX, ZPX, DLY, IMP, ABY, ACC, IMP, ABX, ABX, ABX, LGX, // 3
+    IMP, IZX, IMM, SRL, BLK, ZPG, ZPG, DIL, IMP, IMA, ACC, IMP, ABS, ABS, ABS, LNG, // 4
+    REL, IZY, ZPI, SRY, BLK, ZPX, ZPX, DLY, IMP, ABY, IMP, IMP, LNG, ABX, ABX, LGX, // 5
+    IMP, IZX, RLL, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, ACC, IMP, IND, ABS, ABS, LNG, // 6
+    REL, IZY, ZPI, SRY, ZPX, ZPX, ZPX, DLY, IMP, ABY, IMP, IMP, AIX, ABX, ABX, LGX, // 7
+    REL, IZX, RLL, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, IMP, IMP, ABS, ABS, ABS, LNG, // 8
+    REL, IZY, ZPI, SRY, ZPX, ZPX, ZPY, DLY, IMP, ABY, IMP, IMP, ABS, ABX, ABX, LGX, // 9
+    IMX, IZX, IMX, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, IMP, IMP, ABS, ABS, ABS, LNG, // A
+    REL, IZY, ZPI, SRY, ZPX, ZPX, ZPY, DLY, IMP, ABY, IMP, IMP, ABX, ABX, ABY, LGX, // B
+    IMX, IZX, IMM, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, IMP, IMP, ABS, ABS, ABS, LNG, // C
+    REL, IZY, ZPI, SRY, ZPI, ZPX, ZPX, DLY, IMP, ABY, IMP, IMP, AIL, ABX, ABX, LGX, // D
+    IMX, IZX, IMM, SRL, ZPG, ZPG, ZPG, DIL, IMP, IMA, IMP, IMP, ABS, ABS, ABS, LNG, // E
+    REL, IZY, ZPI, SRY, ABS, ZPX, ZPX, DLY, IMP, ABY, IMP, IMP, AIX, ABX, ABX, LGX, // F
+];
+
+/// One decoded instruction, ready to be printed as assembly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disassembly {
+    pub address: u32, // Bank byte included on the 65C816
+    pub bytes: Vec<u8>,
+    pub mnemonic: &'static str,
+    pub operand: Operand,
+}
+
+impl Operand {
+    pub fn length(&self, wide_accumulator: bool, wide_index: bool) -> usize {
+        match self {
+            Implied | Accumulator => return 1,
+            ImmediateAccumulator => return 2 + wide_accumulator as usize,
+            ImmediateIndex => return 2 + wide_index as usize,
+            Absolute | AbsoluteX | AbsoluteY | Indirect | AbsoluteIndexedIndirect => return 3,
+            RelativeLong | ZeroPageRelative | AbsoluteIndirectLong | BlockMove => return 3,
+            AbsoluteLong | AbsoluteLongX => return 4,
+            _ => return 2,
+        }
+    }
+}
+
+pub fn mnemonic(variant: Variant, opcode: u8) -> (&'static str, Operand) {
+    /* The names an assembler for this part would accept, undefined CMOS opcodes come out as NOP */
+    match variant.instruction_set() {
+        InstructionSet::Nmos => {
+            return (
+                NMOS_MNEMONICS[opcode as usize],
+                NMOS_OPERANDS[opcode as usize],
+            )
+        }
+        InstructionSet::Cmos => {
+            if (opcode == 0xCB || opcode == 0xDB) && variant.has_wait_and_stop() == false {
+                return ("NOP", Implied);
+            }
+            return (
+                CMOS_MNEMONICS[opcode as usize],
+                CMOS_OPERANDS[opcode as usize],
+            );
+        }
+        InstructionSet::Wdc65C816 => {
+            return (
+                W65C816_MNEMONICS[opcode as usize],
+                W65C816_OPERANDS[opcode as usize],
+            )
+        }
+    }
+}
+
+pub fn disassemble<B: Bus>(processor: &Processor, memory: &B, address: u32) -> Disassembly {
+    /*
+        Reads the instruction at the address without touching the bus, so devices see nothing.
+        Operand bytes wrap within their bank like the program counter does, and the 65C816
+        immediate widths follow the processor's current M and X flags.
+    */
+    let mask: u32 = processor.variant.address_mask();
+    let byte_at = |offset: u32| -> u8 {
+        let location: u32 = (address & 0xFF_0000) | (address.wrapping_add(offset) & 0xFFFF);
+        return memory.peek(location & mask);
+    };
+
+    let opcode: u8 = byte_at(0);
+    let (mnemonic, operand) = mnemonic(processor.variant, opcode);
+    let length: usize = operand.length(processor.accumulator_is_wide(), processor.index_is_wide());
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(length);
+    for offset in 0..length {
+        bytes.push(byte_at(offset as u32));
+    }
+
+    return Disassembly {
+        address,
+        bytes,
+        mnemonic,
+        operand,
+    };
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let byte = |index: usize| -> u8 { self.bytes[index] };
+        let word = |index: usize| -> u16 { byte(index) as u16 | ((byte(index + 1) as u16) << 8) };
+        let long = |index: usize| -> u32 { word(index) as u32 | ((byte(index + 2) as u32) << 16) };
+        // Branches never leave their bank, so only the low 16 bits of the target are shown
+        let next: u16 = (self.address as u16).wrapping_add(self.bytes.len() as u16);
+        let branch = |offset: i16| -> u16 { next.wrapping_add(offset as u16) };
+
+        write!(f, "{}", self.mnemonic)?;
+        match self.operand {
+            Implied => return Ok(()),
+            Accumulator => return write!(f, " A"),
+            Immediate | ImmediateAccumulator | ImmediateIndex => match self.bytes.len() {
+                3 => return write!(f, " #${:04X}", word(1)),
+                _ => return write!(f, " #${:02X}", byte(1)),
+            },
+            ZeroPage => return write!(f, " ${:02X}", byte(1)),
+            ZeroPageX => return write!(f, " ${:02X},X", byte(1)),
+            ZeroPageY => return write!(f, " ${:02X},Y", byte(1)),
+            Absolute => return write!(f, " ${:04X}", word(1)),
+            AbsoluteX => return write!(f, " ${:04X},X", word(1)),
+            AbsoluteY => return write!(f, " ${:04X},Y", word(1)),
+            Indirect => return write!(f, " (${:04X})", word(1)),
+            IndirectX => return write!(f, " (${:02X},X)", byte(1)),
+            IndirectY => return write!(f, " (${:02X}),Y", byte(1)),
+            ZeroPageIndirect => return write!(f, " (${:02X})", byte(1)),
+            AbsoluteIndexedIndirect => return write!(f, " (${:04X},X)", word(1)),
+            Relative => return write!(f, " ${:04X}", branch(byte(1) as i8 as i16)),
+            RelativeLong => return write!(f, " ${:04X}", branch(word(1) as i16)),
+            ZeroPageRelative => {
+                return write!(f, " ${:02X},${:04X}", byte(1), branch(byte(2) as i8 as i16))
+            }
+            DirectIndirectLong => return write!(f, " [${:02X}]", byte(1)),
+            DirectIndirectLongY => return write!(f, " [${:02X}],Y", byte(1)),
+            AbsoluteLong => return write!(f, " ${:06X}", long(1)),
+            AbsoluteLongX => return write!(f, " ${:06X},X", long(1)),
+            AbsoluteIndirectLong => return write!(f, " [${:04X}]", word(1)),
+            StackRelative => return write!(f, " ${:02X},S", byte(1)),
+            StackRelativeIndirectY => return write!(f, " (${:02X},S),Y", byte(1)),
+            BlockMove => return write!(f, " ${:02X},${:02X}", byte(2), byte(1)), // Source bank is written first but stored last
+        }
+    }
+}
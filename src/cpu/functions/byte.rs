@@ -1,28 +1,226 @@
+use crate::cpu::observer::*;
 use crate::cpu::processor::*;
-use crate::Memory;
+use crate::cpu::regions::Verdict;
+use crate::cpu::stepper::CyclePhase;
+use crate::cpu::variant::Quirk;
+use crate::mem::Bus;
 
 pub trait ByteFunctions {
-    fn read_byte(&mut self, memory: &Memory, address: u16) -> u8;
-    fn fetch_byte(&mut self, memory: &Memory) -> u8;
-    fn write_byte(&mut self, memory: &mut Memory, data: u8, address: u16) -> ();
+    fn read_byte<B: Bus>(&mut self, memory: &mut B, address: u16) -> u8;
+    fn read_long<B: Bus>(&mut self, memory: &mut B, address: u32) -> u8;
+    fn fetch_byte<B: Bus>(&mut self, memory: &mut B) -> u8;
+    fn fetch_opcode<B: Bus>(&mut self, memory: &mut B) -> u8;
+    fn write_byte<B: Bus>(&mut self, memory: &mut B, data: u8, address: u16) -> ();
+    fn write_long<B: Bus>(&mut self, memory: &mut B, data: u8, address: u32) -> ();
+    fn write_modified<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        original: u8,
+        result: u8,
+        address: u16,
+    ) -> ();
+}
+
+fn observe(processor: &mut Processor, access: BusAccess) -> () {
+    processor.stepper.record(access);
+    processor.debugger.observe(access);
+    if let Some(observer) = processor.bus_observer.as_mut() {
+        observer.notify(access);
+    }
+}
+
+fn peek<B: Bus>(processor: &Processor, memory: &B, address: u32) -> u8 {
+    /* For cycles that never reach the bus, devices must not see them */
+    match processor.io_port.as_ref() {
+        Some(port) if port.handles(address as u16) => return port.read(address as u16),
+        _ => {}
+    }
+    match memory.floating(address) {
+        Some(decay) => return processor.data_bus.float(processor.clock, decay),
+        None => return memory.peek(address),
+    }
+}
+
+fn bus_read<B: Bus>(processor: &mut Processor, memory: &mut B, address: u32) -> u8 {
+    /* Whatever answers drives the data bus, where nothing does the read gets what is left on it */
+    let data: u8 = match processor.io_port.as_ref() {
+        Some(port) if port.handles(address as u16) => port.read(address as u16),
+        _ => match memory.floating(address) {
+            Some(decay) => return processor.data_bus.float(processor.clock, decay),
+            None => memory.read(address),
+        },
+    };
+    processor.data_bus.drive(data, processor.clock);
+    return data;
+}
+
+pub fn fetch_cached(processor: &mut Processor, byte: u8) -> () {
+    /* The same cycle and program counter step as fetch_opcode() or fetch_byte(), for a byte of code known without the read */
+    processor.data_bus.drive(byte, processor.clock);
+    processor.decrement_cycles(1);
+    processor.increment_pc();
+}
+
+pub fn read_unobserved<B: Bus>(processor: &mut Processor, memory: &mut B, address: u16) -> u8 {
+    /*
+        read_byte() for compiled code, which only runs while blocks::unobserved() holds. With no
+        stepper, observer, watchpoint or region to answer to, the cycle is the bus read and nothing else.
+    */
+    let address: u32 = address as u32 & processor.variant.address_mask();
+    catch_up_bus(processor, memory);
+    let data: u8 = bus_read(processor, memory, address);
+    processor.decrement_cycles(1);
+    return data;
+}
+
+pub fn write_unobserved<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    data: u8,
+    address: u16,
+) -> () {
+    /* write_byte() under the same conditions as read_unobserved(), blocks cached over the byte still go */
+    let mask: u32 = processor.variant.address_mask();
+    let address: u32 = address as u32 & mask;
+    catch_up_bus(processor, memory);
+    match processor.io_port.as_mut() {
+        Some(port) if port.handles(address as u16) => port.write(address as u16, data),
+        _ => memory.write(address, data),
+    }
+    processor.data_bus.drive(data, processor.clock);
+    processor.blocks.invalidate(address, mask);
+    processor.blocks.switched(memory, mask);
+    processor.decrement_cycles(1);
+}
+
+pub fn catch_up_bus<B: Bus>(processor: &mut Processor, memory: &mut B) -> () {
+    /* Devices hear about every cycle that has passed before they see an access, so each lands on its own cycle */
+    if processor.clock > processor.bus_clock {
+        memory.tick((processor.clock - processor.bus_clock) as u32);
+        processor.bus_clock = processor.clock;
+    }
+}
+
+fn read_cycle<B: Bus>(processor: &mut Processor, memory: &mut B, address: u32, sync: bool) -> u8 {
+    let address: u32 = address & processor.variant.address_mask();
+    let data: u8 = match processor.stepper.phase() {
+        CyclePhase::Replay(access) => access.data,
+        CyclePhase::Discard => peek(processor, memory, address),
+        CyclePhase::Live if processor.control.ready() == false => {
+            // RDY holds reads only, nothing is latched until the read is repeated with RDY high
+            let data: u8 = peek(processor, memory, address);
+            let operation = BusOperation::Read;
+            processor.stepper.stall(BusAccess {
+                address,
+                data,
+                operation,
+                sync,
+            });
+            data
+        }
+        CyclePhase::Direct | CyclePhase::Live => {
+            catch_up_bus(processor, memory);
+            if sync {
+                processor
+                    .regions
+                    .fetch(address, processor.variant.address_mask());
+            }
+            let data: u8 = bus_read(processor, memory, address);
+            let operation = BusOperation::Read;
+            observe(
+                processor,
+                BusAccess {
+                    address,
+                    data,
+                    operation,
+                    sync,
+                },
+            );
+            data
+        }
+    };
+
+    processor.decrement_cycles(1);
+    return data;
 }
 
 impl ByteFunctions for Processor {
-    fn fetch_byte(&mut self, memory: &Memory) -> u8 {
-        let data: u8 = memory.data[self.program_counter as usize];
+    fn fetch_byte<B: Bus>(&mut self, memory: &mut B) -> u8 {
+        let address: u32 = ((self.native.program_bank as u32) << 16) | self.program_counter as u32;
+        let data: u8 = self.read_long(memory, address);
         self.increment_pc();
-        self.decrement_cycles(1);
         return data;
     }
 
-    fn read_byte(&mut self, memory: &Memory, address: u16) -> u8 {
-        let data: u8 = memory.data[address as usize];
-        self.decrement_cycles(1);
+    fn fetch_opcode<B: Bus>(&mut self, memory: &mut B) -> u8 {
+        /* Same as fetch_byte with SYNC raised, which is how the outside world spots an instruction boundary */
+        let address: u32 = ((self.native.program_bank as u32) << 16) | self.program_counter as u32;
+        let data: u8 = read_cycle(self, memory, address, true);
+        self.increment_pc();
         return data;
     }
 
-    fn write_byte(&mut self, memory: &mut Memory, data: u8, address: u16) -> () {
-        memory.data[address as usize] = data;
+    fn read_byte<B: Bus>(&mut self, memory: &mut B, address: u16) -> u8 {
+        return self.read_long(memory, address as u32);
+    }
+
+    fn read_long<B: Bus>(&mut self, memory: &mut B, address: u32) -> u8 {
+        return read_cycle(self, memory, address, false);
+    }
+
+    fn write_byte<B: Bus>(&mut self, memory: &mut B, data: u8, address: u16) -> () {
+        self.write_long(memory, data, address as u32);
+    }
+
+    fn write_long<B: Bus>(&mut self, memory: &mut B, data: u8, address: u32) -> () {
+        let mask: u32 = self.variant.address_mask();
+        let address: u32 = address & mask;
+        if let CyclePhase::Direct | CyclePhase::Live = self.stepper.phase() {
+            catch_up_bus(self, memory);
+            // A write the regions refuse still goes out on the bus, nothing there takes it
+            let verdict: Verdict = self.regions.write(address, data, mask);
+            match (verdict, self.io_port.as_mut()) {
+                (Verdict::Drop, _) => {}
+                (Verdict::Allow, Some(port)) if port.handles(address as u16) => {
+                    port.write(address as u16, data)
+                }
+                (Verdict::Allow, _) => memory.write(address, data),
+            }
+            self.data_bus.drive(data, self.clock);
+            self.blocks.invalidate(address, mask);
+            self.blocks.switched(memory, mask);
+
+            let operation = BusOperation::Write;
+            observe(
+                self,
+                BusAccess {
+                    address,
+                    data,
+                    operation,
+                    sync: false,
+                },
+            );
+        }
+
         self.decrement_cycles(1);
     }
+
+    fn write_modified<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        original: u8,
+        result: u8,
+        address: u16,
+    ) -> () {
+        /*
+            The second half of a read-modify-write. While the ALU works, NMOS parts write the
+            unmodified value straight back and CMOS parts read the location a second time.
+        */
+        if self.variant.has_quirk(Quirk::ModifyWritesTwice) {
+            self.write_byte(memory, original, address);
+        } else {
+            self.read_byte(memory, address);
+        }
+        self.write_byte(memory, result, address);
+    }
 }
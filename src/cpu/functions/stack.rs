@@ -1,62 +1,56 @@
 use crate::cpu::functions;
 use crate::cpu::processor::*;
-use crate::Memory;
+use crate::mem::Bus;
 
 use functions::byte::*;
-use functions::word::*;
 
 pub trait StackFunctions {
-    fn push_pc_minus_one_to_stack(&mut self, memory: &mut Memory) -> ();
-    fn push_pc_plus_one_to_stack(&mut self, memory: &mut Memory) -> ();
-    fn push_pc_to_stack(&mut self, memory: &mut Memory) -> ();
-    fn push_byte_to_stack(&mut self, memory: &mut Memory, value: u8) -> ();
+    fn push_pc_to_stack<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn push_byte_to_stack<B: Bus>(&mut self, memory: &mut B, value: u8) -> ();
     fn stack_pointer_to_address(&mut self) -> u16;
-    fn pop_word_from_stack(&mut self, memory: &mut Memory) -> u16;
-    fn pop_byte_from_stack(&mut self, memory: &mut Memory) -> u8;
+    fn pop_word_from_stack<B: Bus>(&mut self, memory: &mut B) -> u16;
+    fn pop_byte_from_stack<B: Bus>(&mut self, memory: &mut B) -> u8;
+    fn stack_dummy_reads<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl StackFunctions for Processor {
-    fn push_pc_to_stack(&mut self, memory: &mut Memory) -> () {
-        let sp_addr: u16 = self.stack_pointer_to_address() - 1;
-        self.write_word(memory, self.program_counter, sp_addr);
-        self.stack_pointer = self.stack_pointer.wrapping_sub(2);
+    fn push_pc_to_stack<B: Bus>(&mut self, memory: &mut B) -> () {
+        /* Pushed a byte at a time so SP wraps inside page one, high byte first */
+        let bytes: [u8; 2] = self.program_counter.to_le_bytes();
+        self.push_byte_to_stack(memory, bytes[1]);
+        self.push_byte_to_stack(memory, bytes[0]);
     }
 
-    fn push_pc_minus_one_to_stack(&mut self, memory: &mut Memory) -> () {
-        let sp_addr: u16 = self.stack_pointer_to_address() - 1;
-        self.write_word(memory, self.program_counter - 1, sp_addr);
-        self.stack_pointer = self.stack_pointer.wrapping_sub(2);
-    }
-
-    fn push_pc_plus_one_to_stack(&mut self, memory: &mut Memory) -> () {
-        let sp_addr: u16 = self.stack_pointer_to_address() - 1;
-        self.write_word(memory, self.program_counter + 1, sp_addr);
-        self.stack_pointer = self.stack_pointer.wrapping_sub(2);
-    }
-
-    fn push_byte_to_stack(&mut self, memory: &mut Memory, value: u8) -> () {
+    fn push_byte_to_stack<B: Bus>(&mut self, memory: &mut B, value: u8) -> () {
         let stack_addr = self.stack_pointer_to_address();
         self.write_byte(memory, value, stack_addr);
 
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        self.decrement_cycles(1);
     }
 
     fn stack_pointer_to_address(&mut self) -> u16 {
         return 0x100 | self.stack_pointer as u16;
     }
 
-    fn pop_word_from_stack(&mut self, memory: &mut Memory) -> u16 {
-        let sp_addr: u16 = self.stack_pointer_to_address() + 1;
-        self.stack_pointer = self.stack_pointer.wrapping_add(2);
-        self.decrement_cycles(1);
-        return self.read_word(memory, sp_addr);
+    fn pop_word_from_stack<B: Bus>(&mut self, memory: &mut B) -> u16 {
+        let low_byte: u8 = self.pop_byte_from_stack(memory);
+        let high_byte: u8 = self.pop_byte_from_stack(memory);
+        return low_byte as u16 | ((high_byte as u16) << 8);
     }
 
-    fn pop_byte_from_stack(&mut self, memory: &mut Memory) -> u8 {
+    fn pop_byte_from_stack<B: Bus>(&mut self, memory: &mut B) -> u8 {
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
         let sp_addr: u16 = self.stack_pointer_to_address();
-        self.decrement_cycles(2);
         return self.read_byte(memory, sp_addr);
     }
+
+    fn stack_dummy_reads<B: Bus>(&mut self, memory: &mut B) -> () {
+        /*
+            Pulls spend two cycles before the first byte arrives: the byte after the opcode is read
+            and dropped, then the stack is read at the current SP while it is incremented.
+        */
+        self.read_byte(memory, self.program_counter);
+        let sp_addr: u16 = self.stack_pointer_to_address();
+        self.read_byte(memory, sp_addr);
+    }
 }
@@ -1,73 +1,136 @@
 use crate::cpu;
-use crate::Memory;
+use crate::mem::Bus;
 
 use cpu::functions::byte::*;
 use cpu::functions::word::*;
+use cpu::opcodes::AddressingMode::{self, *};
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::*;
+use cpu::variant::{Penalty, Quirk};
 
 pub trait Addressing {
-    fn addr_zero_page(&mut self, memory: &Memory, offset_register: Option<Registers>) -> u16;
-
-    fn addr_absolute(&mut self, memory: &Memory, offset_register: Option<Registers>) -> u16;
-
-    fn addr_indirect_x(&mut self, memory: &Memory) -> u16;
-    fn addr_indirect_y(&mut self, memory: &Memory) -> u16;
+    fn addr_mode<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        mode: AddressingMode,
+        access: MemoryAccess,
+    ) -> u16;
 }
 
-impl Addressing for Processor {
-    fn addr_zero_page(&mut self, memory: &Memory, offset_register: Option<Registers>) -> u16 {
-        let mut zero_page_addr: u8 = self.fetch_byte(&memory);
+fn read_zero_page_pointer<B: Bus>(processor: &mut Processor, memory: &mut B, address: u8) -> u16 {
+    // The high byte of a zero page pointer is fetched from the zero page too, so a pointer at 0xFF wraps to 0x00
+    let low_byte: u8 = processor.read_byte(memory, address as u16);
+    let high_byte: u8 = processor.read_byte(memory, address.wrapping_add(1) as u16);
+    return low_byte as u16 | ((high_byte as u16) << 8);
+}
 
-        match offset_register {
-            Some(RegisterX) => {
-                zero_page_addr = zero_page_addr.wrapping_add(self.register_x);
-                self.decrement_cycles(1);
-            }
-            Some(RegisterY) => {
-                zero_page_addr = zero_page_addr.wrapping_add(self.register_y);
-                self.decrement_cycles(1);
-            }
-            _ => {}
-        }
+pub fn index_fixup_read<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    base: u16,
+    address: u16,
+) -> () {
+    /*
+        The cycle spent fixing up the high byte still drives the bus. NMOS parts read the target
+        with the carry not applied yet, CMOS parts re-read the last operand byte instead.
+    */
+    let dummy_address: u16 = match processor.variant.has_quirk(Quirk::HalfCarriedIndexRead) {
+        true => (base & 0xFF00) | (address & 0x00FF),
+        false => processor.program_counter.wrapping_sub(1),
+    };
+    processor.read_byte(memory, dummy_address);
+}
 
-        return zero_page_addr as u16;
+pub fn zero_page_mode(offset_register: Option<Registers>) -> AddressingMode {
+    match offset_register {
+        Some(RegisterX) => return ZeroPageX,
+        Some(RegisterY) => return ZeroPageY,
+        _ => return ZeroPage,
     }
+}
 
-    fn addr_absolute(&mut self, memory: &Memory, offset_register: Option<Registers>) -> u16 {
-        let absolute_addr: u16 = self.fetch_word(memory);
-        let absolute_addr_offset: u16;
-
-        match offset_register {
-            Some(RegisterX) => absolute_addr_offset = absolute_addr + self.register_x as u16,
-            Some(RegisterY) => absolute_addr_offset = absolute_addr + self.register_y as u16,
-            _ => absolute_addr_offset = absolute_addr,
-        }
-
-        if absolute_addr_offset - absolute_addr >= 0xFF {
-            self.decrement_cycles(1);
-        }
-
-        return absolute_addr_offset;
+pub fn absolute_mode(offset_register: Option<Registers>) -> AddressingMode {
+    match offset_register {
+        Some(RegisterX) => return AbsoluteX,
+        Some(RegisterY) => return AbsoluteY,
+        _ => return Absolute,
     }
+}
 
-    fn addr_indirect_x(&mut self, memory: &Memory) -> u16 {
-        let mut zero_page_address: u8 = self.fetch_byte(memory);
-        zero_page_address += self.register_x;
-        self.decrement_cycles(1);
+pub fn indexed_access(processor: &Processor, opcode: u8, access: MemoryAccess) -> MemoryAccess {
+    // Opcodes the timing table only charges on a page cross take the fix-up cycle the way reads do
+    if processor.variant.timing(opcode).penalty == Penalty::PageCross {
+        return MemoryAccess::Read;
+    }
+    return access;
+}
 
-        return self.read_word(memory, zero_page_address as u16);
+fn index_address<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    base: u16,
+    offset: u8,
+    access: MemoryAccess,
+) -> u16 {
+    let address: u16 = base.wrapping_add(offset as u16);
+
+    // Reads only pay for the high byte fix-up when a page is crossed, writes and read-modify-writes always do
+    if access != MemoryAccess::Read || (address >> 8) != (base >> 8) {
+        index_fixup_read(processor, memory, base, address);
     }
 
-    fn addr_indirect_y(&mut self, memory: &Memory) -> u16 {
-        let zero_page_address: u8 = self.fetch_byte(memory);
-        let effective_address: u16 = self.read_word(memory, zero_page_address as u16);
-        let effective_address_y: u16 = effective_address + self.register_y as u16;
+    return address;
+}
 
-        if effective_address_y - effective_address >= 0xFF {
-            self.decrement_cycles(1);
+impl Addressing for Processor {
+    fn addr_mode<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        mode: AddressingMode,
+        access: MemoryAccess,
+    ) -> u16 {
+        match mode {
+            Immediate => {
+                let address: u16 = self.program_counter;
+                self.increment_pc();
+                return address;
+            }
+            ZeroPage => return self.fetch_byte(memory) as u16,
+            ZeroPageX | ZeroPageY => {
+                let offset: u8 = if mode == ZeroPageX {
+                    self.register_x
+                } else {
+                    self.register_y
+                };
+                let base: u8 = self.fetch_byte(memory);
+                self.read_byte(memory, base as u16);
+                return base.wrapping_add(offset) as u16;
+            }
+            Absolute => return self.fetch_word(memory),
+            AbsoluteX => {
+                let base: u16 = self.fetch_word(memory);
+                return index_address(self, memory, base, self.register_x, access);
+            }
+            AbsoluteY => {
+                let base: u16 = self.fetch_word(memory);
+                return index_address(self, memory, base, self.register_y, access);
+            }
+            IndirectX => {
+                let base: u8 = self.fetch_byte(memory);
+                self.read_byte(memory, base as u16);
+                return read_zero_page_pointer(self, memory, base.wrapping_add(self.register_x));
+            }
+            IndirectY => {
+                let pointer: u8 = self.fetch_byte(memory);
+                let base: u16 = read_zero_page_pointer(self, memory, pointer);
+                return index_address(self, memory, base, self.register_y, access);
+            }
+            ZeroPageIndirect => {
+                let pointer: u8 = self.fetch_byte(memory);
+                return read_zero_page_pointer(self, memory, pointer);
+            }
+            Implied => return self.program_counter,
         }
-
-        return effective_address_y;
     }
 }
@@ -4,15 +4,29 @@ use crate::cpu::opcodes::Registers;
 use crate::mem::*;
 
 use cpu::instructions::addressing::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::processor::*;
+use cpu::variant::{DecimalArithmetic, Quirk};
 
-fn add(processor: &mut Processor, operand: u16) -> () {
-    assert_eq!(
-        processor.fetch_status(DecimalMode),
-        false,
-        "Decimal Mode is not handled"
-    );
+pub fn add(processor: &mut Processor, operand: u16) -> () {
+    if processor.fetch_status(DecimalMode) {
+        match processor.variant.decimal_arithmetic() {
+            DecimalArithmetic::Nmos => return add_decimal(processor, operand as u8),
+            DecimalArithmetic::Cmos => {
+                // N and Z describe the decimal result, which costs the 65C02 an extra cycle
+                add_decimal(processor, operand as u8);
+                processor.set_status(ZeroFlag, processor.accumulator == 0);
+                processor.set_status(NegativeFlag, fetch_bit(processor.accumulator, 7));
+                if processor.variant.has_quirk(Quirk::DecimalExtraCycle) {
+                    processor.decrement_cycles(1);
+                }
+                return;
+            }
+            DecimalArithmetic::Ignored => {}
+        }
+    }
 
     let same_signs: bool = fetch_bit(processor.accumulator ^ operand as u8, 7) as u8 == 0;
     let carry = processor.fetch_status(CarryFlag) as u16;
@@ -30,42 +44,81 @@ fn add(processor: &mut Processor, operand: u16) -> () {
     // Above line checks whether the sign bits of operand and ACC were originally the sign, and then checks whether the sign bit of the sum and operand differ post add
 }
 
+fn add_decimal(processor: &mut Processor, operand: u8) -> () {
+    /*
+        NMOS decimal addition, following Bruce Clark's "Decimal Mode" tutorial (6502.org).
+        The low nibble is adjusted first, then N and V are taken from the sum before the high nibble is adjusted.
+        The Z flag is not affected by the decimal adjust at all - it reflects the plain binary sum.
+        Invalid BCD digits (A to F) go through exactly the same steps, which reproduces the hardware results for them.
+    */
+    let accumulator = processor.accumulator;
+    let carry = processor.fetch_status(CarryFlag) as u16;
+    let binary_sum: u16 = accumulator as u16 + operand as u16 + carry;
+
+    let mut low: u16 = (accumulator & 0x0F) as u16 + (operand & 0x0F) as u16 + carry;
+    if low >= 0x0A {
+        low = ((low + 0x06) & 0x0F) + 0x10;
+    }
+
+    let mut sum: u16 = (accumulator & 0xF0) as u16 + (operand & 0xF0) as u16 + low;
+    let signed_sum: i16 =
+        (accumulator & 0xF0) as i8 as i16 + (operand & 0xF0) as i8 as i16 + low as i16;
+
+    processor.set_status(NegativeFlag, fetch_bit(sum as u8, 7));
+    processor.set_status(OverflowFlag, !(-128..=127).contains(&signed_sum));
+
+    if sum >= 0xA0 {
+        sum += 0x60;
+    }
+
+    processor.accumulator = (sum & 0xFF) as u8;
+    processor.set_status(ZeroFlag, binary_sum & 0xFF == 0);
+    processor.set_status(CarryFlag, sum >= 0x100);
+}
+
 pub trait AddWithCarry {
-    fn adc_immediate(&mut self, memory: &mut Memory) -> ();
+    fn adc_immediate<B: Bus>(&mut self, memory: &mut B) -> ();
 
-    fn adc_absolute(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> ();
-    fn adc_zero_page(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> ();
+    fn adc_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
+    fn adc_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
 
-    fn adc_indirect_x(&mut self, memory: &mut Memory) -> ();
-    fn adc_indirect_y(&mut self, memory: &mut Memory) -> ();
+    fn adc_indirect_x<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn adc_indirect_y<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn adc_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl AddWithCarry for Processor {
-    fn adc_immediate(&mut self, memory: &mut Memory) -> () {
+    fn adc_immediate<B: Bus>(&mut self, memory: &mut B) -> () {
         let operand: u16 = self.fetch_byte(memory) as u16;
         add(self, operand);
     }
 
-    fn adc_absolute(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> () {
-        let address = self.addr_absolute(memory, offset_register);
+    fn adc_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let address = self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
+        let operand: u16 = self.read_byte(memory, address) as u16;
+        add(self, operand);
+    }
+
+    fn adc_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let address = self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
         let operand: u16 = self.read_byte(memory, address) as u16;
         add(self, operand);
     }
 
-    fn adc_zero_page(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> () {
-        let address = self.addr_zero_page(memory, offset_register);
+    fn adc_indirect_x<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
         let operand: u16 = self.read_byte(memory, address) as u16;
         add(self, operand);
     }
 
-    fn adc_indirect_x(&mut self, memory: &mut Memory) -> () {
-        let address = self.addr_indirect_x(memory);
+    fn adc_indirect_y<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, IndirectY, MemoryAccess::Read);
         let operand: u16 = self.read_byte(memory, address) as u16;
         add(self, operand);
     }
 
-    fn adc_indirect_y(&mut self, memory: &mut Memory) -> () {
-        let address = self.addr_indirect_y(memory);
+    fn adc_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Read);
         let operand: u16 = self.read_byte(memory, address) as u16;
         add(self, operand);
     }
@@ -3,11 +3,13 @@ use crate::mem::*;
 
 use cpu::functions::byte::*;
 use cpu::instructions::addressing::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::*;
 
-fn compare(processor: &mut Processor, operand: u8, register: Registers) -> () {
+pub fn compare(processor: &mut Processor, operand: u8, register: Registers) -> () {
     let result: u8;
     match register {
         Accumulator => {
@@ -29,60 +31,67 @@ fn compare(processor: &mut Processor, operand: u8, register: Registers) -> () {
 }
 
 pub trait Compare {
-    fn cmp_immediate(&mut self, memory: &mut Memory, register: Registers) -> ();
-    fn cmp_absolute(
+    fn cmp_immediate<B: Bus>(&mut self, memory: &mut B, register: Registers) -> ();
+    fn cmp_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
-    fn cmp_zero_page(
+    fn cmp_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn cmp_indirect_x(&mut self, memory: &mut Memory, register: Registers) -> ();
-    fn cmp_indirect_y(&mut self, memory: &mut Memory, register: Registers) -> ();
+    fn cmp_indirect_x<B: Bus>(&mut self, memory: &mut B, register: Registers) -> ();
+    fn cmp_indirect_y<B: Bus>(&mut self, memory: &mut B, register: Registers) -> ();
+    fn cmp_zero_page_indirect<B: Bus>(&mut self, memory: &mut B, register: Registers) -> ();
 }
 
 impl Compare for Processor {
-    fn cmp_immediate(&mut self, memory: &mut Memory, register: Registers) -> () {
+    fn cmp_immediate<B: Bus>(&mut self, memory: &mut B, register: Registers) -> () {
         let operand = self.fetch_byte(memory);
         compare(self, operand, register);
     }
 
-    fn cmp_absolute(
+    fn cmp_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let address = self.addr_absolute(memory, offset_register);
+        let address = self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
         compare(self, operand, register);
     }
 
-    fn cmp_zero_page(
+    fn cmp_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let address = self.addr_zero_page(memory, offset_register);
+        let address = self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
         compare(self, operand, register);
     }
 
-    fn cmp_indirect_x(&mut self, memory: &mut Memory, register: Registers) -> () {
-        let address = self.addr_indirect_x(memory);
+    fn cmp_indirect_x<B: Bus>(&mut self, memory: &mut B, register: Registers) -> () {
+        let address = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
         compare(self, operand, register);
     }
 
-    fn cmp_indirect_y(&mut self, memory: &mut Memory, register: Registers) -> () {
-        let address = self.addr_indirect_y(memory);
+    fn cmp_indirect_y<B: Bus>(&mut self, memory: &mut B, register: Registers) -> () {
+        let address = self.addr_mode(memory, IndirectY, MemoryAccess::Read);
+        let operand = self.read_byte(memory, address);
+        compare(self, operand, register);
+    }
+
+    fn cmp_zero_page_indirect<B: Bus>(&mut self, memory: &mut B, register: Registers) -> () {
+        let address = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
         compare(self, operand, register);
     }
@@ -4,19 +4,22 @@ use crate::cpu::opcodes::Registers;
 use crate::mem::*;
 
 use cpu::instructions::addressing::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::processor::*;
+use cpu::variant::{DecimalArithmetic, Quirk};
 
-fn subtract(processor: &mut Processor, operand: u8) -> () {
-    assert_eq!(
-        processor.fetch_status(DecimalMode),
-        false,
-        "Decimal Mode is not handled"
-    );
+pub fn subtract(processor: &mut Processor, operand: u8) -> () {
+    let decimal: bool = processor.fetch_status(DecimalMode);
+    let original_accumulator: u8 = processor.accumulator;
+    let original_carry: bool = processor.fetch_status(CarryFlag);
 
-    let same_signs: bool = fetch_bit(processor.accumulator ^ operand as u8, 7) as u8 == 0;
+    // SBC is performed as an ADC of the complemented operand - all NMOS flags come from this binary result
+    let complement: u8 = !operand;
+    let same_signs: bool = fetch_bit(processor.accumulator ^ complement, 7) as u8 == 0;
     let carry = processor.fetch_status(CarryFlag) as u16;
-    let sum: u16 = processor.accumulator as u16 + operand as u16 + carry;
+    let sum: u16 = processor.accumulator as u16 + complement as u16 + carry;
 
     processor.accumulator = (sum & 0xFF) as u8; // sum & 0xFF = mask for second byte of sum
     processor.set_status(ZeroFlag, processor.accumulator == 0);
@@ -25,48 +28,114 @@ fn subtract(processor: &mut Processor, operand: u8) -> () {
     processor.set_status(OverflowFlag, false);
     processor.set_status(
         OverflowFlag,
-        same_signs == true && fetch_bit(processor.accumulator, 7) != fetch_bit(operand, 7),
+        same_signs == true && fetch_bit(processor.accumulator, 7) != fetch_bit(complement, 7),
     );
     // Above line checks whether the sign bits of operand and ACC were originally the sign, and then checks whether the sign bit of the sum and operand differ post subtract
+
+    if decimal {
+        match processor.variant.decimal_arithmetic() {
+            DecimalArithmetic::Nmos => {
+                processor.accumulator =
+                    subtract_decimal(original_accumulator, operand, original_carry);
+            }
+            DecimalArithmetic::Cmos => {
+                // N and Z describe the decimal result, which costs the 65C02 an extra cycle
+                processor.accumulator =
+                    subtract_decimal_cmos(original_accumulator, operand, original_carry);
+                processor.set_status(ZeroFlag, processor.accumulator == 0);
+                processor.set_status(NegativeFlag, fetch_bit(processor.accumulator, 7));
+                if processor.variant.has_quirk(Quirk::DecimalExtraCycle) {
+                    processor.decrement_cycles(1);
+                }
+            }
+            DecimalArithmetic::Ignored => {}
+        }
+    }
+}
+
+fn subtract_decimal(accumulator: u8, operand: u8, carry: bool) -> u8 {
+    /*
+        NMOS decimal subtraction, following Bruce Clark's "Decimal Mode" tutorial (6502.org).
+        Only the accumulator is decimal adjusted - N, V, Z and C are identical to a binary SBC.
+    */
+    let borrow: i16 = carry as i16 - 1;
+
+    let mut low: i16 = (accumulator & 0x0F) as i16 - (operand & 0x0F) as i16 + borrow;
+    if low < 0 {
+        low = ((low - 0x06) & 0x0F) - 0x10;
+    }
+
+    let mut difference: i16 = (accumulator & 0xF0) as i16 - (operand & 0xF0) as i16 + low;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+
+    return (difference & 0xFF) as u8;
+}
+
+fn subtract_decimal_cmos(accumulator: u8, operand: u8, carry: bool) -> u8 {
+    /*
+        The 65C02 adjusts the binary difference as a whole rather than nibble by nibble,
+        which gives different (but equally defined) results for invalid BCD digits.
+    */
+    let borrow: i16 = carry as i16 - 1;
+    let low: i16 = (accumulator & 0x0F) as i16 - (operand & 0x0F) as i16 + borrow;
+
+    let mut difference: i16 = accumulator as i16 - operand as i16 + borrow;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+    if low < 0 {
+        difference -= 0x06;
+    }
+
+    return (difference & 0xFF) as u8;
 }
 
 pub trait SubtractWithCarry {
-    fn sbc_immediate(&mut self, memory: &mut Memory) -> ();
+    fn sbc_immediate<B: Bus>(&mut self, memory: &mut B) -> ();
 
-    fn sbc_absolute(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> ();
-    fn sbc_zero_page(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> ();
+    fn sbc_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
+    fn sbc_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
 
-    fn sbc_indirect_x(&mut self, memory: &mut Memory) -> ();
-    fn sbc_indirect_y(&mut self, memory: &mut Memory) -> ();
+    fn sbc_indirect_x<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn sbc_indirect_y<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn sbc_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl SubtractWithCarry for Processor {
-    fn sbc_immediate(&mut self, memory: &mut Memory) -> () {
+    fn sbc_immediate<B: Bus>(&mut self, memory: &mut B) -> () {
         let operand = self.fetch_byte(memory);
-        subtract(self, !operand);
+        subtract(self, operand);
+    }
+
+    fn sbc_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let address = self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
+        let operand = self.read_byte(memory, address);
+        subtract(self, operand);
     }
 
-    fn sbc_absolute(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> () {
-        let address = self.addr_absolute(memory, offset_register);
+    fn sbc_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let address = self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
-        subtract(self, !operand);
+        subtract(self, operand);
     }
 
-    fn sbc_zero_page(&mut self, memory: &mut Memory, offset_register: Option<Registers>) -> () {
-        let address = self.addr_zero_page(memory, offset_register);
+    fn sbc_indirect_x<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
-        subtract(self, !operand);
+        subtract(self, operand);
     }
 
-    fn sbc_indirect_x(&mut self, memory: &mut Memory) -> () {
-        let address = self.addr_indirect_x(memory);
+    fn sbc_indirect_y<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, IndirectY, MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
-        subtract(self, !operand);
+        subtract(self, operand);
     }
 
-    fn sbc_indirect_y(&mut self, memory: &mut Memory) -> () {
-        let address = self.addr_indirect_y(memory);
+    fn sbc_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Read);
         let operand = self.read_byte(memory, address);
-        subtract(self, !operand);
+        subtract(self, operand);
     }
 }
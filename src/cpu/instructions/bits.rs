@@ -0,0 +1,56 @@
+use super::addressing::*;
+use super::branches::Branches;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::functions::byte::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
+use cpu::processor::*;
+
+pub trait BitManipulation {
+    fn reset_memory_bit<B: Bus>(&mut self, memory: &mut B, bit: u8) -> ();
+    fn set_memory_bit<B: Bus>(&mut self, memory: &mut B, bit: u8) -> ();
+
+    fn branch_on_bit_reset<B: Bus>(&mut self, memory: &mut B, bit: u8) -> ();
+    fn branch_on_bit_set<B: Bus>(&mut self, memory: &mut B, bit: u8) -> ();
+}
+
+fn change_memory_bit<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    bit: u8,
+    value: bool,
+) -> () {
+    let zero_page_addr = processor.addr_mode(memory, ZeroPage, MemoryAccess::Read);
+    let byte_value: u8 = processor.read_byte(memory, zero_page_addr);
+    let result: u8 = set_bit(byte_value, bit, value);
+    processor.write_modified(memory, byte_value, result, zero_page_addr);
+}
+
+fn test_memory_bit<B: Bus>(processor: &mut Processor, memory: &mut B, bit: u8) -> bool {
+    let zero_page_addr = processor.addr_mode(memory, ZeroPage, MemoryAccess::Read);
+    let byte_value: u8 = processor.read_byte(memory, zero_page_addr);
+    processor.decrement_cycles(1);
+    return fetch_bit(byte_value, bit);
+}
+
+impl BitManipulation for Processor {
+    fn reset_memory_bit<B: Bus>(&mut self, memory: &mut B, bit: u8) -> () {
+        change_memory_bit(self, memory, bit, false);
+    }
+
+    fn set_memory_bit<B: Bus>(&mut self, memory: &mut B, bit: u8) -> () {
+        change_memory_bit(self, memory, bit, true);
+    }
+
+    fn branch_on_bit_reset<B: Bus>(&mut self, memory: &mut B, bit: u8) -> () {
+        let bit_value: bool = test_memory_bit(self, memory, bit);
+        self.branch(memory, bit_value == false);
+    }
+
+    fn branch_on_bit_set<B: Bus>(&mut self, memory: &mut B, bit: u8) -> () {
+        let bit_value: bool = test_memory_bit(self, memory, bit);
+        self.branch(memory, bit_value);
+    }
+}
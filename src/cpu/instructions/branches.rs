@@ -1,26 +1,32 @@
 use crate::cpu;
 use crate::cpu::functions::byte::ByteFunctions;
-use crate::mem::Memory;
+use crate::cpu::instructions::system::System;
+use crate::mem::Bus;
 
 use cpu::processor::*;
 
 pub trait Branches {
-    fn branch(&mut self, memory: &mut Memory, condition: bool) -> ();
+    fn branch<B: Bus>(&mut self, memory: &mut B, condition: bool) -> ();
 }
 
 impl Branches for Processor {
-    fn branch(&mut self, memory: &mut Memory, condition: bool) -> () {
+    fn branch<B: Bus>(&mut self, memory: &mut B, condition: bool) -> () {
         let jump_offset: u8 = self.fetch_byte(memory);
         if condition == true {
             let original_pc = self.program_counter.clone();
             let twos_comp: i8 = jump_offset as i8;
 
+            // The next opcode is read and dropped while the offset is added to the low byte
+            self.read_byte(memory, original_pc);
             self.program_counter = self.program_counter.wrapping_add(twos_comp as u16);
-            self.decrement_cycles(1);
 
-            if (self.program_counter >> 8) != (original_pc >> 8) {
-                // Detects if page is crossed by comparing masks of first byte in PC and PCorig
-                self.decrement_cycles(2);
+            if (self.program_counter >> 8) != (original_pc >> 8) && self.native.emulation {
+                // Detects if page is crossed by comparing masks of first byte in PC and PCorig (a native 65C816 never pays for it)
+                // The fix-up cycle reads the target with its high byte still on the old page
+                let uncarried: u16 = (original_pc & 0xFF00) | (self.program_counter & 0x00FF);
+                self.read_byte(memory, uncarried);
+            } else {
+                self.delay_interrupt_poll();
             }
         }
     }
@@ -5,56 +5,77 @@ use crate::cpu::functions::byte::ByteFunctions;
 use crate::cpu::opcodes::Registers;
 use crate::mem::*;
 
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::opcodes::Registers::*;
 use cpu::processor::*;
 
-fn set_memory(processor: &mut Processor, memory: &mut Memory, value: u8, address: u16) -> () {
-    processor.write_byte(memory, value, address);
+fn set_memory<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    original: u8,
+    value: u8,
+    address: u16,
+) -> () {
+    processor.write_modified(memory, original, value, address);
     processor.set_status(ZeroFlag, value == 0);
     processor.set_status(NegativeFlag, fetch_bit(value, 7));
-    processor.decrement_cycles(1);
 }
 
 pub trait Decrement {
-    fn decrement_memory_zero_page(
+    fn decrement_memory_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         offset_register: Option<Registers>,
     ) -> ();
-    fn decrement_memory_absolute(
+    fn decrement_memory_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         offset_register: Option<Registers>,
     ) -> ();
 
     fn decrement_x(&mut self) -> ();
     fn decrement_y(&mut self) -> ();
+    fn decrement_accumulator(&mut self) -> ();
 }
 
 impl Decrement for Processor {
-    fn decrement_memory_zero_page(
+    fn decrement_memory_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         offset_register: Option<Registers>,
     ) -> () {
-        let zero_page_addr = self.addr_zero_page(memory, offset_register);
-        let value: u8 = self.read_byte(memory, zero_page_addr).wrapping_sub(1);
-        set_memory(self, memory, value, zero_page_addr);
+        let zero_page_addr =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
+        let original: u8 = self.read_byte(memory, zero_page_addr);
+        set_memory(
+            self,
+            memory,
+            original,
+            original.wrapping_sub(1),
+            zero_page_addr,
+        );
     }
 
-    fn decrement_memory_absolute(
+    fn decrement_memory_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         offset_register: Option<Registers>,
     ) -> () {
-        let absolute_addr = self.addr_absolute(memory, offset_register);
-        let value: u8 = self.read_byte(memory, absolute_addr).wrapping_sub(1);
-        set_memory(self, memory, value, absolute_addr);
-
-        if let Some(_) = offset_register {
-            self.decrement_cycles(1);
-        }
+        let mode = match offset_register {
+            Some(_) => AbsoluteX,
+            None => Absolute,
+        };
+        let absolute_addr = self.addr_mode(memory, mode, MemoryAccess::Modify);
+        let original: u8 = self.read_byte(memory, absolute_addr);
+        set_memory(
+            self,
+            memory,
+            original,
+            original.wrapping_sub(1),
+            absolute_addr,
+        );
     }
 
     fn decrement_x(&mut self) -> () {
@@ -66,4 +87,9 @@ impl Decrement for Processor {
         self.set_register(RegisterY, self.register_y.wrapping_sub(1));
         self.decrement_cycles(1);
     }
+
+    fn decrement_accumulator(&mut self) -> () {
+        self.set_register(Accumulator, self.accumulator.wrapping_sub(1));
+        self.decrement_cycles(1);
+    }
 }
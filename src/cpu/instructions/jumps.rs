@@ -1,47 +1,76 @@
 use super::addressing::*;
 use crate::cpu;
-use crate::mem::Memory;
+use crate::mem::Bus;
 
+use cpu::functions::byte::*;
 use cpu::functions::stack::*;
 use cpu::functions::word::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::processor::*;
+use cpu::variant::Quirk;
 
 pub trait Jumps {
-    fn jsr(&mut self, memory: &mut Memory) -> ();
-    fn rts(&mut self, memory: &mut Memory) -> ();
+    fn jsr<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn rts<B: Bus>(&mut self, memory: &mut B) -> ();
 
-    fn jump_absolute(&mut self, memory: &mut Memory) -> ();
-    fn jump_indirect(&mut self, memory: &mut Memory) -> ();
+    fn jump_absolute<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn jump_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn jump_indexed_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl Jumps for Processor {
-    fn jsr(&mut self, memory: &mut Memory) -> () {
-        let sub_addr: u16 = self.fetch_word(memory);
-        self.push_pc_minus_one_to_stack(memory);
-        self.program_counter = sub_addr;
+    fn jsr<B: Bus>(&mut self, memory: &mut B) -> () {
+        /*
+            The high byte of the target is fetched last, after the return address is pushed.
+            PC still points at that byte while it is pushed, which is why JSR stacks the address minus one.
+        */
+        let low_byte: u8 = self.fetch_byte(memory);
+        let sp_addr: u16 = self.stack_pointer_to_address();
+        self.read_byte(memory, sp_addr);
+        self.push_pc_to_stack(memory);
+        let high_byte: u8 = self.read_byte(memory, self.program_counter);
 
-        self.decrement_cycles(1);
+        self.program_counter = low_byte as u16 | ((high_byte as u16) << 8);
     }
 
-    fn rts(&mut self, memory: &mut Memory) -> () {
+    fn rts<B: Bus>(&mut self, memory: &mut B) -> () {
+        self.stack_dummy_reads(memory);
         let return_addr: u16 = self.pop_word_from_stack(memory);
-        self.program_counter = return_addr + 1;
-        self.decrement_cycles(2);
+
+        // The pulled address is read once more while PC is incremented past it
+        self.read_byte(memory, return_addr);
+        self.program_counter = return_addr.wrapping_add(1);
     }
 
-    fn jump_absolute(&mut self, memory: &mut Memory) -> () {
-        let address: u16 = self.addr_absolute(memory, None);
+    fn jump_absolute<B: Bus>(&mut self, memory: &mut B) -> () {
+        let address: u16 = self.addr_mode(memory, Absolute, MemoryAccess::Read);
         self.program_counter = address;
-        // IS JUMP OFFSET FORWARD BY 2???
-        println!(
-            "NEW PC: {:X} | DATA AT PC: {:X}",
-            self.program_counter, &memory.data[self.program_counter as usize]
-        );
     }
 
-    fn jump_indirect(&mut self, memory: &mut Memory) -> () {
-        let mut address: u16 = self.addr_absolute(memory, None);
-        address = self.read_word(memory, address);
-        self.program_counter = address;
+    fn jump_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let pointer: u16 = self.addr_mode(memory, Absolute, MemoryAccess::Read);
+
+        if self.variant.has_quirk(Quirk::IndirectJumpPageWrap) {
+            let low_byte: u8 = self.read_byte(memory, pointer);
+            let high_byte: u8 = self.read_byte(
+                memory,
+                (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF),
+            );
+            self.program_counter = low_byte as u16 | ((high_byte as u16) << 8);
+        } else {
+            self.program_counter = self.read_word(memory, pointer);
+            if self.variant.has_quirk(Quirk::IndirectJumpExtraCycle) {
+                self.decrement_cycles(1);
+            }
+        }
+    }
+
+    fn jump_indexed_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let mut address: u16 = self.fetch_word(memory);
+        address = address.wrapping_add(self.register_x as u16);
+        self.decrement_cycles(1);
+
+        self.program_counter = self.read_word(memory, address);
     }
 }
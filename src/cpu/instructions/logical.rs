@@ -1,10 +1,12 @@
-use super::addressing::Addressing;
+use super::addressing::*;
 use super::registers::load::LoadRegister;
 use crate::cpu;
 use crate::mem::*;
 
 use cpu::functions::byte::*;
+use cpu::opcodes::AddressingMode::*;
 use cpu::opcodes::LogicalOperations::{self, *};
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::{Functions, Processor};
@@ -18,29 +20,53 @@ fn complete_logic_op(a: u8, b: u8, operation: LogicalOperations) -> u8 {
 }
 
 pub trait Logical {
-    fn logic_immediate(&mut self, memory: &mut Memory, operation: LogicalOperations) -> ();
-    fn logic_zero_page(
+    fn logic_immediate<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> ();
+    fn logic_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         operation: LogicalOperations,
         offset_register: Option<Registers>,
     ) -> ();
-    fn logic_absolute(
+    fn logic_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         operation: LogicalOperations,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn logic_indirect_x(&mut self, memory: &mut Memory, operation: LogicalOperations) -> ();
-    fn logic_indirect_y(&mut self, memory: &mut Memory, operation: LogicalOperations) -> ();
+    fn logic_indirect_x<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> ();
+    fn logic_indirect_y<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> ();
+    fn logic_zero_page_indirect<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        operation: LogicalOperations,
+    ) -> ();
+
+    fn bit_immediate<B: Bus>(&mut self, memory: &mut B);
+    fn bit_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>);
+    fn bit_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>);
+
+    fn trb_zero_page<B: Bus>(&mut self, memory: &mut B);
+    fn trb_absolute<B: Bus>(&mut self, memory: &mut B);
+    fn tsb_zero_page<B: Bus>(&mut self, memory: &mut B);
+    fn tsb_absolute<B: Bus>(&mut self, memory: &mut B);
+}
+
+fn test_bits<B: Bus>(processor: &mut Processor, memory: &mut B, address: u16, set: bool) -> () {
+    /* TRB and TSB set Z from A AND memory like BIT, then clear or set the accumulator's bits in memory */
+    let value: u8 = processor.read_byte(memory, address);
+    processor.set_status(ZeroFlag, processor.accumulator & value == 0);
 
-    fn bit_zero_page(&mut self, memory: &mut Memory);
-    fn bit_absolute(&mut self, memory: &mut Memory);
+    let result: u8 = if set {
+        value | processor.accumulator
+    } else {
+        value & !processor.accumulator
+    };
+    processor.write_modified(memory, value, result, address);
 }
 
 impl Logical for Processor {
-    fn logic_immediate(&mut self, memory: &mut Memory, operation: LogicalOperations) -> () {
+    fn logic_immediate<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> () {
         let byte_value = self.fetch_byte(memory);
         self.set_register(
             Accumulator,
@@ -48,13 +74,14 @@ impl Logical for Processor {
         );
     }
 
-    fn logic_zero_page(
+    fn logic_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         operation: LogicalOperations,
         offset_register: Option<Registers>,
     ) -> () {
-        let zero_page_addr: u16 = self.addr_zero_page(memory, offset_register);
+        let zero_page_addr: u16 =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
         let byte_value: u8 = self.read_byte(memory, zero_page_addr);
         self.set_register(
             Accumulator,
@@ -62,13 +89,23 @@ impl Logical for Processor {
         );
     }
 
-    fn logic_absolute(
+    fn logic_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         operation: LogicalOperations,
         offset_register: Option<Registers>,
     ) -> () {
-        let absolute_addr: u16 = self.addr_absolute(memory, offset_register);
+        let absolute_addr: u16 =
+            self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
+        let byte_value: u8 = self.read_byte(memory, absolute_addr);
+        self.set_register(
+            Accumulator,
+            complete_logic_op(self.accumulator, byte_value, operation),
+        );
+    }
+
+    fn logic_indirect_x<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> () {
+        let absolute_addr: u16 = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
         let byte_value: u8 = self.read_byte(memory, absolute_addr);
         self.set_register(
             Accumulator,
@@ -76,8 +113,8 @@ impl Logical for Processor {
         );
     }
 
-    fn logic_indirect_x(&mut self, memory: &mut Memory, operation: LogicalOperations) -> () {
-        let absolute_addr: u16 = self.addr_indirect_x(memory);
+    fn logic_indirect_y<B: Bus>(&mut self, memory: &mut B, operation: LogicalOperations) -> () {
+        let absolute_addr: u16 = self.addr_mode(memory, IndirectY, MemoryAccess::Read);
         let byte_value: u8 = self.read_byte(memory, absolute_addr);
         self.set_register(
             Accumulator,
@@ -85,8 +122,12 @@ impl Logical for Processor {
         );
     }
 
-    fn logic_indirect_y(&mut self, memory: &mut Memory, operation: LogicalOperations) -> () {
-        let absolute_addr: u16 = self.addr_indirect_y(memory);
+    fn logic_zero_page_indirect<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        operation: LogicalOperations,
+    ) -> () {
+        let absolute_addr: u16 = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Read);
         let byte_value: u8 = self.read_byte(memory, absolute_addr);
         self.set_register(
             Accumulator,
@@ -94,8 +135,15 @@ impl Logical for Processor {
         );
     }
 
-    fn bit_zero_page(&mut self, memory: &mut Memory) {
-        let zero_page_addr = self.addr_zero_page(memory, None);
+    fn bit_immediate<B: Bus>(&mut self, memory: &mut B) {
+        // Immediate BIT has no memory operand to copy bits 6 and 7 from, so only Z is affected
+        let value = self.fetch_byte(memory);
+        self.set_status(ZeroFlag, self.accumulator & value == 0);
+    }
+
+    fn bit_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) {
+        let zero_page_addr =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
         let value = self.read_byte(memory, zero_page_addr);
 
         self.set_status(ZeroFlag, self.accumulator & value == 0);
@@ -103,12 +151,33 @@ impl Logical for Processor {
         self.set_status(NegativeFlag, fetch_bit(value, 7))
     }
 
-    fn bit_absolute(&mut self, memory: &mut Memory) {
-        let absolute_addr = self.addr_absolute(memory, None);
+    fn bit_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) {
+        let absolute_addr =
+            self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
         let value = self.read_byte(memory, absolute_addr);
 
         self.set_status(ZeroFlag, self.accumulator & value == 0);
         self.set_status(OverflowFlag, fetch_bit(value, 6));
         self.set_status(NegativeFlag, fetch_bit(value, 7))
     }
+
+    fn trb_zero_page<B: Bus>(&mut self, memory: &mut B) {
+        let zero_page_addr = self.addr_mode(memory, ZeroPage, MemoryAccess::Read);
+        test_bits(self, memory, zero_page_addr, false);
+    }
+
+    fn trb_absolute<B: Bus>(&mut self, memory: &mut B) {
+        let absolute_addr = self.addr_mode(memory, Absolute, MemoryAccess::Read);
+        test_bits(self, memory, absolute_addr, false);
+    }
+
+    fn tsb_zero_page<B: Bus>(&mut self, memory: &mut B) {
+        let zero_page_addr = self.addr_mode(memory, ZeroPage, MemoryAccess::Read);
+        test_bits(self, memory, zero_page_addr, true);
+    }
+
+    fn tsb_absolute<B: Bus>(&mut self, memory: &mut B) {
+        let absolute_addr = self.addr_mode(memory, Absolute, MemoryAccess::Read);
+        test_bits(self, memory, absolute_addr, true);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod addressing;
 pub mod arithmetic;
+pub mod bits;
 pub mod branches;
 pub mod decrement;
 pub mod increment;
@@ -10,3 +11,4 @@ pub mod shifts;
 pub mod stackops;
 pub mod system;
 pub mod transfers;
+pub mod undocumented;
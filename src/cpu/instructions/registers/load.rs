@@ -3,6 +3,8 @@ use crate::mem::*;
 
 use cpu::functions::byte::*;
 use cpu::instructions::addressing::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::*;
@@ -10,23 +12,24 @@ use cpu::processor::*;
 pub trait LoadRegister {
     fn set_register(&mut self, register: Registers, value: u8) -> ();
 
-    fn load_immediate(&mut self, memory: &Memory, register: Registers) -> ();
-    fn load_zero_page(
+    fn load_immediate<B: Bus>(&mut self, memory: &mut B, register: Registers) -> ();
+    fn load_zero_page<B: Bus>(
         &mut self,
-        memory: &Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn load_absolute(
+    fn load_absolute<B: Bus>(
         &mut self,
-        memory: &Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn load_indirect_x(&mut self, memory: &Memory) -> ();
-    fn load_indirect_y(&mut self, memory: &Memory) -> ();
+    fn load_indirect_x<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn load_indirect_y<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn load_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl LoadRegister for Processor {
@@ -50,44 +53,53 @@ impl LoadRegister for Processor {
         }
     }
 
-    fn load_immediate(&mut self, memory: &Memory, register: Registers) -> () {
-        let byte_value = self.fetch_byte(&memory);
+    fn load_immediate<B: Bus>(&mut self, memory: &mut B, register: Registers) -> () {
+        let byte_value = self.fetch_byte(memory);
         self.set_register(register, byte_value);
     }
 
-    fn load_zero_page(
+    fn load_zero_page<B: Bus>(
         &mut self,
-        memory: &Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let zero_page_addr: u16 = self.addr_zero_page(&memory, offset_register);
-        let byte_value: u8 = self.read_byte(&memory, zero_page_addr);
+        let zero_page_addr: u16 =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
+        let byte_value: u8 = self.read_byte(memory, zero_page_addr);
 
         self.set_register(register, byte_value);
     }
 
-    fn load_absolute(
+    fn load_absolute<B: Bus>(
         &mut self,
-        memory: &Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let absolute_addr: u16 = self.addr_absolute(&memory, offset_register);
+        let absolute_addr: u16 =
+            self.addr_mode(memory, absolute_mode(offset_register), MemoryAccess::Read);
         let byte_value: u8 = self.read_byte(memory, absolute_addr);
 
         self.set_register(register, byte_value);
     }
 
-    fn load_indirect_x(&mut self, memory: &Memory) -> () {
-        let indirect_addr: u16 = self.addr_indirect_x(memory);
+    fn load_indirect_x<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
         let byte_value = self.read_byte(memory, indirect_addr);
 
         self.set_register(Accumulator, byte_value)
     }
 
-    fn load_indirect_y(&mut self, memory: &Memory) -> () {
-        let indirect_addr: u16 = self.addr_indirect_y(memory);
+    fn load_indirect_y<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, IndirectY, MemoryAccess::Read);
+        let byte_value = self.read_byte(memory, indirect_addr);
+
+        self.set_register(Accumulator, byte_value);
+    }
+
+    fn load_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Read);
         let byte_value = self.read_byte(memory, indirect_addr);
 
         self.set_register(Accumulator, byte_value);
@@ -1,38 +1,45 @@
 use crate::cpu;
-use crate::Memory;
+use crate::mem::Bus;
 
 use cpu::functions::byte::*;
 use cpu::instructions::addressing::*;
+use cpu::opcodes::AddressingMode::*;
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::*;
 
 pub trait StoreRegister {
-    fn store_zero_page(
+    fn store_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn store_absolute(
+    fn store_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> ();
 
-    fn store_indirect_x(&mut self, memory: &mut Memory) -> ();
-    fn store_indirect_y(&mut self, memory: &mut Memory) -> ();
+    fn store_indirect_x<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn store_indirect_y<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn store_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> ();
+
+    fn stz_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
+    fn stz_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> ();
 }
 
 impl StoreRegister for Processor {
-    fn store_zero_page(
+    fn store_zero_page<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let zero_page_address: u16 = self.addr_zero_page(memory, offset_register);
+        let zero_page_address: u16 =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
 
         match register {
             Accumulator => self.write_byte(memory, self.accumulator, zero_page_address),
@@ -41,36 +48,58 @@ impl StoreRegister for Processor {
         }
     }
 
-    fn store_absolute(
+    fn store_absolute<B: Bus>(
         &mut self,
-        memory: &mut Memory,
+        memory: &mut B,
         register: Registers,
         offset_register: Option<Registers>,
     ) -> () {
-        let zero_page_address: u16 = self.addr_absolute(memory, offset_register);
+        let mode = match offset_register {
+            Some(RegisterX) => AbsoluteX,
+            Some(RegisterY) => AbsoluteY,
+            _ => Absolute,
+        };
+        let absolute_address: u16 = self.addr_mode(memory, mode, MemoryAccess::Write);
 
         match register {
-            Accumulator => self.write_byte(memory, self.accumulator, zero_page_address),
-            RegisterX => self.write_byte(memory, self.register_x, zero_page_address),
-            RegisterY => self.write_byte(memory, self.register_y, zero_page_address),
+            Accumulator => self.write_byte(memory, self.accumulator, absolute_address),
+            RegisterX => self.write_byte(memory, self.register_x, absolute_address),
+            RegisterY => self.write_byte(memory, self.register_y, absolute_address),
         }
+    }
 
-        match offset_register {
-            Some(RegisterX) | Some(RegisterY) => self.decrement_cycles(1),
-            _ => {}
-        }
+    fn store_indirect_x<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, IndirectX, MemoryAccess::Read);
+
+        self.write_byte(memory, self.accumulator, indirect_addr);
     }
 
-    fn store_indirect_x(&mut self, memory: &mut Memory) -> () {
-        let indirect_addr: u16 = self.addr_indirect_x(memory);
+    fn store_indirect_y<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, IndirectY, MemoryAccess::Write);
 
         self.write_byte(memory, self.accumulator, indirect_addr);
     }
 
-    fn store_indirect_y(&mut self, memory: &mut Memory) -> () {
-        let indirect_addr: u16 = self.addr_indirect_y(memory);
+    fn store_zero_page_indirect<B: Bus>(&mut self, memory: &mut B) -> () {
+        let indirect_addr: u16 = self.addr_mode(memory, ZeroPageIndirect, MemoryAccess::Write);
 
         self.write_byte(memory, self.accumulator, indirect_addr);
-        self.decrement_cycles(1);
+    }
+
+    fn stz_zero_page<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let zero_page_address: u16 =
+            self.addr_mode(memory, zero_page_mode(offset_register), MemoryAccess::Read);
+
+        self.write_byte(memory, 0, zero_page_address);
+    }
+
+    fn stz_absolute<B: Bus>(&mut self, memory: &mut B, offset_register: Option<Registers>) -> () {
+        let mode = match offset_register {
+            Some(_) => AbsoluteX,
+            None => Absolute,
+        };
+        let absolute_address: u16 = self.addr_mode(memory, mode, MemoryAccess::Write);
+
+        self.write_byte(memory, 0, absolute_address);
     }
 }
@@ -3,176 +3,104 @@ use crate::cpu;
 use crate::mem::*;
 
 use cpu::functions::byte::*;
+use cpu::opcodes::AddressingMode::{self, *};
+use cpu::opcodes::MemoryAccess;
 use cpu::opcodes::ProcessorStatus::*;
-use cpu::opcodes::Registers::*;
-use cpu::opcodes::*;
 use cpu::processor::*;
+use cpu::variant::Quirk;
+
+fn read_operand<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    opcode: u8,
+    mode: AddressingMode,
+) -> (u8, Option<u16>) {
+    /* Implied is the accumulator form, anything else reads the byte it will write back */
+    if mode == Implied {
+        processor.decrement_cycles(1);
+        return (processor.accumulator, None);
+    }
 
-pub trait Shifts {
-    fn shift_left(&mut self, memory: &mut Memory, opcode: u8) -> ();
-    fn shift_right(&mut self, memory: &mut Memory, opcode: u8) -> ();
+    let access: MemoryAccess = indexed_access(processor, opcode, MemoryAccess::Modify);
+    let address: u16 = processor.addr_mode(memory, mode, access);
+    return (processor.read_byte(memory, address), Some(address));
+}
+
+fn write_result<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    operand: u8,
+    result: u8,
+    address: Option<u16>,
+) -> () {
+    match address {
+        Some(address) => processor.write_modified(memory, operand, result, address),
+        None => processor.accumulator = result,
+    }
 
-    fn rotate_left(&mut self, memory: &mut Memory, opcode: u8) -> ();
-    fn rotate_right(&mut self, memory: &mut Memory, opcode: u8) -> ();
+    processor.set_status(NegativeFlag, fetch_bit(result, 7));
+    processor.set_status(ZeroFlag, result == 0);
 }
 
-impl Shifts for Processor {
-    fn shift_left(&mut self, memory: &mut Memory, opcode: u8) -> () {
-        let mut result: u8 = 0;
-        let mut old_carry: bool = false;
-        let mut address: Option<u16> = None;
-
-        match opcode {
-            ASL_ACCUMULATOR => {
-                old_carry = fetch_bit(self.accumulator, 7);
-                result = self.accumulator << 1;
-                self.accumulator = result;
-            }
-            ASL_ZERO_PAGE => {
-                address = Some(self.addr_zero_page(memory, None));
-            }
-            ASL_ZERO_PAGE_X => {
-                address = Some(self.addr_zero_page(memory, Some(RegisterX)));
-            }
-            ASL_ABSOLUTE => {
-                address = Some(self.addr_absolute(memory, None));
-            }
-            ASL_ABSOLUTE_X => {
-                address = Some(self.addr_absolute(memory, Some(RegisterX)));
-            }
-            _ => {}
-        }
+pub trait Shifts {
+    fn shift_left<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> ();
+    fn shift_right<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> ();
+
+    fn rotate_left<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> ();
+    fn rotate_right<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> ();
+    fn rotate_right_missing<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        opcode: u8,
+        mode: AddressingMode,
+    ) -> ();
+}
 
-        if address.is_some() {
-            let operand = Some(self.read_byte(memory, address.unwrap()));
-            old_carry = fetch_bit(operand.unwrap(), 7);
-            result = operand.unwrap() << 1;
-            self.write_byte(memory, result, address.unwrap());
-        }
+impl Shifts for Processor {
+    fn shift_left<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> () {
+        let (operand, address) = read_operand(self, memory, opcode, mode);
+        let result: u8 = operand << 1;
 
-        self.set_status(NegativeFlag, fetch_bit(result, 7));
-        self.set_status(ZeroFlag, result == 0);
-        self.set_status(CarryFlag, old_carry);
-        self.decrement_cycles(1);
+        write_result(self, memory, operand, result, address);
+        self.set_status(CarryFlag, fetch_bit(operand, 7));
     }
 
-    fn shift_right(&mut self, memory: &mut Memory, opcode: u8) -> () {
-        let mut result: u8 = 0;
-        let mut old_carry: bool = false;
-        let mut address: Option<u16> = None;
-
-        match opcode {
-            LSR_ACCUMULATOR => {
-                old_carry = fetch_bit(self.accumulator, 0);
-                result = self.accumulator >> 1;
-                self.accumulator = result;
-            }
-            LSR_ZERO_PAGE => {
-                address = Some(self.addr_zero_page(memory, None));
-            }
-            LSR_ZERO_PAGE_X => {
-                address = Some(self.addr_zero_page(memory, Some(RegisterX)));
-            }
-            LSR_ABSOLUTE => {
-                address = Some(self.addr_absolute(memory, None));
-            }
-            LSR_ABSOLUTE_X => {
-                address = Some(self.addr_absolute(memory, Some(RegisterX)));
-            }
-            _ => {}
-        }
+    fn shift_right<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> () {
+        let (operand, address) = read_operand(self, memory, opcode, mode);
+        let result: u8 = operand >> 1;
 
-        if address.is_some() {
-            let operand = Some(self.read_byte(memory, address.unwrap()));
-            old_carry = fetch_bit(operand.unwrap(), 0);
-            result = operand.unwrap() >> 1;
-            self.write_byte(memory, result, address.unwrap());
-        }
-
-        self.set_status(NegativeFlag, fetch_bit(result, 7));
-        self.set_status(ZeroFlag, result == 0);
-        self.set_status(CarryFlag, old_carry);
-        self.decrement_cycles(1);
+        write_result(self, memory, operand, result, address);
+        self.set_status(CarryFlag, fetch_bit(operand, 0));
     }
 
-    fn rotate_left(&mut self, memory: &mut Memory, opcode: u8) -> () {
-        let mut result: u8 = 0;
-        let mut old_carry: bool = false;
-        let mut address: Option<u16> = None;
-
-        match opcode {
-            ROL_ACCUMULATOR => {
-                old_carry = fetch_bit(self.accumulator, 7);
-                result = self.accumulator << 1;
-                result = set_bit(result, 0, self.fetch_status(CarryFlag));
-                self.accumulator = result;
-            }
-            ROL_ZERO_PAGE => {
-                address = Some(self.addr_zero_page(memory, None));
-            }
-            ROL_ZERO_PAGE_X => {
-                address = Some(self.addr_zero_page(memory, Some(RegisterX)));
-            }
-            ROL_ABSOLUTE => {
-                address = Some(self.addr_absolute(memory, None));
-            }
-            ROL_ABSOLUTE_X => {
-                address = Some(self.addr_absolute(memory, Some(RegisterX)));
-            }
-            _ => {}
-        }
-
-        if address.is_some() {
-            let operand = Some(self.read_byte(memory, address.unwrap()));
-            old_carry = fetch_bit(operand.unwrap(), 7);
-            result = operand.unwrap() << 1;
-            result = set_bit(result, 0, self.fetch_status(CarryFlag));
-            self.write_byte(memory, result, address.unwrap());
-        }
+    fn rotate_left<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> () {
+        let (operand, address) = read_operand(self, memory, opcode, mode);
+        let result: u8 = set_bit(operand << 1, 0, self.fetch_status(CarryFlag));
 
-        self.set_status(NegativeFlag, fetch_bit(result, 7));
-        self.set_status(ZeroFlag, result == 0);
-        self.set_status(CarryFlag, old_carry);
-        self.decrement_cycles(1);
+        write_result(self, memory, operand, result, address);
+        self.set_status(CarryFlag, fetch_bit(operand, 7));
     }
 
-    fn rotate_right(&mut self, memory: &mut Memory, opcode: u8) -> () {
-        let mut result: u8 = 0;
-        let mut old_carry: bool = false;
-        let mut address: Option<u16> = None;
-
-        match opcode {
-            ROR_ACCUMULATOR => {
-                old_carry = fetch_bit(self.accumulator, 0);
-                result = self.accumulator >> 1;
-                result = set_bit(result, 7, self.fetch_status(CarryFlag));
-                self.accumulator = result;
-            }
-            ROR_ZERO_PAGE => {
-                address = Some(self.addr_zero_page(memory, None));
-            }
-            ROR_ZERO_PAGE_X => {
-                address = Some(self.addr_zero_page(memory, Some(RegisterX)));
-            }
-            ROR_ABSOLUTE => {
-                address = Some(self.addr_absolute(memory, None));
-            }
-            ROR_ABSOLUTE_X => {
-                address = Some(self.addr_absolute(memory, Some(RegisterX)));
-            }
-            _ => {}
+    fn rotate_right<B: Bus>(&mut self, memory: &mut B, opcode: u8, mode: AddressingMode) -> () {
+        if self.variant.has_quirk(Quirk::MissingRotateRight) {
+            return self.rotate_right_missing(memory, opcode, mode);
         }
 
-        if address.is_some() {
-            let operand = Some(self.read_byte(memory, address.unwrap()));
-            old_carry = fetch_bit(operand.unwrap(), 0);
-            result = operand.unwrap() >> 1;
-            self.write_byte(memory, result, address.unwrap());
-        }
+        let (operand, address) = read_operand(self, memory, opcode, mode);
+        let result: u8 = set_bit(operand >> 1, 7, self.fetch_status(CarryFlag));
+
+        write_result(self, memory, operand, result, address);
+        self.set_status(CarryFlag, fetch_bit(operand, 0));
+    }
 
-        self.set_status(NegativeFlag, fetch_bit(result, 7));
-        self.set_status(ZeroFlag, result == 0);
-        self.set_status(CarryFlag, old_carry);
-        self.decrement_cycles(1);
+    fn rotate_right_missing<B: Bus>(
+        &mut self,
+        memory: &mut B,
+        opcode: u8,
+        mode: AddressingMode,
+    ) -> () {
+        /* Rev A parts shift left without touching carry, bit 0 is always cleared */
+        let (operand, address) = read_operand(self, memory, opcode, mode);
+        write_result(self, memory, operand, operand << 1, address);
     }
 }
@@ -2,22 +2,36 @@ use super::registers::load::LoadRegister;
 use crate::cpu;
 use crate::mem::fetch_bit;
 use crate::mem::set_bit;
-use crate::mem::Memory;
+use crate::mem::Bus;
 
+use cpu::functions::byte::*;
 use cpu::functions::stack::*;
+use cpu::instructions::system::System;
 use cpu::opcodes::Registers::*;
 use cpu::processor::Functions;
 use cpu::processor::Processor;
 
+fn push_register<B: Bus>(processor: &mut Processor, memory: &mut B, value: u8) -> () {
+    // The byte after the opcode is read and dropped before the push
+    processor.read_byte(memory, processor.program_counter);
+    processor.push_byte_to_stack(memory, value);
+}
+
 pub trait StackOperations {
     fn tsx(&mut self) -> ();
     fn txs(&mut self) -> ();
 
-    fn pha(&mut self, memory: &mut Memory) -> ();
-    fn php(&mut self, memory: &mut Memory) -> ();
+    fn pha<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn php<B: Bus>(&mut self, memory: &mut B) -> ();
+
+    fn pla<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn plp<B: Bus>(&mut self, memory: &mut B) -> ();
+
+    fn phx<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn phy<B: Bus>(&mut self, memory: &mut B) -> ();
 
-    fn pla(&mut self, memory: &mut Memory) -> ();
-    fn plp(&mut self, memory: &mut Memory) -> ();
+    fn plx<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn ply<B: Bus>(&mut self, memory: &mut B) -> ();
 }
 
 impl StackOperations for Processor {
@@ -33,11 +47,11 @@ impl StackOperations for Processor {
         self.decrement_cycles(1);
     }
 
-    fn pha(&mut self, memory: &mut Memory) -> () {
-        self.push_byte_to_stack(memory, self.accumulator);
+    fn pha<B: Bus>(&mut self, memory: &mut B) -> () {
+        push_register(self, memory, self.accumulator);
     }
 
-    fn php(&mut self, memory: &mut Memory) -> () {
+    fn php<B: Bus>(&mut self, memory: &mut B) -> () {
         /*
             When pushing processor status to the stack, the byte that is pushed is not an exact copy of the status.
             The 4th bit pushed (break flag) will be set to 1 (true)
@@ -47,20 +61,43 @@ impl StackOperations for Processor {
         status_to_push = set_bit(status_to_push, 4, true);
         status_to_push = set_bit(status_to_push, 5, true);
 
-        self.push_byte_to_stack(memory, status_to_push);
+        push_register(self, memory, status_to_push);
     }
 
-    fn pla(&mut self, memory: &mut Memory) -> () {
+    fn pla<B: Bus>(&mut self, memory: &mut B) -> () {
+        self.stack_dummy_reads(memory);
         let byte_value = self.pop_byte_from_stack(memory);
         self.set_register(Accumulator, byte_value);
     }
 
-    fn plp(&mut self, memory: &mut Memory) -> () {
+    fn plp<B: Bus>(&mut self, memory: &mut B) -> () {
         /* When setting the processor status from the stack, the 4th and 5th bit (break and unused) are cleared */
+        self.stack_dummy_reads(memory);
         let mut status_to_set = self.pop_byte_from_stack(memory);
         status_to_set = set_bit(status_to_set, 4, false);
         status_to_set = set_bit(status_to_set, 5, false);
 
+        self.latch_interrupt_disable();
         self.status = status_to_set;
     }
+
+    fn phx<B: Bus>(&mut self, memory: &mut B) -> () {
+        push_register(self, memory, self.register_x);
+    }
+
+    fn phy<B: Bus>(&mut self, memory: &mut B) -> () {
+        push_register(self, memory, self.register_y);
+    }
+
+    fn plx<B: Bus>(&mut self, memory: &mut B) -> () {
+        self.stack_dummy_reads(memory);
+        let byte_value = self.pop_byte_from_stack(memory);
+        self.set_register(RegisterX, byte_value);
+    }
+
+    fn ply<B: Bus>(&mut self, memory: &mut B) -> () {
+        self.stack_dummy_reads(memory);
+        let byte_value = self.pop_byte_from_stack(memory);
+        self.set_register(RegisterY, byte_value);
+    }
 }
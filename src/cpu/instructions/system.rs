@@ -1,43 +1,232 @@
 use super::registers::load::LoadRegister;
 use crate::cpu;
+use crate::cpu::functions::byte::{catch_up_bus, ByteFunctions};
 use crate::cpu::functions::stack::StackFunctions;
 use crate::cpu::functions::word::WordFunctions;
+use crate::mem::Bus;
 use crate::mem::{fetch_bit, set_bit};
-use crate::Memory;
 
+use cpu::interrupts::*;
+use cpu::native::decode::enter_interrupt;
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::processor::*;
-
-const INTERRUPT_VECTOR: u16 = 0xFFFE;
+use cpu::stepper::CyclePhase;
+use cpu::variant::{InstructionSet, Quirk};
 
 pub trait System {
-    fn force_interrupt(&mut self, memory: &mut Memory) -> ();
-    fn return_from_interrupt(&mut self, memory: &mut Memory) -> ();
+    fn force_interrupt<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn return_from_interrupt<B: Bus>(&mut self, memory: &mut B) -> ();
+
+    fn wait_for_interrupt(&mut self) -> ();
+    fn stop(&mut self) -> ();
+
+    fn pending_interrupt(&mut self) -> Option<bool>;
+    fn poll_interrupts<B: Bus>(&mut self, memory: &mut B) -> bool;
+    fn service_interrupt<B: Bus>(&mut self, memory: &mut B, non_maskable: bool) -> ();
+
+    fn sync_bus<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn wake_on_interrupt(&mut self) -> ();
+    fn apply_set_overflow(&mut self) -> ();
+    fn latch_interrupt_disable(&mut self) -> ();
+    fn delay_interrupt_poll(&mut self) -> ();
+    fn hijack_vector(&mut self, vector: u16, nmi_vector: u16) -> u16;
 }
 
 impl System for Processor {
-    fn force_interrupt(&mut self, memory: &mut Memory) -> () {
-        self.push_pc_plus_one_to_stack(memory);
+    fn force_interrupt<B: Bus>(&mut self, memory: &mut B) -> () {
+        // BRK reads the byte after it as padding, so the return address skips over it
+        self.fetch_byte(memory);
+        self.push_pc_to_stack(memory);
         let mut processor_status = self.status;
         processor_status = set_bit(processor_status, 4, true);
         processor_status = set_bit(processor_status, 5, true);
+        let vector: u16 = self.hijack_vector(IRQ_VECTOR, NMI_VECTOR);
         self.push_byte_to_stack(memory, processor_status);
 
-        self.program_counter = self.read_word(memory, INTERRUPT_VECTOR);
+        self.program_counter = self.read_word(memory, vector);
 
         self.set_status(BreakCommand, true);
         self.set_status(InterruptDisable, true);
+
+        if self.variant.has_quirk(Quirk::BreakClearsDecimal) {
+            self.set_status(DecimalMode, false);
+        }
     }
 
-    fn return_from_interrupt(&mut self, memory: &mut Memory) -> () {
+    fn return_from_interrupt<B: Bus>(&mut self, memory: &mut B) -> () {
+        self.stack_dummy_reads(memory);
         let mut new_processor_status = self.pop_byte_from_stack(memory);
         new_processor_status = set_bit(new_processor_status, 4, fetch_bit(self.status, 4));
         new_processor_status = set_bit(new_processor_status, 5, fetch_bit(self.status, 5));
 
         self.status = new_processor_status;
-        self.cycles += 1; // Extra cycle is consumed in other functions / not consumed in this (but this fixes that issue so...)
         self.program_counter = self.pop_word_from_stack(memory);
         self.set_status(BreakCommand, false);
         self.set_status(UnusedFlag, false);
     }
+
+    fn wait_for_interrupt(&mut self) -> () {
+        self.state = ExecutionState::Waiting;
+        self.decrement_cycles(2);
+    }
+
+    fn stop(&mut self) -> () {
+        self.state = ExecutionState::Stopped;
+        self.decrement_cycles(2);
+    }
+
+    fn pending_interrupt(&mut self) -> Option<bool> {
+        /*
+            Decided between instructions from what the last poll saw, a pending NMI always wins over
+            IRQ. Some(true) is an NMI. tick() polls on the second to last cycle of each instruction,
+            whole instructions only let the host move the lines between them so the boundary stands in.
+        */
+        let masked: bool = match self.poll.lagged_disable.take() {
+            Some(disable) => disable,
+            None => self.fetch_status(InterruptDisable),
+        };
+        self.poll.branch_delayed = false;
+        let (nmi, irq): (bool, bool) = match self.stepper.take_sample() {
+            Some(sample) => sample,
+            None => (
+                self.interrupts.nmi_pending(),
+                self.interrupts.irq_asserted(),
+            ),
+        };
+
+        if self.variant.has_interrupt_lines() == false {
+            return None;
+        }
+
+        if nmi && self.interrupts.nmi_pending() {
+            self.interrupts.acknowledge_nmi();
+            return Some(true);
+        }
+
+        if irq && masked == false {
+            return Some(false);
+        }
+
+        return None;
+    }
+
+    fn poll_interrupts<B: Bus>(&mut self, memory: &mut B) -> bool {
+        match self.pending_interrupt() {
+            Some(non_maskable) => {
+                self.service_interrupt(memory, non_maskable);
+                return true;
+            }
+            None => return false,
+        }
+    }
+
+    fn service_interrupt<B: Bus>(&mut self, memory: &mut B, non_maskable: bool) -> () {
+        /*
+            A hardware interrupt runs the BRK sequence without an opcode: two idle cycles while the
+            fetched opcode is thrown away, the return address, the status with B clear, then the vector.
+        */
+        self.read_byte(memory, self.program_counter);
+        self.read_byte(memory, self.program_counter);
+
+        if self.variant.instruction_set() == InstructionSet::Wdc65C816 {
+            let vector: u16 = match (non_maskable, self.native.emulation) {
+                (true, true) => NMI_VECTOR,
+                (false, true) => IRQ_VECTOR,
+                (true, false) => NATIVE_NMI_VECTOR,
+                (false, false) => NATIVE_IRQ_VECTOR,
+            };
+            let mut processor_status = self.status;
+            if self.native.emulation == true {
+                processor_status = set_bit(processor_status, 4, false);
+                processor_status = set_bit(processor_status, 5, true);
+            }
+            return enter_interrupt(self, memory, processor_status, vector);
+        }
+
+        self.push_pc_to_stack(memory);
+        let mut processor_status = self.status;
+        processor_status = set_bit(processor_status, 4, false);
+        processor_status = set_bit(processor_status, 5, true);
+        let vector: u16 = if non_maskable { NMI_VECTOR } else { IRQ_VECTOR };
+        let vector: u16 = self.hijack_vector(vector, NMI_VECTOR);
+        let stack_address: u16 = self.stack_pointer_to_address();
+        self.write_byte(memory, processor_status, stack_address);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+
+        self.set_status(InterruptDisable, true);
+        if self.variant.has_quirk(Quirk::BreakClearsDecimal) {
+            self.set_status(DecimalMode, false);
+        }
+
+        self.program_counter = self.read_word(memory, vector);
+    }
+
+    fn sync_bus<B: Bus>(&mut self, memory: &mut B) -> () {
+        /* At an instruction boundary the bus is caught up to the clock and its IRQ output joins the host's */
+        catch_up_bus(self, memory);
+        self.interrupts.set_bus_irq(memory.irq());
+    }
+
+    fn wake_on_interrupt(&mut self) -> () {
+        /* Any interrupt ends WAI, a masked IRQ simply resumes after it without being serviced */
+        if self.state == ExecutionState::Waiting
+            && self.variant.has_interrupt_lines()
+            && (self.interrupts.nmi_pending() || self.interrupts.irq_asserted())
+        {
+            self.state = ExecutionState::Running;
+            self.stepper.take_sample(); // The poll WAI made is stale, the lines that woke it are looked at afresh
+        }
+    }
+
+    fn apply_set_overflow(&mut self) -> () {
+        /* A falling edge on SO sets V, picked up before the next instruction starts */
+        if self.control.take_set_overflow() && self.variant.has_set_overflow_pin() {
+            self.set_status(OverflowFlag, true);
+        }
+    }
+
+    fn latch_interrupt_disable(&mut self) -> () {
+        /*
+            Called by CLI, SEI and PLP before they change I. They do it on their last cycle, after the
+            poll, so the interrupt decision at the end of the instruction still sees the old flag.
+        */
+        if self.poll.lagged_disable.is_none() {
+            self.poll.lagged_disable = Some(self.fetch_status(InterruptDisable));
+        }
+    }
+
+    fn delay_interrupt_poll(&mut self) -> () {
+        // A taken branch that needs no fix-up does not poll again on its extra cycle
+        self.poll.branch_delayed = true;
+    }
+
+    fn hijack_vector(&mut self, vector: u16, nmi_vector: u16) -> u16 {
+        /*
+            The vector is settled on the cycle that pushes the status, so an NMI latched by then takes
+            over a BRK or IRQ sequence. The status already on its way to the stack keeps its B flag.
+        */
+        if vector == nmi_vector || self.variant.has_interrupt_lines() == false {
+            return vector;
+        }
+
+        let hijacked: bool = match self.stepper.phase() {
+            CyclePhase::Replay(_) => self.stepper.hijacked(),
+            CyclePhase::Discard => false,
+            CyclePhase::Direct | CyclePhase::Live => {
+                let pending: bool = self.interrupts.nmi_pending();
+                if pending {
+                    self.interrupts.acknowledge_nmi();
+                }
+                if self.stepper.phase() == CyclePhase::Live {
+                    self.stepper.record_hijack(pending);
+                }
+                pending
+            }
+        };
+
+        if hijacked {
+            return nmi_vector;
+        }
+        return vector;
+    }
 }
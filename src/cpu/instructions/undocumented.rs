@@ -0,0 +1,400 @@
+use super::addressing::*;
+use super::arithmetic::add::add;
+use super::arithmetic::compare::compare;
+use super::arithmetic::subtract::subtract;
+use super::registers::load::LoadRegister;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::functions::byte::*;
+use cpu::functions::word::*;
+use cpu::opcodes::AddressingMode::{self, *};
+use cpu::opcodes::MemoryAccess::{self, *};
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::DecimalArithmetic;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Slo,
+    Rla,
+    Sre,
+    Rra,
+    Sax,
+    Lax,
+    Dcp,
+    Isc,
+    Anc,
+    Alr,
+    Arr,
+    Xaa,
+    Lxa,
+    Sbx,
+    Sbc,
+    Ahx,
+    Shx,
+    Shy,
+    Tas,
+    Las,
+    Nop,
+    Jam,
+}
+
+use Operation::*;
+
+// Looked up by opcode on every undocumented instruction
+const DECODED: [Option<(Operation, AddressingMode)>; 256] = decode_all();
+
+const fn decode_all() -> [Option<(Operation, AddressingMode)>; 256] {
+    let mut table: [Option<(Operation, AddressingMode)>; 256] = [None; 256];
+    let mut opcode: usize = 0;
+    while opcode < 256 {
+        table[opcode] = decode(opcode as u8);
+        opcode += 1;
+    }
+    return table;
+}
+
+const fn decode(opcode: u8) -> Option<(Operation, AddressingMode)> {
+    let decoded = match opcode {
+        SLO_ZERO_PAGE => (Slo, ZeroPage),
+        SLO_ZERO_PAGE_X => (Slo, ZeroPageX),
+        SLO_ABSOLUTE => (Slo, Absolute),
+        SLO_ABSOLUTE_X => (Slo, AbsoluteX),
+        SLO_ABSOLUTE_Y => (Slo, AbsoluteY),
+        SLO_INDIRECT_X => (Slo, IndirectX),
+        SLO_INDIRECT_Y => (Slo, IndirectY),
+
+        RLA_ZERO_PAGE => (Rla, ZeroPage),
+        RLA_ZERO_PAGE_X => (Rla, ZeroPageX),
+        RLA_ABSOLUTE => (Rla, Absolute),
+        RLA_ABSOLUTE_X => (Rla, AbsoluteX),
+        RLA_ABSOLUTE_Y => (Rla, AbsoluteY),
+        RLA_INDIRECT_X => (Rla, IndirectX),
+        RLA_INDIRECT_Y => (Rla, IndirectY),
+
+        SRE_ZERO_PAGE => (Sre, ZeroPage),
+        SRE_ZERO_PAGE_X => (Sre, ZeroPageX),
+        SRE_ABSOLUTE => (Sre, Absolute),
+        SRE_ABSOLUTE_X => (Sre, AbsoluteX),
+        SRE_ABSOLUTE_Y => (Sre, AbsoluteY),
+        SRE_INDIRECT_X => (Sre, IndirectX),
+        SRE_INDIRECT_Y => (Sre, IndirectY),
+
+        RRA_ZERO_PAGE => (Rra, ZeroPage),
+        RRA_ZERO_PAGE_X => (Rra, ZeroPageX),
+        RRA_ABSOLUTE => (Rra, Absolute),
+        RRA_ABSOLUTE_X => (Rra, AbsoluteX),
+        RRA_ABSOLUTE_Y => (Rra, AbsoluteY),
+        RRA_INDIRECT_X => (Rra, IndirectX),
+        RRA_INDIRECT_Y => (Rra, IndirectY),
+
+        SAX_ZERO_PAGE => (Sax, ZeroPage),
+        SAX_ZERO_PAGE_Y => (Sax, ZeroPageY),
+        SAX_ABSOLUTE => (Sax, Absolute),
+        SAX_INDIRECT_X => (Sax, IndirectX),
+
+        LAX_ZERO_PAGE => (Lax, ZeroPage),
+        LAX_ZERO_PAGE_Y => (Lax, ZeroPageY),
+        LAX_ABSOLUTE => (Lax, Absolute),
+        LAX_ABSOLUTE_Y => (Lax, AbsoluteY),
+        LAX_INDIRECT_X => (Lax, IndirectX),
+        LAX_INDIRECT_Y => (Lax, IndirectY),
+
+        DCP_ZERO_PAGE => (Dcp, ZeroPage),
+        DCP_ZERO_PAGE_X => (Dcp, ZeroPageX),
+        DCP_ABSOLUTE => (Dcp, Absolute),
+        DCP_ABSOLUTE_X => (Dcp, AbsoluteX),
+        DCP_ABSOLUTE_Y => (Dcp, AbsoluteY),
+        DCP_INDIRECT_X => (Dcp, IndirectX),
+        DCP_INDIRECT_Y => (Dcp, IndirectY),
+
+        ISC_ZERO_PAGE => (Isc, ZeroPage),
+        ISC_ZERO_PAGE_X => (Isc, ZeroPageX),
+        ISC_ABSOLUTE => (Isc, Absolute),
+        ISC_ABSOLUTE_X => (Isc, AbsoluteX),
+        ISC_ABSOLUTE_Y => (Isc, AbsoluteY),
+        ISC_INDIRECT_X => (Isc, IndirectX),
+        ISC_INDIRECT_Y => (Isc, IndirectY),
+
+        ANC_IMMEDIATE | ANC_IMMEDIATE_2B => (Anc, Immediate),
+        ALR_IMMEDIATE => (Alr, Immediate),
+        ARR_IMMEDIATE => (Arr, Immediate),
+        XAA_IMMEDIATE => (Xaa, Immediate),
+        LXA_IMMEDIATE => (Lxa, Immediate),
+        SBX_IMMEDIATE => (Sbx, Immediate),
+        SBC_IMMEDIATE_EB => (Sbc, Immediate),
+
+        AHX_ABSOLUTE_Y => (Ahx, AbsoluteY),
+        AHX_INDIRECT_Y => (Ahx, IndirectY),
+        SHY_ABSOLUTE_X => (Shy, AbsoluteX),
+        SHX_ABSOLUTE_Y => (Shx, AbsoluteY),
+        TAS_ABSOLUTE_Y => (Tas, AbsoluteY),
+        LAS_ABSOLUTE_Y => (Las, AbsoluteY),
+
+        NOP_IMPLIED_1A | NOP_IMPLIED_3A | NOP_IMPLIED_5A | NOP_IMPLIED_7A | NOP_IMPLIED_DA
+        | NOP_IMPLIED_FA => (Nop, Implied),
+        NOP_IMMEDIATE_80 | NOP_IMMEDIATE_82 | NOP_IMMEDIATE_89 | NOP_IMMEDIATE_C2
+        | NOP_IMMEDIATE_E2 => (Nop, Immediate),
+        NOP_ZERO_PAGE_04 | NOP_ZERO_PAGE_44 | NOP_ZERO_PAGE_64 => (Nop, ZeroPage),
+        NOP_ZERO_PAGE_X_14 | NOP_ZERO_PAGE_X_34 | NOP_ZERO_PAGE_X_54 | NOP_ZERO_PAGE_X_74
+        | NOP_ZERO_PAGE_X_D4 | NOP_ZERO_PAGE_X_F4 => (Nop, ZeroPageX),
+        NOP_ABSOLUTE_0C => (Nop, Absolute),
+        NOP_ABSOLUTE_X_1C | NOP_ABSOLUTE_X_3C | NOP_ABSOLUTE_X_5C | NOP_ABSOLUTE_X_7C
+        | NOP_ABSOLUTE_X_DC | NOP_ABSOLUTE_X_FC => (Nop, AbsoluteX),
+
+        JAM_02 | JAM_12 | JAM_22 | JAM_32 | JAM_42 | JAM_52 | JAM_62 | JAM_72 | JAM_92 | JAM_B2
+        | JAM_D2 | JAM_F2 => (Jam, Implied),
+
+        _ => return None,
+    };
+
+    return Some(decoded);
+}
+
+fn access_for(operation: Operation) -> MemoryAccess {
+    match operation {
+        Slo | Rla | Sre | Rra | Dcp | Isc => Modify,
+        Sax | Ahx | Shx | Shy | Tas => Write,
+        _ => Read,
+    }
+}
+
+fn shift_left(processor: &mut Processor, value: u8, carry_in: bool) -> u8 {
+    processor.set_status(CarryFlag, fetch_bit(value, 7));
+    return set_bit(value << 1, 0, carry_in);
+}
+
+fn shift_right(processor: &mut Processor, value: u8, carry_in: bool) -> u8 {
+    processor.set_status(CarryFlag, fetch_bit(value, 0));
+    return set_bit(value >> 1, 7, carry_in);
+}
+
+fn modify(processor: &mut Processor, operation: Operation, value: u8) -> u8 {
+    let carry: bool = processor.fetch_status(CarryFlag);
+
+    match operation {
+        Slo => {
+            let result = shift_left(processor, value, false);
+            processor.set_register(Accumulator, processor.accumulator | result);
+            return result;
+        }
+        Rla => {
+            let result = shift_left(processor, value, carry);
+            processor.set_register(Accumulator, processor.accumulator & result);
+            return result;
+        }
+        Sre => {
+            let result = shift_right(processor, value, false);
+            processor.set_register(Accumulator, processor.accumulator ^ result);
+            return result;
+        }
+        Rra => {
+            let result = shift_right(processor, value, carry);
+            add(processor, result as u16);
+            return result;
+        }
+        Dcp => {
+            let result = value.wrapping_sub(1);
+            compare(processor, result, Accumulator);
+            return result;
+        }
+        Isc => {
+            let result = value.wrapping_add(1);
+            subtract(processor, result);
+            return result;
+        }
+        _ => return value,
+    }
+}
+
+fn arr(processor: &mut Processor, operand: u8) -> () {
+    let value: u8 = processor.accumulator & operand;
+    let carry: bool = processor.fetch_status(CarryFlag);
+    let mut result: u8 = set_bit(value >> 1, 7, carry);
+
+    processor.set_status(ZeroFlag, result == 0);
+    processor.set_status(NegativeFlag, fetch_bit(result, 7));
+
+    if processor.fetch_status(DecimalMode)
+        && processor.variant.decimal_arithmetic() == DecimalArithmetic::Nmos
+    {
+        // The NMOS decimal ARR fixes up each nibble of the rotated value as if it had been an ADC result
+        processor.set_status(OverflowFlag, fetch_bit(value ^ result, 6));
+
+        if (value & 0x0F) + (value & 0x01) > 0x05 {
+            result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+        }
+
+        let decimal_carry: bool = (value >> 4) as u16 + (value & 0x10 != 0) as u16 > 0x05;
+        if decimal_carry {
+            result = result.wrapping_add(0x60);
+        }
+        processor.set_status(CarryFlag, decimal_carry);
+    } else {
+        processor.set_status(CarryFlag, fetch_bit(result, 6));
+        processor.set_status(OverflowFlag, fetch_bit(result, 6) != fetch_bit(result, 5));
+    }
+
+    processor.accumulator = result;
+}
+
+fn read_operation(processor: &mut Processor, operation: Operation, value: u8) -> () {
+    match operation {
+        Lax => {
+            processor.set_register(Accumulator, value);
+            processor.set_register(RegisterX, value);
+        }
+        Las => {
+            let result: u8 = value & processor.stack_pointer;
+            processor.stack_pointer = result;
+            processor.set_register(Accumulator, result);
+            processor.set_register(RegisterX, result);
+        }
+        Anc => {
+            processor.set_register(Accumulator, processor.accumulator & value);
+            processor.set_status(CarryFlag, fetch_bit(processor.accumulator, 7));
+        }
+        Alr => {
+            let result = shift_right(processor, processor.accumulator & value, false);
+            processor.set_register(Accumulator, result);
+        }
+        Arr => arr(processor, value),
+        Xaa => {
+            let result: u8 =
+                (processor.accumulator | processor.unstable_magic) & processor.register_x & value;
+            processor.set_register(Accumulator, result);
+        }
+        Lxa => {
+            let result: u8 = (processor.accumulator | processor.unstable_magic) & value;
+            processor.set_register(Accumulator, result);
+            processor.set_register(RegisterX, result);
+        }
+        Sbx => {
+            let masked: u8 = processor.accumulator & processor.register_x;
+            processor.set_status(CarryFlag, masked >= value);
+            processor.set_register(RegisterX, masked.wrapping_sub(value));
+        }
+        Sbc => subtract(processor, value),
+        _ => {}
+    }
+}
+
+fn store_high_byte_and<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    mode: AddressingMode,
+    value: u8,
+) -> () {
+    /*
+        SHA/SHX/SHY/TAS store the register ANDed with the high byte of the base address plus one.
+        That term is unstable on real chips, so the configurable magic constant is ORed into it (0x00 gives the usual behaviour).
+        When indexing crosses a page, the stored value also replaces the high byte of the target address.
+    */
+    let base: u16 = match mode {
+        IndirectY => {
+            let pointer: u8 = processor.fetch_byte(memory);
+            let low_byte: u8 = processor.read_byte(memory, pointer as u16);
+            let high_byte: u8 = processor.read_byte(memory, pointer.wrapping_add(1) as u16);
+            low_byte as u16 | ((high_byte as u16) << 8)
+        }
+        _ => processor.fetch_word(memory),
+    };
+    let offset: u8 = match mode {
+        AbsoluteX => processor.register_x,
+        _ => processor.register_y,
+    };
+
+    let mut address: u16 = base.wrapping_add(offset as u16);
+    let high_byte_term: u8 = ((base >> 8) as u8).wrapping_add(1) | processor.unstable_magic;
+    let result: u8 = value & high_byte_term;
+
+    index_fixup_read(processor, memory, base, address);
+    if (address >> 8) != (base >> 8) {
+        address = ((result as u16) << 8) | (address & 0xFF);
+    }
+
+    processor.write_byte(memory, result, address);
+}
+
+pub trait Undocumented {
+    fn undocumented<B: Bus>(&mut self, memory: &mut B, opcode: u8) -> ();
+}
+
+impl Undocumented for Processor {
+    fn undocumented<B: Bus>(&mut self, memory: &mut B, opcode: u8) -> () {
+        let opcode_address: u16 = self.program_counter.wrapping_sub(1);
+        let (operation, mode) = match DECODED[opcode as usize] {
+            Some(decoded) => decoded,
+            None => return,
+        };
+
+        match self.illegal_opcode_policy {
+            IllegalOpcodePolicy::Halt => {
+                self.program_counter = opcode_address;
+                self.fault = Some(Fault::IllegalOpcode {
+                    opcode,
+                    address: opcode_address,
+                });
+                return;
+            }
+            IllegalOpcodePolicy::Nop => {
+                // Consume the operand bytes and the cycles of the instruction without touching memory
+                let access = access_for(operation);
+                if mode == Implied {
+                    self.decrement_cycles(1);
+                } else {
+                    self.addr_mode(memory, mode, access);
+                    match access {
+                        Modify => self.decrement_cycles(3),
+                        _ => self.decrement_cycles(1),
+                    }
+                }
+                return;
+            }
+            IllegalOpcodePolicy::Emulate => {}
+        }
+
+        match operation {
+            Jam => {
+                self.program_counter = opcode_address;
+                self.fault = Some(Fault::Jammed {
+                    opcode,
+                    address: opcode_address,
+                });
+            }
+            Nop => {
+                if mode == Implied {
+                    self.decrement_cycles(1);
+                } else {
+                    let address = self.addr_mode(memory, mode, Read);
+                    self.read_byte(memory, address);
+                }
+            }
+            Slo | Rla | Sre | Rra | Dcp | Isc => {
+                let address = self.addr_mode(memory, mode, Modify);
+                let value: u8 = self.read_byte(memory, address);
+                let result: u8 = modify(self, operation, value);
+                self.write_modified(memory, value, result, address);
+            }
+            Sax => {
+                let address = self.addr_mode(memory, mode, Write);
+                self.write_byte(memory, self.accumulator & self.register_x, address);
+            }
+            Ahx => store_high_byte_and(self, memory, mode, self.accumulator & self.register_x),
+            Shx => store_high_byte_and(self, memory, mode, self.register_x),
+            Shy => store_high_byte_and(self, memory, mode, self.register_y),
+            Tas => {
+                self.stack_pointer = self.accumulator & self.register_x;
+                store_high_byte_and(self, memory, mode, self.stack_pointer);
+            }
+            _ => {
+                let address = self.addr_mode(memory, mode, Read);
+                let value: u8 = self.read_byte(memory, address);
+                read_operation(self, operation, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+pub const NMI_VECTOR: u16 = 0xFFFA;
+pub const RESET_VECTOR: u16 = 0xFFFC;
+pub const IRQ_VECTOR: u16 = 0xFFFE;
+pub const NATIVE_NMI_VECTOR: u16 = 0xFFEA; // 65C816 native mode
+pub const NATIVE_IRQ_VECTOR: u16 = 0xFFEE;
+
+/// Where the processor is in deciding whether to take an interrupt at the next instruction boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterruptPoll {
+    pub lagged_disable: Option<bool>, // I as the poll saw it, CLI, SEI and PLP only change it on their last cycle
+    pub branch_delayed: bool, // A taken branch that stays on its page polls before its extra cycle
+}
+
+impl InterruptPoll {
+    pub fn new() -> InterruptPoll {
+        return InterruptPoll {
+            lagged_disable: None,
+            branch_delayed: false,
+        };
+    }
+}
+
+/// The IRQ and NMI pins, driven by the host on behalf of its devices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterruptLines {
+    irq_sources: BTreeSet<u8>, // IRQ is wired-OR, the line stays low while any source holds it
+    bus_irq: bool,             // Held by a device on the bus rather than by the host
+    nmi_asserted: bool,
+    nmi_pending: bool, // Latched on the falling edge, so holding NMI low only interrupts once
+}
+
+impl InterruptLines {
+    pub fn new() -> InterruptLines {
+        return InterruptLines {
+            irq_sources: BTreeSet::new(),
+            bus_irq: false,
+            nmi_asserted: false,
+            nmi_pending: false,
+        };
+    }
+
+    pub fn assert_irq(&mut self, source: u8) -> () {
+        self.irq_sources.insert(source);
+    }
+
+    pub fn release_irq(&mut self, source: u8) -> () {
+        self.irq_sources.remove(&source);
+    }
+
+    pub fn irq_asserted(&self) -> bool {
+        return self.irq_sources.is_empty() == false || self.bus_irq;
+    }
+
+    pub fn set_bus_irq(&mut self, asserted: bool) -> () {
+        /* Copied from Bus::irq() by the processor, the host's own sources are left alone */
+        self.bus_irq = asserted;
+    }
+
+    pub fn irq_sources(&self) -> Vec<u8> {
+        /* The sources currently pulling IRQ low, in ascending order */
+        return self.irq_sources.iter().copied().collect();
+    }
+
+    pub fn assert_nmi(&mut self) -> () {
+        if self.nmi_asserted == false {
+            self.nmi_pending = true;
+        }
+        self.nmi_asserted = true;
+    }
+
+    pub fn release_nmi(&mut self) -> () {
+        self.nmi_asserted = false;
+    }
+
+    pub fn nmi_asserted(&self) -> bool {
+        return self.nmi_asserted;
+    }
+
+    pub fn nmi_pending(&self) -> bool {
+        return self.nmi_pending;
+    }
+
+    pub fn acknowledge_nmi(&mut self) -> () {
+        self.nmi_pending = false;
+    }
+}
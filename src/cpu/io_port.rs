@@ -0,0 +1,107 @@
+use std::fmt;
+
+pub const DATA_DIRECTION_ADDRESS: u16 = 0x0000;
+pub const PORT_ADDRESS: u16 = 0x0001;
+
+pub const DEFAULT_FADE_CYCLES: u32 = 350_000; // Roughly how long the C64's unconnected port bits hold their charge
+pub const UNCONNECTED_LINES: u8 = 0b1100_0000; // The 6510 only bonds out P0 to P5
+
+/// The 6510's on-chip I/O port, mapped over the first two bytes of the address space.
+pub struct IoPort {
+    pub direction: u8, // A set bit drives the matching pin from `output`
+    pub output: u8,
+    pub input: u8, // Levels the outside world puts on pins that are configured as inputs
+    pub fade_cycles: u32,
+    floating: u8,
+    fade_remaining: u32,
+    on_output: Option<Box<dyn FnMut(u8)>>,
+}
+
+impl fmt::Debug for IoPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoPort")
+            .field("direction", &format!("{:#X}", self.direction))
+            .field("output", &format!("{:#X}", self.output))
+            .field("input", &format!("{:#X}", self.input))
+            .field("floating", &format!("{:#X}", self.floating))
+            .field("fade_remaining", &self.fade_remaining)
+            .finish()
+    }
+}
+
+impl IoPort {
+    pub fn new() -> IoPort {
+        return IoPort {
+            direction: 0x00,
+            output: 0x00,
+            input: 0xFF, // Undriven pins are pulled up
+            fade_cycles: DEFAULT_FADE_CYCLES,
+            floating: 0x00,
+            fade_remaining: 0,
+            on_output: None,
+        };
+    }
+
+    pub fn on_output<F: FnMut(u8) + 'static>(&mut self, callback: F) -> () {
+        /* The callback receives the pin levels every time the program writes either register */
+        self.on_output = Some(Box::new(callback));
+    }
+
+    pub fn reset(&mut self) -> () {
+        /* Reset clears the direction register, turning every pin into an input */
+        self.direction = 0x00;
+        self.floating = 0x00;
+        self.fade_remaining = 0;
+    }
+
+    pub fn lines(&self) -> u8 {
+        return (self.output & self.direction) | (self.input & !self.direction);
+    }
+
+    pub fn handles(&self, address: u16) -> bool {
+        return address == DATA_DIRECTION_ADDRESS || address == PORT_ADDRESS;
+    }
+
+    pub fn read(&self, address: u16) -> u8 {
+        if address == DATA_DIRECTION_ADDRESS {
+            return self.direction;
+        }
+
+        let connected_inputs: u8 = !self.direction & !UNCONNECTED_LINES;
+        let floating_inputs: u8 = !self.direction & UNCONNECTED_LINES;
+
+        return (self.output & self.direction)
+            | (self.input & connected_inputs)
+            | (self.floating & floating_inputs);
+    }
+
+    pub fn write(&mut self, address: u16, data: u8) -> () {
+        if address == DATA_DIRECTION_ADDRESS {
+            // Unconnected pins switched to input keep the level they were driven to until it leaks away
+            let released: u8 = self.direction & !data & UNCONNECTED_LINES;
+            if released != 0 {
+                self.floating = (self.floating & !released) | (self.output & released);
+                self.fade_remaining = self.fade_cycles;
+            }
+            self.direction = data;
+        } else {
+            self.output = data;
+        }
+
+        let lines: u8 = self.lines();
+        if let Some(callback) = self.on_output.as_mut() {
+            callback(lines);
+        }
+    }
+
+    pub fn tick(&mut self, cycles: u32) -> () {
+        if self.fade_remaining == 0 {
+            return;
+        }
+
+        self.fade_remaining = self.fade_remaining.saturating_sub(cycles);
+        if self.fade_remaining == 0 {
+            self.floating = 0x00;
+        }
+    }
+}
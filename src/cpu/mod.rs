@@ -1,4 +1,18 @@
+pub mod blocks;
+pub mod compiled;
+pub mod control;
+pub mod debugger;
+pub mod decode;
+pub mod disassembler;
 pub mod functions;
 pub mod instructions;
+pub mod interrupts;
+pub mod io_port;
+pub mod native;
+pub mod observer;
 pub mod opcodes;
 pub mod processor;
+pub mod regions;
+pub mod stepper;
+pub mod tracer;
+pub mod variant;
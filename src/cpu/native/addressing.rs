@@ -0,0 +1,236 @@
+use super::registers::WideRegisters;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::functions::byte::*;
+use cpu::opcodes::MemoryAccess;
+use cpu::opcodes::Registers;
+use cpu::processor::*;
+
+/// Addressing modes of the 65C816, including the long and stack relative ones the 6502 lacks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NativeMode {
+    Implied,
+    Accumulator,
+    Immediate,
+    Direct,
+    DirectX,
+    DirectY,
+    DirectIndirect,
+    DirectIndirectX,
+    DirectIndirectY,
+    DirectIndirectLong,
+    DirectIndirectLongY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    AbsoluteLong,
+    AbsoluteLongX,
+    StackRelative,
+    StackRelativeIndirectY,
+}
+
+use NativeMode::*;
+
+const BANK_MASK: u32 = 0xFF_FFFF;
+
+fn in_bank_zero(mode: NativeMode) -> bool {
+    /* Direct page and stack operands always live in bank 0, everything else is a 24-bit address */
+    match mode {
+        Direct | DirectX | DirectY | StackRelative => return true,
+        _ => return false,
+    }
+}
+
+fn next_address(mode: NativeMode, address: u32) -> u32 {
+    if in_bank_zero(mode) {
+        return (address as u16).wrapping_add(1) as u32;
+    }
+    return address.wrapping_add(1) & BANK_MASK;
+}
+
+fn direct_address(processor: &mut Processor, offset: u8, index: u16) -> u16 {
+    /*
+        In emulation mode with a page aligned direct page, indexing wraps inside the page
+        exactly like the 6502 zero page. Otherwise the sum wraps inside bank 0.
+    */
+    let direct_page: u16 = processor.native.direct_page;
+    if direct_page & 0xFF != 0 {
+        processor.decrement_cycles(1);
+    }
+
+    if processor.native.emulation && direct_page & 0xFF == 0 {
+        return direct_page | (offset as u16).wrapping_add(index) & 0xFF;
+    }
+    return direct_page.wrapping_add(offset as u16).wrapping_add(index);
+}
+
+fn read_direct_pointer<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    pointer: u16,
+    long: bool,
+) -> u32 {
+    let wrap_in_page: bool = processor.native.emulation && processor.native.direct_page & 0xFF == 0;
+    let following = |address: u16| -> u16 {
+        match wrap_in_page {
+            true => (address & 0xFF00) | (address.wrapping_add(1) & 0xFF),
+            false => address.wrapping_add(1),
+        }
+    };
+
+    let low_byte: u8 = processor.read_long(memory, pointer as u32);
+    let high_address: u16 = following(pointer);
+    let high_byte: u8 = processor.read_long(memory, high_address as u32);
+
+    if long == true {
+        let bank: u8 = processor.read_long(memory, following(high_address) as u32);
+        return low_byte as u32 | ((high_byte as u32) << 8) | ((bank as u32) << 16);
+    }
+
+    let bank: u32 = (processor.native.data_bank as u32) << 16;
+    return bank | low_byte as u32 | ((high_byte as u32) << 8);
+}
+
+fn index_long(processor: &mut Processor, base: u32, index: u16, access: MemoryAccess) -> u32 {
+    let address: u32 = base.wrapping_add(index as u32) & BANK_MASK;
+
+    // Wide index registers, page crossings and anything but a plain read pay for the carry into the high byte
+    if processor.index_is_wide() || access != MemoryAccess::Read || (address >> 8) != (base >> 8) {
+        processor.decrement_cycles(1);
+    }
+
+    return address;
+}
+
+fn fetch_long<B: Bus>(processor: &mut Processor, memory: &mut B) -> u32 {
+    let low_byte: u8 = processor.fetch_byte(memory);
+    let high_byte: u8 = processor.fetch_byte(memory);
+    let bank: u8 = processor.fetch_byte(memory);
+    return low_byte as u32 | ((high_byte as u32) << 8) | ((bank as u32) << 16);
+}
+
+pub fn native_address<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    mode: NativeMode,
+    access: MemoryAccess,
+    wide: bool,
+) -> u32 {
+    let data_bank: u32 = (processor.native.data_bank as u32) << 16;
+    let index_x: u16 = processor.wide_register(Registers::RegisterX);
+    let index_y: u16 = processor.wide_register(Registers::RegisterY);
+
+    match mode {
+        Immediate => {
+            let address: u32 =
+                ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32;
+            processor.increment_pc();
+            if wide == true {
+                processor.increment_pc();
+            }
+            return address;
+        }
+        Direct => {
+            let offset: u8 = processor.fetch_byte(memory);
+            return direct_address(processor, offset, 0) as u32;
+        }
+        DirectX | DirectY => {
+            let offset: u8 = processor.fetch_byte(memory);
+            let index: u16 = if mode == DirectX { index_x } else { index_y };
+            processor.decrement_cycles(1);
+            return direct_address(processor, offset, index) as u32;
+        }
+        DirectIndirect => {
+            let offset: u8 = processor.fetch_byte(memory);
+            let pointer: u16 = direct_address(processor, offset, 0);
+            return read_direct_pointer(processor, memory, pointer, false);
+        }
+        DirectIndirectX => {
+            let offset: u8 = processor.fetch_byte(memory);
+            processor.decrement_cycles(1);
+            let pointer: u16 = direct_address(processor, offset, index_x);
+            return read_direct_pointer(processor, memory, pointer, false);
+        }
+        DirectIndirectY => {
+            let offset: u8 = processor.fetch_byte(memory);
+            let pointer: u16 = direct_address(processor, offset, 0);
+            let base: u32 = read_direct_pointer(processor, memory, pointer, false);
+            return index_long(processor, base, index_y, access);
+        }
+        DirectIndirectLong => {
+            let offset: u8 = processor.fetch_byte(memory);
+            let pointer: u16 = direct_address(processor, offset, 0);
+            return read_direct_pointer(processor, memory, pointer, true);
+        }
+        DirectIndirectLongY => {
+            let offset: u8 = processor.fetch_byte(memory);
+            let pointer: u16 = direct_address(processor, offset, 0);
+            let base: u32 = read_direct_pointer(processor, memory, pointer, true);
+            return base.wrapping_add(index_y as u32) & BANK_MASK;
+        }
+        Absolute => {
+            let low_byte: u8 = processor.fetch_byte(memory);
+            let high_byte: u8 = processor.fetch_byte(memory);
+            return data_bank | low_byte as u32 | ((high_byte as u32) << 8);
+        }
+        AbsoluteX | AbsoluteY => {
+            let low_byte: u8 = processor.fetch_byte(memory);
+            let high_byte: u8 = processor.fetch_byte(memory);
+            let base: u32 = data_bank | low_byte as u32 | ((high_byte as u32) << 8);
+            let index: u16 = if mode == AbsoluteX { index_x } else { index_y };
+            return index_long(processor, base, index, access);
+        }
+        AbsoluteLong => return fetch_long(processor, memory),
+        AbsoluteLongX => {
+            let base: u32 = fetch_long(processor, memory);
+            return base.wrapping_add(index_x as u32) & BANK_MASK;
+        }
+        StackRelative => {
+            let offset: u8 = processor.fetch_byte(memory);
+            processor.decrement_cycles(1);
+            return processor.wide_stack_pointer().wrapping_add(offset as u16) as u32;
+        }
+        StackRelativeIndirectY => {
+            let offset: u8 = processor.fetch_byte(memory);
+            processor.decrement_cycles(1);
+            let pointer: u16 = processor.wide_stack_pointer().wrapping_add(offset as u16);
+            let low_byte: u8 = processor.read_long(memory, pointer as u32);
+            let high_byte: u8 = processor.read_long(memory, pointer.wrapping_add(1) as u32);
+            let base: u32 = data_bank | low_byte as u32 | ((high_byte as u32) << 8);
+            processor.decrement_cycles(1);
+            return base.wrapping_add(index_y as u32) & BANK_MASK;
+        }
+        Implied | Accumulator => return processor.program_counter as u32,
+    }
+}
+
+pub fn read_value<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    mode: NativeMode,
+    address: u32,
+    wide: bool,
+) -> u16 {
+    let low_byte: u8 = processor.read_long(memory, address);
+    if wide == false {
+        return low_byte as u16;
+    }
+
+    let high_byte: u8 = processor.read_long(memory, next_address(mode, address));
+    return low_byte as u16 | ((high_byte as u16) << 8);
+}
+
+pub fn write_value<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    mode: NativeMode,
+    address: u32,
+    value: u16,
+    wide: bool,
+) -> () {
+    processor.write_long(memory, value as u8, address);
+    if wide == true {
+        processor.write_long(memory, (value >> 8) as u8, next_address(mode, address));
+    }
+}
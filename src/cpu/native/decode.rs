@@ -0,0 +1,1048 @@
+use super::addressing::NativeMode::{self, *};
+use super::addressing::*;
+use super::registers::*;
+use crate::cpu;
+use crate::cpu::instructions::arithmetic::add::add;
+use crate::cpu::instructions::arithmetic::compare::compare;
+use crate::cpu::instructions::arithmetic::subtract::subtract;
+use crate::cpu::instructions::branches::Branches;
+use crate::cpu::instructions::system::System;
+use crate::cpu::interrupts::{NATIVE_NMI_VECTOR, NMI_VECTOR};
+use crate::mem::*;
+
+use cpu::functions::byte::*;
+use cpu::opcodes::MemoryAccess::{self, *};
+use cpu::opcodes::ProcessorStatus::{self, *};
+use cpu::opcodes::Registers::{self, RegisterX, RegisterY};
+use cpu::opcodes::*;
+use cpu::processor::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Lda,
+    Ldx,
+    Ldy,
+    Sta,
+    Stx,
+    Sty,
+    Stz,
+    Ora,
+    And,
+    Eor,
+    Adc,
+    Sbc,
+    Cmp,
+    Cpx,
+    Cpy,
+    Bit,
+    Tsb,
+    Trb,
+    Asl,
+    Lsr,
+    Rol,
+    Ror,
+    Inc,
+    Dec,
+    Inx,
+    Iny,
+    Dex,
+    Dey,
+    Tax,
+    Tay,
+    Txa,
+    Tya,
+    Tsx,
+    Txs,
+    Txy,
+    Tyx,
+    Tcd,
+    Tdc,
+    Tcs,
+    Tsc,
+    Xba,
+    Xce,
+    Pha,
+    Phx,
+    Phy,
+    Php,
+    Phb,
+    Phd,
+    Phk,
+    Pla,
+    Plx,
+    Ply,
+    Plp,
+    Plb,
+    Pld,
+    Pea,
+    Pei,
+    Per,
+    Clc,
+    Sec,
+    Cli,
+    Sei,
+    Cld,
+    Sed,
+    Clv,
+    Rep,
+    Sep,
+    Bpl,
+    Bmi,
+    Bvc,
+    Bvs,
+    Bcc,
+    Bcs,
+    Bne,
+    Beq,
+    Bra,
+    Brl,
+    Jmp,
+    JmpIndirect,
+    JmpIndexedIndirect,
+    Jml,
+    JmlIndirect,
+    Jsr,
+    JsrIndexedIndirect,
+    Jsl,
+    Rts,
+    Rtl,
+    Rti,
+    Brk,
+    Cop,
+    Wai,
+    Stp,
+    Wdm,
+    Nop,
+    Mvn,
+    Mvp,
+}
+
+use Operation::*;
+
+// Looked up by opcode on every native instruction
+const DECODED: [(Operation, NativeMode); 256] = decode_all();
+
+const fn decode_all() -> [(Operation, NativeMode); 256] {
+    let mut table: [(Operation, NativeMode); 256] = [(Nop, Implied); 256];
+    let mut opcode: usize = 0;
+    while opcode < 256 {
+        table[opcode] = decode(opcode as u8);
+        opcode += 1;
+    }
+    return table;
+}
+
+const fn decode(opcode: u8) -> (Operation, NativeMode) {
+    /* Every one of the 256 opcodes is defined on the 65C816, so unlike the undocumented decoder this never fails */
+    match opcode {
+        LDA_IMMEDIATE => (Lda, Immediate),
+        LDA_ZERO_PAGE => (Lda, Direct),
+        LDA_ZERO_PAGE_X => (Lda, DirectX),
+        LDA_ZERO_PAGE_INDIRECT => (Lda, DirectIndirect),
+        LDA_INDIRECT_X => (Lda, DirectIndirectX),
+        LDA_INDIRECT_Y => (Lda, DirectIndirectY),
+        LDA_DIRECT_INDIRECT_LONG => (Lda, DirectIndirectLong),
+        LDA_DIRECT_INDIRECT_LONG_Y => (Lda, DirectIndirectLongY),
+        LDA_ABSOLUTE => (Lda, Absolute),
+        LDA_ABSOLUTE_X => (Lda, AbsoluteX),
+        LDA_ABSOLUTE_Y => (Lda, AbsoluteY),
+        LDA_ABSOLUTE_LONG => (Lda, AbsoluteLong),
+        LDA_ABSOLUTE_LONG_X => (Lda, AbsoluteLongX),
+        LDA_STACK_RELATIVE => (Lda, StackRelative),
+        LDA_STACK_RELATIVE_INDIRECT_Y => (Lda, StackRelativeIndirectY),
+
+        LDX_IMMEDIATE => (Ldx, Immediate),
+        LDX_ZERO_PAGE => (Ldx, Direct),
+        LDX_ZERO_PAGE_Y => (Ldx, DirectY),
+        LDX_ABSOLUTE => (Ldx, Absolute),
+        LDX_ABSOLUTE_Y => (Ldx, AbsoluteY),
+
+        LDY_IMMEDIATE => (Ldy, Immediate),
+        LDY_ZERO_PAGE => (Ldy, Direct),
+        LDY_ZERO_PAGE_X => (Ldy, DirectX),
+        LDY_ABSOLUTE => (Ldy, Absolute),
+        LDY_ABSOLUTE_X => (Ldy, AbsoluteX),
+
+        STA_ZERO_PAGE => (Sta, Direct),
+        STA_ZERO_PAGE_X => (Sta, DirectX),
+        STA_ZERO_PAGE_INDIRECT => (Sta, DirectIndirect),
+        STA_INDIRECT_X => (Sta, DirectIndirectX),
+        STA_INDIRECT_Y => (Sta, DirectIndirectY),
+        STA_DIRECT_INDIRECT_LONG => (Sta, DirectIndirectLong),
+        STA_DIRECT_INDIRECT_LONG_Y => (Sta, DirectIndirectLongY),
+        STA_ABSOLUTE => (Sta, Absolute),
+        STA_ABSOLUTE_X => (Sta, AbsoluteX),
+        STA_ABSOLUTE_Y => (Sta, AbsoluteY),
+        STA_ABSOLUTE_LONG => (Sta, AbsoluteLong),
+        STA_ABSOLUTE_LONG_X => (Sta, AbsoluteLongX),
+        STA_STACK_RELATIVE => (Sta, StackRelative),
+        STA_STACK_RELATIVE_INDIRECT_Y => (Sta, StackRelativeIndirectY),
+
+        STX_ZERO_PAGE => (Stx, Direct),
+        STX_ZERO_PAGE_Y => (Stx, DirectY),
+        STX_ABSOLUTE => (Stx, Absolute),
+
+        STY_ZERO_PAGE => (Sty, Direct),
+        STY_ZERO_PAGE_X => (Sty, DirectX),
+        STY_ABSOLUTE => (Sty, Absolute),
+
+        STZ_ZERO_PAGE => (Stz, Direct),
+        STZ_ZERO_PAGE_X => (Stz, DirectX),
+        STZ_ABSOLUTE => (Stz, Absolute),
+        STZ_ABSOLUTE_X => (Stz, AbsoluteX),
+
+        OR_IMMEDIATE => (Ora, Immediate),
+        OR_ZERO_PAGE => (Ora, Direct),
+        OR_ZERO_PAGE_X => (Ora, DirectX),
+        OR_ZERO_PAGE_INDIRECT => (Ora, DirectIndirect),
+        OR_INDIRECT_X => (Ora, DirectIndirectX),
+        OR_INDIRECT_Y => (Ora, DirectIndirectY),
+        OR_DIRECT_INDIRECT_LONG => (Ora, DirectIndirectLong),
+        OR_DIRECT_INDIRECT_LONG_Y => (Ora, DirectIndirectLongY),
+        OR_ABSOLUTE => (Ora, Absolute),
+        OR_ABSOLUTE_X => (Ora, AbsoluteX),
+        OR_ABSOLUTE_Y => (Ora, AbsoluteY),
+        OR_ABSOLUTE_LONG => (Ora, AbsoluteLong),
+        OR_ABSOLUTE_LONG_X => (Ora, AbsoluteLongX),
+        OR_STACK_RELATIVE => (Ora, StackRelative),
+        OR_STACK_RELATIVE_INDIRECT_Y => (Ora, StackRelativeIndirectY),
+
+        AND_IMMEDIATE => (And, Immediate),
+        AND_ZERO_PAGE => (And, Direct),
+        AND_ZERO_PAGE_X => (And, DirectX),
+        AND_ZERO_PAGE_INDIRECT => (And, DirectIndirect),
+        AND_INDIRECT_X => (And, DirectIndirectX),
+        AND_INDIRECT_Y => (And, DirectIndirectY),
+        AND_DIRECT_INDIRECT_LONG => (And, DirectIndirectLong),
+        AND_DIRECT_INDIRECT_LONG_Y => (And, DirectIndirectLongY),
+        AND_ABSOLUTE => (And, Absolute),
+        AND_ABSOLUTE_X => (And, AbsoluteX),
+        AND_ABSOLUTE_Y => (And, AbsoluteY),
+        AND_ABSOLUTE_LONG => (And, AbsoluteLong),
+        AND_ABSOLUTE_LONG_X => (And, AbsoluteLongX),
+        AND_STACK_RELATIVE => (And, StackRelative),
+        AND_STACK_RELATIVE_INDIRECT_Y => (And, StackRelativeIndirectY),
+
+        EOR_IMMEDIATE => (Eor, Immediate),
+        EOR_ZERO_PAGE => (Eor, Direct),
+        EOR_ZERO_PAGE_X => (Eor, DirectX),
+        EOR_ZERO_PAGE_INDIRECT => (Eor, DirectIndirect),
+        EOR_INDIRECT_X => (Eor, DirectIndirectX),
+        EOR_INDIRECT_Y => (Eor, DirectIndirectY),
+        EOR_DIRECT_INDIRECT_LONG => (Eor, DirectIndirectLong),
+        EOR_DIRECT_INDIRECT_LONG_Y => (Eor, DirectIndirectLongY),
+        EOR_ABSOLUTE => (Eor, Absolute),
+        EOR_ABSOLUTE_X => (Eor, AbsoluteX),
+        EOR_ABSOLUTE_Y => (Eor, AbsoluteY),
+        EOR_ABSOLUTE_LONG => (Eor, AbsoluteLong),
+        EOR_ABSOLUTE_LONG_X => (Eor, AbsoluteLongX),
+        EOR_STACK_RELATIVE => (Eor, StackRelative),
+        EOR_STACK_RELATIVE_INDIRECT_Y => (Eor, StackRelativeIndirectY),
+
+        ADC_IMMEDIATE => (Adc, Immediate),
+        ADC_ZERO_PAGE => (Adc, Direct),
+        ADC_ZERO_PAGE_X => (Adc, DirectX),
+        ADC_ZERO_PAGE_INDIRECT => (Adc, DirectIndirect),
+        ADC_INDIRECT_X => (Adc, DirectIndirectX),
+        ADC_INDIRECT_Y => (Adc, DirectIndirectY),
+        ADC_DIRECT_INDIRECT_LONG => (Adc, DirectIndirectLong),
+        ADC_DIRECT_INDIRECT_LONG_Y => (Adc, DirectIndirectLongY),
+        ADC_ABSOLUTE => (Adc, Absolute),
+        ADC_ABSOLUTE_X => (Adc, AbsoluteX),
+        ADC_ABSOLUTE_Y => (Adc, AbsoluteY),
+        ADC_ABSOLUTE_LONG => (Adc, AbsoluteLong),
+        ADC_ABSOLUTE_LONG_X => (Adc, AbsoluteLongX),
+        ADC_STACK_RELATIVE => (Adc, StackRelative),
+        ADC_STACK_RELATIVE_INDIRECT_Y => (Adc, StackRelativeIndirectY),
+
+        SBC_IMMEDIATE => (Sbc, Immediate),
+        SBC_ZERO_PAGE => (Sbc, Direct),
+        SBC_ZERO_PAGE_X => (Sbc, DirectX),
+        SBC_ZERO_PAGE_INDIRECT => (Sbc, DirectIndirect),
+        SBC_INDIRECT_X => (Sbc, DirectIndirectX),
+        SBC_INDIRECT_Y => (Sbc, DirectIndirectY),
+        SBC_DIRECT_INDIRECT_LONG => (Sbc, DirectIndirectLong),
+        SBC_DIRECT_INDIRECT_LONG_Y => (Sbc, DirectIndirectLongY),
+        SBC_ABSOLUTE => (Sbc, Absolute),
+        SBC_ABSOLUTE_X => (Sbc, AbsoluteX),
+        SBC_ABSOLUTE_Y => (Sbc, AbsoluteY),
+        SBC_ABSOLUTE_LONG => (Sbc, AbsoluteLong),
+        SBC_ABSOLUTE_LONG_X => (Sbc, AbsoluteLongX),
+        SBC_STACK_RELATIVE => (Sbc, StackRelative),
+        SBC_STACK_RELATIVE_INDIRECT_Y => (Sbc, StackRelativeIndirectY),
+
+        CMP_IMMEDIATE => (Cmp, Immediate),
+        CMP_ZERO_PAGE => (Cmp, Direct),
+        CMP_ZERO_PAGE_X => (Cmp, DirectX),
+        CMP_ZERO_PAGE_INDIRECT => (Cmp, DirectIndirect),
+        CMP_INDIRECT_X => (Cmp, DirectIndirectX),
+        CMP_INDIRECT_Y => (Cmp, DirectIndirectY),
+        CMP_DIRECT_INDIRECT_LONG => (Cmp, DirectIndirectLong),
+        CMP_DIRECT_INDIRECT_LONG_Y => (Cmp, DirectIndirectLongY),
+        CMP_ABSOLUTE => (Cmp, Absolute),
+        CMP_ABSOLUTE_X => (Cmp, AbsoluteX),
+        CMP_ABSOLUTE_Y => (Cmp, AbsoluteY),
+        CMP_ABSOLUTE_LONG => (Cmp, AbsoluteLong),
+        CMP_ABSOLUTE_LONG_X => (Cmp, AbsoluteLongX),
+        CMP_STACK_RELATIVE => (Cmp, StackRelative),
+        CMP_STACK_RELATIVE_INDIRECT_Y => (Cmp, StackRelativeIndirectY),
+
+        CPX_IMMEDIATE => (Cpx, Immediate),
+        CPX_ZERO_PAGE => (Cpx, Direct),
+        CPX_ABSOLUTE => (Cpx, Absolute),
+
+        CPY_IMMEDIATE => (Cpy, Immediate),
+        CPY_ZERO_PAGE => (Cpy, Direct),
+        CPY_ABSOLUTE => (Cpy, Absolute),
+
+        BIT_IMMEDIATE => (Bit, Immediate),
+        BIT_ZERO_PAGE => (Bit, Direct),
+        BIT_ZERO_PAGE_X => (Bit, DirectX),
+        BIT_ABSOLUTE => (Bit, Absolute),
+        BIT_ABSOLUTE_X => (Bit, AbsoluteX),
+
+        TSB_ZERO_PAGE => (Tsb, Direct),
+        TSB_ABSOLUTE => (Tsb, Absolute),
+
+        TRB_ZERO_PAGE => (Trb, Direct),
+        TRB_ABSOLUTE => (Trb, Absolute),
+
+        ASL_ACCUMULATOR => (Asl, Accumulator),
+        ASL_ZERO_PAGE => (Asl, Direct),
+        ASL_ZERO_PAGE_X => (Asl, DirectX),
+        ASL_ABSOLUTE => (Asl, Absolute),
+        ASL_ABSOLUTE_X => (Asl, AbsoluteX),
+
+        LSR_ACCUMULATOR => (Lsr, Accumulator),
+        LSR_ZERO_PAGE => (Lsr, Direct),
+        LSR_ZERO_PAGE_X => (Lsr, DirectX),
+        LSR_ABSOLUTE => (Lsr, Absolute),
+        LSR_ABSOLUTE_X => (Lsr, AbsoluteX),
+
+        ROL_ACCUMULATOR => (Rol, Accumulator),
+        ROL_ZERO_PAGE => (Rol, Direct),
+        ROL_ZERO_PAGE_X => (Rol, DirectX),
+        ROL_ABSOLUTE => (Rol, Absolute),
+        ROL_ABSOLUTE_X => (Rol, AbsoluteX),
+
+        ROR_ACCUMULATOR => (Ror, Accumulator),
+        ROR_ZERO_PAGE => (Ror, Direct),
+        ROR_ZERO_PAGE_X => (Ror, DirectX),
+        ROR_ABSOLUTE => (Ror, Absolute),
+        ROR_ABSOLUTE_X => (Ror, AbsoluteX),
+
+        INC_ACCUMULATOR => (Inc, Accumulator),
+        INC_ZERO_PAGE => (Inc, Direct),
+        INC_ZERO_PAGE_X => (Inc, DirectX),
+        INC_ABSOLUTE => (Inc, Absolute),
+        INC_ABSOLUTE_X => (Inc, AbsoluteX),
+
+        DEC_ACCUMULATOR => (Dec, Accumulator),
+        DEC_ZERO_PAGE => (Dec, Direct),
+        DEC_ZERO_PAGE_X => (Dec, DirectX),
+        DEC_ABSOLUTE => (Dec, Absolute),
+        DEC_ABSOLUTE_X => (Dec, AbsoluteX),
+
+        INX => (Inx, Implied),
+        INY => (Iny, Implied),
+        DEX => (Dex, Implied),
+        DEY => (Dey, Implied),
+        TAX => (Tax, Implied),
+        TAY => (Tay, Implied),
+        TXA => (Txa, Implied),
+        TYA => (Tya, Implied),
+        TSX => (Tsx, Implied),
+        TXS => (Txs, Implied),
+        TXY => (Txy, Implied),
+        TYX => (Tyx, Implied),
+        TCD => (Tcd, Implied),
+        TDC => (Tdc, Implied),
+        TCS => (Tcs, Implied),
+        TSC => (Tsc, Implied),
+        XBA => (Xba, Implied),
+        XCE => (Xce, Implied),
+        PHA => (Pha, Implied),
+        PHX => (Phx, Implied),
+        PHY => (Phy, Implied),
+        PHP => (Php, Implied),
+        PHB => (Phb, Implied),
+        PHD => (Phd, Implied),
+        PHK => (Phk, Implied),
+        PLA => (Pla, Implied),
+        PLX => (Plx, Implied),
+        PLY => (Ply, Implied),
+        PLP => (Plp, Implied),
+        PLB => (Plb, Implied),
+        PLD => (Pld, Implied),
+        PEA => (Pea, Implied),
+        PEI => (Pei, Implied),
+        PER => (Per, Implied),
+        CLC => (Clc, Implied),
+        SEC => (Sec, Implied),
+        CLI => (Cli, Implied),
+        SEI => (Sei, Implied),
+        CLD => (Cld, Implied),
+        SED => (Sed, Implied),
+        CLV => (Clv, Implied),
+        REP => (Rep, Implied),
+        SEP => (Sep, Implied),
+        BPL => (Bpl, Implied),
+        BMI => (Bmi, Implied),
+        BVC => (Bvc, Implied),
+        BVS => (Bvs, Implied),
+        BCC => (Bcc, Implied),
+        BCS => (Bcs, Implied),
+        BNE => (Bne, Implied),
+        BEQ => (Beq, Implied),
+        BRA => (Bra, Implied),
+        BRL => (Brl, Implied),
+        JMP_ABSOLUTE => (Jmp, Implied),
+        JMP_INDIRECT => (JmpIndirect, Implied),
+        JMP_ABSOLUTE_INDEXED_INDIRECT => (JmpIndexedIndirect, Implied),
+        JML_ABSOLUTE_LONG => (Jml, Implied),
+        JML_ABSOLUTE_INDIRECT_LONG => (JmlIndirect, Implied),
+        JSR => (Jsr, Implied),
+        JSR_ABSOLUTE_INDEXED_INDIRECT => (JsrIndexedIndirect, Implied),
+        JSL => (Jsl, Implied),
+        RTS => (Rts, Implied),
+        RTL => (Rtl, Implied),
+        RTI => (Rti, Implied),
+        BRK => (Brk, Implied),
+        COP => (Cop, Implied),
+        WAI => (Wai, Implied),
+        STP => (Stp, Implied),
+        WDM => (Wdm, Implied),
+        NOP => (Nop, Implied),
+        MVN => (Mvn, Implied),
+        MVP => (Mvp, Implied),
+    }
+}
+
+fn access_for(operation: Operation) -> MemoryAccess {
+    match operation {
+        Sta | Stx | Sty | Stz => Write,
+        Asl | Lsr | Rol | Ror | Inc | Dec | Tsb | Trb => Modify,
+        _ => Read,
+    }
+}
+
+fn register_for(operation: Operation) -> Registers {
+    match operation {
+        Ldx | Stx | Cpx => RegisterX,
+        Ldy | Sty | Cpy => RegisterY,
+        _ => Registers::Accumulator,
+    }
+}
+
+fn is_wide(processor: &Processor, operation: Operation) -> bool {
+    match register_for(operation) {
+        RegisterX | RegisterY => return processor.index_is_wide(),
+        Registers::Accumulator => return processor.accumulator_is_wide(),
+    }
+}
+
+fn add_wide(processor: &mut Processor, operand: u16) -> () {
+    /*
+        A 16-bit ADC is two chained 8-bit additions through the shared adder, which keeps the
+        decimal adjust per byte exactly like the hardware. N and V come from the high byte.
+    */
+    add(processor, operand & 0xFF);
+    if processor.accumulator_is_wide() == false {
+        return;
+    }
+
+    let low_result: u8 = processor.accumulator;
+    processor.accumulator = processor.native.accumulator_high;
+    add(processor, operand >> 8);
+    processor.native.accumulator_high = processor.accumulator;
+    processor.accumulator = low_result;
+    processor.set_status(
+        ZeroFlag,
+        processor.wide_register(Registers::Accumulator) == 0,
+    );
+}
+
+fn subtract_wide(processor: &mut Processor, operand: u16) -> () {
+    subtract(processor, operand as u8);
+    if processor.accumulator_is_wide() == false {
+        return;
+    }
+
+    let low_result: u8 = processor.accumulator;
+    processor.accumulator = processor.native.accumulator_high;
+    subtract(processor, (operand >> 8) as u8);
+    processor.native.accumulator_high = processor.accumulator;
+    processor.accumulator = low_result;
+    processor.set_status(
+        ZeroFlag,
+        processor.wide_register(Registers::Accumulator) == 0,
+    );
+}
+
+fn compare_wide(processor: &mut Processor, register: Registers, operand: u16, wide: bool) -> () {
+    if wide == false {
+        return compare(processor, operand as u8, register);
+    }
+
+    let value: u16 = processor.wide_register(register);
+    processor.set_status(CarryFlag, value >= operand);
+    processor.set_result_flags(value.wrapping_sub(operand), true);
+}
+
+fn modify(processor: &mut Processor, operation: Operation, value: u16, wide: bool) -> u16 {
+    let top_bit: u16 = if wide { 0x8000 } else { 0x80 };
+    let mask: u16 = if wide { 0xFFFF } else { 0xFF };
+    let carry: bool = processor.fetch_status(CarryFlag);
+
+    let result: u16 = match operation {
+        Asl => {
+            processor.set_status(CarryFlag, value & top_bit != 0);
+            (value << 1) & mask
+        }
+        Lsr => {
+            processor.set_status(CarryFlag, value & 0x01 != 0);
+            value >> 1
+        }
+        Rol => {
+            processor.set_status(CarryFlag, value & top_bit != 0);
+            ((value << 1) | carry as u16) & mask
+        }
+        Ror => {
+            processor.set_status(CarryFlag, value & 0x01 != 0);
+            (value >> 1) | if carry { top_bit } else { 0 }
+        }
+        Inc => value.wrapping_add(1) & mask,
+        Dec => value.wrapping_sub(1) & mask,
+        _ => value,
+    };
+
+    processor.set_result_flags(result, wide);
+    return result;
+}
+
+fn read_operation(
+    processor: &mut Processor,
+    operation: Operation,
+    mode: NativeMode,
+    value: u16,
+    wide: bool,
+) -> () {
+    let accumulator: u16 = processor.wide_register(Registers::Accumulator);
+
+    match operation {
+        Lda | Ldx | Ldy => {
+            processor.set_wide_register(register_for(operation), value);
+            processor.set_result_flags(value, wide);
+        }
+        Ora | And | Eor => {
+            let result: u16 = match operation {
+                Ora => accumulator | value,
+                And => accumulator & value,
+                _ => accumulator ^ value,
+            };
+            processor.set_wide_register(Registers::Accumulator, result);
+            processor.set_result_flags(result, wide);
+        }
+        Adc => add_wide(processor, value),
+        Sbc => subtract_wide(processor, value),
+        Cmp | Cpx | Cpy => compare_wide(processor, register_for(operation), value, wide),
+        Bit => {
+            let (top_bit, next_bit): (u16, u16) =
+                if wide { (0x8000, 0x4000) } else { (0x80, 0x40) };
+            let mask: u16 = if wide { 0xFFFF } else { 0xFF };
+
+            // The immediate form has no memory operand to take N and V from
+            if mode != Immediate {
+                processor.set_status(NegativeFlag, value & top_bit != 0);
+                processor.set_status(OverflowFlag, value & next_bit != 0);
+            }
+            processor.set_status(ZeroFlag, accumulator & value & mask == 0);
+        }
+        _ => {}
+    }
+}
+
+fn push<B: Bus>(processor: &mut Processor, memory: &mut B, value: u8) -> () {
+    let stack_pointer: u16 = processor.wide_stack_pointer();
+    processor.write_long(memory, value, stack_pointer as u32);
+    processor.set_wide_stack_pointer(stack_pointer.wrapping_sub(1));
+}
+
+fn push_value<B: Bus>(processor: &mut Processor, memory: &mut B, value: u16, wide: bool) -> () {
+    if wide == true {
+        push(processor, memory, (value >> 8) as u8);
+    }
+    push(processor, memory, value as u8);
+}
+
+fn pull<B: Bus>(processor: &mut Processor, memory: &mut B) -> u8 {
+    let stack_pointer: u16 = processor.wide_stack_pointer().wrapping_add(1);
+    processor.set_wide_stack_pointer(stack_pointer);
+    return processor.read_long(memory, processor.wide_stack_pointer() as u32);
+}
+
+fn pull_value<B: Bus>(processor: &mut Processor, memory: &mut B, wide: bool) -> u16 {
+    let low_byte: u8 = pull(processor, memory);
+    if wide == false {
+        return low_byte as u16;
+    }
+
+    let high_byte: u8 = pull(processor, memory);
+    return low_byte as u16 | ((high_byte as u16) << 8);
+}
+
+fn fetch_word_native<B: Bus>(processor: &mut Processor, memory: &mut B) -> u16 {
+    let low_byte: u8 = processor.fetch_byte(memory);
+    let high_byte: u8 = processor.fetch_byte(memory);
+    return low_byte as u16 | ((high_byte as u16) << 8);
+}
+
+fn read_word_long<B: Bus>(processor: &mut Processor, memory: &mut B, address: u32) -> u16 {
+    // Pointers used by jumps stay inside their bank
+    let low_byte: u8 = processor.read_long(memory, address);
+    let high_address: u32 = (address & 0xFF_0000) | ((address as u16).wrapping_add(1) as u32);
+    let high_byte: u8 = processor.read_long(memory, high_address);
+    return low_byte as u16 | ((high_byte as u16) << 8);
+}
+
+fn transfer(processor: &mut Processor, from: u16, to: Registers, wide: bool) -> () {
+    processor.set_wide_register(to, from);
+    processor.set_result_flags(from, wide);
+    processor.decrement_cycles(1);
+}
+
+fn interrupt<B: Bus>(processor: &mut Processor, memory: &mut B, operation: Operation) -> () {
+    /* BRK and COP skip a signature byte, only emulation mode has a B flag to set in the pushed status */
+    processor.fetch_byte(memory);
+
+    let vector: u16;
+    let mut status: u8 = processor.status;
+    if processor.native.emulation == true {
+        status = set_bit(status, 5, true);
+        status = set_bit(status, 4, operation == Brk);
+        vector = if operation == Brk { 0xFFFE } else { 0xFFF4 };
+    } else {
+        vector = if operation == Brk { 0xFFE6 } else { 0xFFE4 };
+    }
+
+    enter_interrupt(processor, memory, status, vector);
+}
+
+pub fn enter_interrupt<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    status: u8,
+    vector: u16,
+) -> () {
+    /*
+        Shared by BRK, COP and the hardware interrupts. Native mode also pushes the program
+        bank, and every handler starts in bank 0 with decimal mode off.
+    */
+    if processor.native.emulation == false {
+        push(processor, memory, processor.native.program_bank);
+    }
+
+    push_value(processor, memory, processor.program_counter, true);
+    let nmi_vector: u16 = if processor.native.emulation {
+        NMI_VECTOR
+    } else {
+        NATIVE_NMI_VECTOR
+    };
+    let vector: u16 = processor.hijack_vector(vector, nmi_vector);
+    push(processor, memory, status);
+
+    processor.set_status(InterruptDisable, true);
+    processor.set_status(DecimalMode, false);
+    processor.native.program_bank = 0x00;
+    processor.program_counter = read_word_long(processor, memory, vector as u32);
+}
+
+fn block_move<B: Bus>(processor: &mut Processor, memory: &mut B, operation: Operation) -> () {
+    /*
+        MVN and MVP move a single byte per execution and rewind the program counter until
+        the count in C runs out, so an interrupt can be taken between any two bytes.
+    */
+    let destination_bank: u8 = processor.fetch_byte(memory);
+    let source_bank: u8 = processor.fetch_byte(memory);
+    processor.native.data_bank = destination_bank;
+
+    let source: u16 = processor.wide_register(RegisterX);
+    let destination: u16 = processor.wide_register(RegisterY);
+    let value: u8 = processor.read_long(memory, ((source_bank as u32) << 16) | source as u32);
+    processor.write_long(
+        memory,
+        value,
+        ((destination_bank as u32) << 16) | destination as u32,
+    );
+    processor.decrement_cycles(2);
+
+    match operation {
+        Mvn => {
+            processor.set_wide_register(RegisterX, source.wrapping_add(1));
+            processor.set_wide_register(RegisterY, destination.wrapping_add(1));
+        }
+        _ => {
+            processor.set_wide_register(RegisterX, source.wrapping_sub(1));
+            processor.set_wide_register(RegisterY, destination.wrapping_sub(1));
+        }
+    }
+
+    // The count is always 16 bits, whatever the accumulator width
+    let count: u16 = processor
+        .wide_register(Registers::Accumulator)
+        .wrapping_sub(1);
+    processor.accumulator = count as u8;
+    processor.native.accumulator_high = (count >> 8) as u8;
+
+    if count != 0xFFFF {
+        processor.program_counter = processor.program_counter.wrapping_sub(3);
+    }
+}
+
+fn change_status<B: Bus>(processor: &mut Processor, memory: &mut B, operation: Operation) -> () {
+    let mut mask: u8 = processor.fetch_byte(memory);
+    if processor.native.emulation == true {
+        mask &= !(ACCUMULATOR_WIDTH | INDEX_WIDTH);
+    }
+
+    let status: u8 = match operation {
+        Rep => processor.status & !mask,
+        _ => processor.status | mask,
+    };
+    processor.set_native_status(status);
+    processor.decrement_cycles(1);
+}
+
+fn set_flag(processor: &mut Processor, flag: ProcessorStatus, value: bool) -> () {
+    processor.set_status(flag, value);
+    processor.decrement_cycles(1);
+}
+
+fn implied<B: Bus>(processor: &mut Processor, memory: &mut B, operation: Operation) -> () {
+    let index_wide: bool = processor.index_is_wide();
+    let accumulator_wide: bool = processor.accumulator_is_wide();
+    let accumulator: u16 = processor.wide_register(Registers::Accumulator);
+    let register_x: u16 = processor.wide_register(RegisterX);
+    let register_y: u16 = processor.wide_register(RegisterY);
+
+    match operation {
+        Inx => transfer(processor, register_x.wrapping_add(1), RegisterX, index_wide),
+        Iny => transfer(processor, register_y.wrapping_add(1), RegisterY, index_wide),
+        Dex => transfer(processor, register_x.wrapping_sub(1), RegisterX, index_wide),
+        Dey => transfer(processor, register_y.wrapping_sub(1), RegisterY, index_wide),
+
+        Tax => transfer(processor, accumulator, RegisterX, index_wide),
+        Tay => transfer(processor, accumulator, RegisterY, index_wide),
+        Txa => transfer(
+            processor,
+            register_x,
+            Registers::Accumulator,
+            accumulator_wide,
+        ),
+        Tya => transfer(
+            processor,
+            register_y,
+            Registers::Accumulator,
+            accumulator_wide,
+        ),
+        Txy => transfer(processor, register_x, RegisterY, index_wide),
+        Tyx => transfer(processor, register_y, RegisterX, index_wide),
+        Tsx => transfer(
+            processor,
+            processor.wide_stack_pointer(),
+            RegisterX,
+            index_wide,
+        ),
+        Txs => {
+            processor.set_wide_stack_pointer(register_x);
+            processor.decrement_cycles(1);
+        }
+        Tcs => {
+            processor.set_wide_stack_pointer(accumulator);
+            processor.decrement_cycles(1);
+        }
+        Tsc | Tdc => {
+            // Both copy all 16 bits into C, even with an 8-bit accumulator
+            let value: u16 = match operation {
+                Tsc => processor.wide_stack_pointer(),
+                _ => processor.native.direct_page,
+            };
+            processor.accumulator = value as u8;
+            processor.native.accumulator_high = (value >> 8) as u8;
+            processor.set_result_flags(value, true);
+            processor.decrement_cycles(1);
+        }
+        Tcd => {
+            processor.native.direct_page = accumulator;
+            processor.set_result_flags(accumulator, true);
+            processor.decrement_cycles(1);
+        }
+        Xba => {
+            processor.accumulator = (accumulator >> 8) as u8;
+            processor.native.accumulator_high = accumulator as u8;
+            processor.set_result_flags(processor.accumulator as u16, false);
+            processor.decrement_cycles(2);
+        }
+        Xce => {
+            let emulation: bool = processor.native.emulation;
+            processor.set_emulation(processor.fetch_status(CarryFlag));
+            processor.set_status(CarryFlag, emulation);
+            processor.decrement_cycles(1);
+        }
+
+        Pha => {
+            push_value(processor, memory, accumulator, accumulator_wide);
+            processor.decrement_cycles(1);
+        }
+        Phx | Phy => {
+            let value: u16 = if operation == Phx {
+                register_x
+            } else {
+                register_y
+            };
+            push_value(processor, memory, value, index_wide);
+            processor.decrement_cycles(1);
+        }
+        Php => {
+            let mut status: u8 = processor.status;
+            if processor.native.emulation == true {
+                status = set_bit(set_bit(status, 4, true), 5, true);
+            }
+            push(processor, memory, status);
+            processor.decrement_cycles(1);
+        }
+        Phb | Phk => {
+            let bank: u8 = match operation {
+                Phb => processor.native.data_bank,
+                _ => processor.native.program_bank,
+            };
+            push(processor, memory, bank);
+            processor.decrement_cycles(1);
+        }
+        Phd => {
+            push_value(processor, memory, processor.native.direct_page, true);
+            processor.decrement_cycles(1);
+        }
+        Pla | Plx | Ply => {
+            processor.decrement_cycles(2);
+            let register: Registers = match operation {
+                Plx => RegisterX,
+                Ply => RegisterY,
+                _ => Registers::Accumulator,
+            };
+            let wide: bool = if operation == Pla {
+                accumulator_wide
+            } else {
+                index_wide
+            };
+            let value: u16 = pull_value(processor, memory, wide);
+            processor.set_wide_register(register, value);
+            processor.set_result_flags(value, wide);
+        }
+        Plp => {
+            processor.decrement_cycles(2);
+            let status: u8 = pull(processor, memory);
+            processor.latch_interrupt_disable();
+            processor.set_native_status(status);
+        }
+        Plb => {
+            processor.decrement_cycles(2);
+            processor.native.data_bank = pull(processor, memory);
+            processor.set_result_flags(processor.native.data_bank as u16, false);
+        }
+        Pld => {
+            processor.decrement_cycles(2);
+            processor.native.direct_page = pull_value(processor, memory, true);
+            processor.set_result_flags(processor.native.direct_page, true);
+        }
+        Pea => {
+            let value: u16 = fetch_word_native(processor, memory);
+            push_value(processor, memory, value, true);
+        }
+        Pei => {
+            let pointer: u32 = native_address(processor, memory, Direct, Read, false);
+            let value: u16 = read_value(processor, memory, Direct, pointer, true);
+            push_value(processor, memory, value, true);
+        }
+        Per => {
+            let offset: u16 = fetch_word_native(processor, memory);
+            processor.decrement_cycles(1);
+            push_value(
+                processor,
+                memory,
+                processor.program_counter.wrapping_add(offset),
+                true,
+            );
+        }
+
+        Clc => set_flag(processor, CarryFlag, false),
+        Sec => set_flag(processor, CarryFlag, true),
+        Cli | Sei => {
+            processor.latch_interrupt_disable();
+            set_flag(processor, InterruptDisable, operation == Sei);
+        }
+        Cld => set_flag(processor, DecimalMode, false),
+        Sed => set_flag(processor, DecimalMode, true),
+        Clv => set_flag(processor, OverflowFlag, false),
+        Rep | Sep => change_status(processor, memory, operation),
+
+        Bpl => processor.branch(memory, processor.fetch_status(NegativeFlag) == false),
+        Bmi => processor.branch(memory, processor.fetch_status(NegativeFlag)),
+        Bvc => processor.branch(memory, processor.fetch_status(OverflowFlag) == false),
+        Bvs => processor.branch(memory, processor.fetch_status(OverflowFlag)),
+        Bcc => processor.branch(memory, processor.fetch_status(CarryFlag) == false),
+        Bcs => processor.branch(memory, processor.fetch_status(CarryFlag)),
+        Bne => processor.branch(memory, processor.fetch_status(ZeroFlag) == false),
+        Beq => processor.branch(memory, processor.fetch_status(ZeroFlag)),
+        Bra => processor.branch(memory, true),
+        Brl => {
+            let offset: u16 = fetch_word_native(processor, memory);
+            processor.program_counter = processor.program_counter.wrapping_add(offset);
+            processor.decrement_cycles(1);
+        }
+
+        Jmp => processor.program_counter = fetch_word_native(processor, memory),
+        JmpIndirect => {
+            let pointer: u16 = fetch_word_native(processor, memory);
+            processor.program_counter = read_word_long(processor, memory, pointer as u32);
+        }
+        JmpIndexedIndirect | JsrIndexedIndirect => {
+            // The pointer table lives in the program bank rather than the data bank
+            let base: u16 = fetch_word_native(processor, memory);
+            if operation == JsrIndexedIndirect {
+                push_value(
+                    processor,
+                    memory,
+                    processor.program_counter.wrapping_sub(1),
+                    true,
+                );
+            }
+            processor.decrement_cycles(1);
+            let pointer: u32 = ((processor.native.program_bank as u32) << 16)
+                | base.wrapping_add(register_x) as u32;
+            processor.program_counter = read_word_long(processor, memory, pointer);
+        }
+        Jml => {
+            let address: u16 = fetch_word_native(processor, memory);
+            processor.native.program_bank = processor.fetch_byte(memory);
+            processor.program_counter = address;
+        }
+        JmlIndirect => {
+            let pointer: u16 = fetch_word_native(processor, memory);
+            processor.program_counter = read_word_long(processor, memory, pointer as u32);
+            processor.native.program_bank =
+                processor.read_long(memory, pointer.wrapping_add(2) as u32);
+        }
+        Jsr => {
+            let address: u16 = fetch_word_native(processor, memory);
+            processor.decrement_cycles(1);
+            push_value(
+                processor,
+                memory,
+                processor.program_counter.wrapping_sub(1),
+                true,
+            );
+            processor.program_counter = address;
+        }
+        Jsl => {
+            let address: u16 = fetch_word_native(processor, memory);
+            push(processor, memory, processor.native.program_bank);
+            processor.decrement_cycles(1);
+            let bank: u8 = processor.fetch_byte(memory);
+            push_value(
+                processor,
+                memory,
+                processor.program_counter.wrapping_sub(1),
+                true,
+            );
+            processor.native.program_bank = bank;
+            processor.program_counter = address;
+        }
+        Rts => {
+            processor.decrement_cycles(2);
+            processor.program_counter = pull_value(processor, memory, true).wrapping_add(1);
+            processor.decrement_cycles(1);
+        }
+        Rtl => {
+            processor.decrement_cycles(2);
+            processor.program_counter = pull_value(processor, memory, true).wrapping_add(1);
+            processor.native.program_bank = pull(processor, memory);
+        }
+        Rti => {
+            processor.decrement_cycles(2);
+            let status: u8 = pull(processor, memory);
+            processor.set_native_status(status);
+            processor.program_counter = pull_value(processor, memory, true);
+            if processor.native.emulation == false {
+                processor.native.program_bank = pull(processor, memory);
+            }
+        }
+
+        Brk | Cop => interrupt(processor, memory, operation),
+        Wai => processor.wait_for_interrupt(),
+        Stp => processor.stop(),
+        Wdm => {
+            processor.fetch_byte(memory); // Reserved for future expansion, the operand is skipped
+        }
+        Nop => processor.decrement_cycles(1),
+        Mvn | Mvp => block_move(processor, memory, operation),
+
+        _ => {}
+    }
+}
+
+pub trait NativeInstructions {
+    fn execute_native<B: Bus>(&mut self, memory: &mut B, opcode: u8) -> ();
+}
+
+impl NativeInstructions for Processor {
+    fn execute_native<B: Bus>(&mut self, memory: &mut B, opcode: u8) -> () {
+        let (operation, mode) = DECODED[opcode as usize];
+        let access: MemoryAccess = access_for(operation);
+        let wide: bool = is_wide(self, operation);
+
+        match mode {
+            Implied => return implied(self, memory, operation),
+            Accumulator => {
+                let value: u16 = self.wide_register(Registers::Accumulator);
+                let result: u16 = modify(self, operation, value, wide);
+                self.set_wide_register(Registers::Accumulator, result);
+                self.decrement_cycles(1);
+                return;
+            }
+            _ => {}
+        }
+
+        let address: u32 = native_address(self, memory, mode, access, wide);
+        match access {
+            Write => {
+                let value: u16 = match operation {
+                    Stz => 0,
+                    _ => self.wide_register(register_for(operation)),
+                };
+                write_value(self, memory, mode, address, value, wide);
+            }
+            Modify => {
+                let value: u16 = read_value(self, memory, mode, address, wide);
+                self.decrement_cycles(1); // The ALU works while the bus is idle
+                let result: u16 = match operation {
+                    Tsb | Trb => {
+                        let accumulator: u16 = self.wide_register(Registers::Accumulator);
+                        let mask: u16 = if wide { 0xFFFF } else { 0xFF };
+                        self.set_status(ZeroFlag, accumulator & value & mask == 0);
+                        match operation {
+                            Tsb => value | accumulator,
+                            _ => value & !accumulator,
+                        }
+                    }
+                    _ => modify(self, operation, value, wide),
+                };
+                write_value(self, memory, mode, address, result, wide);
+            }
+            Read => {
+                let value: u16 = read_value(self, memory, mode, address, wide);
+                read_operation(self, operation, mode, value, wide);
+            }
+        }
+    }
+}
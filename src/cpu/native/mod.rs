@@ -0,0 +1,3 @@
+pub mod addressing;
+pub mod decode;
+pub mod registers;
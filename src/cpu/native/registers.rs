@@ -0,0 +1,157 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::{self, *};
+use cpu::processor::*;
+
+pub const ACCUMULATOR_WIDTH: u8 = 0b0010_0000; // M, shares its bit with the unused flag
+pub const INDEX_WIDTH: u8 = 0b0001_0000; // X, shares its bit with the break flag
+
+/// The 65C816 state that has no home in the 8-bit register file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NativeRegisters {
+    pub emulation: bool,
+    pub accumulator_high: u8, // B, the hidden half of C that XBA swaps in
+    pub register_x_high: u8,
+    pub register_y_high: u8,
+    pub stack_pointer_high: u8,
+    pub direct_page: u16,
+    pub data_bank: u8,
+    pub program_bank: u8,
+}
+
+impl NativeRegisters {
+    pub fn new() -> NativeRegisters {
+        return NativeRegisters {
+            emulation: true,
+            accumulator_high: 0x00,
+            register_x_high: 0x00,
+            register_y_high: 0x00,
+            stack_pointer_high: 0x01,
+            direct_page: 0x0000,
+            data_bank: 0x00,
+            program_bank: 0x00,
+        };
+    }
+}
+
+pub trait WideRegisters {
+    fn accumulator_is_wide(&self) -> bool;
+    fn index_is_wide(&self) -> bool;
+
+    fn wide_register(&self, register: Registers) -> u16;
+    fn set_wide_register(&mut self, register: Registers, value: u16) -> ();
+    fn set_result_flags(&mut self, value: u16, wide: bool) -> ();
+
+    fn wide_stack_pointer(&self) -> u16;
+    fn set_wide_stack_pointer(&mut self, value: u16) -> ();
+
+    fn set_emulation(&mut self, emulation: bool) -> ();
+    fn set_native_status(&mut self, status: u8) -> ();
+}
+
+impl WideRegisters for Processor {
+    fn accumulator_is_wide(&self) -> bool {
+        return self.native.emulation == false && self.status & ACCUMULATOR_WIDTH == 0;
+    }
+
+    fn index_is_wide(&self) -> bool {
+        return self.native.emulation == false && self.status & INDEX_WIDTH == 0;
+    }
+
+    fn wide_register(&self, register: Registers) -> u16 {
+        match register {
+            Accumulator => {
+                return self.accumulator as u16 | ((self.native.accumulator_high as u16) << 8)
+            }
+            RegisterX => {
+                return self.register_x as u16 | ((self.native.register_x_high as u16) << 8)
+            }
+            RegisterY => {
+                return self.register_y as u16 | ((self.native.register_y_high as u16) << 8)
+            }
+        }
+    }
+
+    fn set_wide_register(&mut self, register: Registers, value: u16) -> () {
+        /* Only the low byte is touched unless the register is currently 16 bits wide */
+        match register {
+            Accumulator => {
+                self.accumulator = value as u8;
+                if self.accumulator_is_wide() {
+                    self.native.accumulator_high = (value >> 8) as u8;
+                }
+            }
+            RegisterX => {
+                self.register_x = value as u8;
+                if self.index_is_wide() {
+                    self.native.register_x_high = (value >> 8) as u8;
+                }
+            }
+            RegisterY => {
+                self.register_y = value as u8;
+                if self.index_is_wide() {
+                    self.native.register_y_high = (value >> 8) as u8;
+                }
+            }
+        }
+    }
+
+    fn set_result_flags(&mut self, value: u16, wide: bool) -> () {
+        match wide {
+            true => {
+                self.set_status(ZeroFlag, value == 0);
+                self.set_status(NegativeFlag, value & 0x8000 != 0);
+            }
+            false => {
+                self.set_status(ZeroFlag, value as u8 == 0);
+                self.set_status(NegativeFlag, fetch_bit(value as u8, 7));
+            }
+        }
+    }
+
+    fn wide_stack_pointer(&self) -> u16 {
+        return self.stack_pointer as u16 | ((self.native.stack_pointer_high as u16) << 8);
+    }
+
+    fn set_wide_stack_pointer(&mut self, value: u16) -> () {
+        // The stack never leaves page one in emulation mode
+        self.stack_pointer = value as u8;
+        if self.native.emulation == false {
+            self.native.stack_pointer_high = (value >> 8) as u8;
+        }
+    }
+
+    fn set_emulation(&mut self, emulation: bool) -> () {
+        /*
+            Entering emulation forces 8-bit registers and pins the stack to page one.
+            Leaving it exposes M and X, which start out set so the registers stay 8 bits wide.
+        */
+        self.native.emulation = emulation;
+
+        if emulation == true {
+            self.status &= !(ACCUMULATOR_WIDTH | INDEX_WIDTH);
+            self.native.register_x_high = 0x00;
+            self.native.register_y_high = 0x00;
+            self.native.stack_pointer_high = 0x01;
+        } else {
+            self.status |= ACCUMULATOR_WIDTH | INDEX_WIDTH;
+        }
+    }
+
+    fn set_native_status(&mut self, status: u8) -> () {
+        if self.native.emulation == true {
+            // Emulation mode has no M and X, the bits behave like they do for the shared PLP
+            self.status = status & !(ACCUMULATOR_WIDTH | INDEX_WIDTH);
+            return;
+        }
+
+        self.status = status;
+        if status & INDEX_WIDTH != 0 {
+            // Narrowing the index registers throws their high bytes away
+            self.native.register_x_high = 0x00;
+            self.native.register_y_high = 0x00;
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Direction of a single bus cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BusOperation {
+    Read,
+    Write,
+    Internal, // A cycle spent inside the processor, only reported by tick()
+}
+
+/// One access as it appears on the address and data pins, after any address masking.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusAccess {
+    pub address: u32, // Bank byte included on the 65C816
+    pub data: u8,
+    pub operation: BusOperation,
+    pub sync: bool, // High while the opcode of an instruction is being fetched
+}
+
+/// Host callback that sees every access the processor puts on the bus.
+pub struct BusObserver {
+    callback: Box<dyn FnMut(BusAccess)>,
+}
+
+impl fmt::Debug for BusObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BusObserver").finish()
+    }
+}
+
+impl BusObserver {
+    pub fn new<F: FnMut(BusAccess) + 'static>(callback: F) -> BusObserver {
+        return BusObserver {
+            callback: Box::new(callback),
+        };
+    }
+
+    pub fn notify(&mut self, access: BusAccess) -> () {
+        (self.callback)(access);
+    }
+}
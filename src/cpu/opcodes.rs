@@ -23,6 +23,28 @@ pub enum Registers {
     RegisterY,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressingMode {
+    Implied,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    IndirectX,
+    IndirectY,
+    ZeroPageIndirect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemoryAccess {
+    Read,
+    Write,
+    Modify,
+}
+
 pub const LDA_IMMEDIATE: u8 = 0xA9;
 pub const LDA_ZERO_PAGE: u8 = 0xA5;
 pub const LDA_ZERO_PAGE_X: u8 = 0xB5;
@@ -200,3 +222,292 @@ pub const ROR_ABSOLUTE_X: u8 = 0x7E;
 pub const NOP: u8 = 0xEA;
 pub const BRK: u8 = 0x00;
 pub const RTI: u8 = 0x40;
+
+// Undocumented NMOS opcodes
+
+pub const SLO_ZERO_PAGE: u8 = 0x07;
+pub const SLO_ZERO_PAGE_X: u8 = 0x17;
+pub const SLO_ABSOLUTE: u8 = 0x0F;
+pub const SLO_ABSOLUTE_X: u8 = 0x1F;
+pub const SLO_ABSOLUTE_Y: u8 = 0x1B;
+pub const SLO_INDIRECT_X: u8 = 0x03;
+pub const SLO_INDIRECT_Y: u8 = 0x13;
+
+pub const RLA_ZERO_PAGE: u8 = 0x27;
+pub const RLA_ZERO_PAGE_X: u8 = 0x37;
+pub const RLA_ABSOLUTE: u8 = 0x2F;
+pub const RLA_ABSOLUTE_X: u8 = 0x3F;
+pub const RLA_ABSOLUTE_Y: u8 = 0x3B;
+pub const RLA_INDIRECT_X: u8 = 0x23;
+pub const RLA_INDIRECT_Y: u8 = 0x33;
+
+pub const SRE_ZERO_PAGE: u8 = 0x47;
+pub const SRE_ZERO_PAGE_X: u8 = 0x57;
+pub const SRE_ABSOLUTE: u8 = 0x4F;
+pub const SRE_ABSOLUTE_X: u8 = 0x5F;
+pub const SRE_ABSOLUTE_Y: u8 = 0x5B;
+pub const SRE_INDIRECT_X: u8 = 0x43;
+pub const SRE_INDIRECT_Y: u8 = 0x53;
+
+pub const RRA_ZERO_PAGE: u8 = 0x67;
+pub const RRA_ZERO_PAGE_X: u8 = 0x77;
+pub const RRA_ABSOLUTE: u8 = 0x6F;
+pub const RRA_ABSOLUTE_X: u8 = 0x7F;
+pub const RRA_ABSOLUTE_Y: u8 = 0x7B;
+pub const RRA_INDIRECT_X: u8 = 0x63;
+pub const RRA_INDIRECT_Y: u8 = 0x73;
+
+pub const SAX_ZERO_PAGE: u8 = 0x87;
+pub const SAX_ZERO_PAGE_Y: u8 = 0x97;
+pub const SAX_ABSOLUTE: u8 = 0x8F;
+pub const SAX_INDIRECT_X: u8 = 0x83;
+
+pub const LAX_ZERO_PAGE: u8 = 0xA7;
+pub const LAX_ZERO_PAGE_Y: u8 = 0xB7;
+pub const LAX_ABSOLUTE: u8 = 0xAF;
+pub const LAX_ABSOLUTE_Y: u8 = 0xBF;
+pub const LAX_INDIRECT_X: u8 = 0xA3;
+pub const LAX_INDIRECT_Y: u8 = 0xB3;
+
+pub const DCP_ZERO_PAGE: u8 = 0xC7;
+pub const DCP_ZERO_PAGE_X: u8 = 0xD7;
+pub const DCP_ABSOLUTE: u8 = 0xCF;
+pub const DCP_ABSOLUTE_X: u8 = 0xDF;
+pub const DCP_ABSOLUTE_Y: u8 = 0xDB;
+pub const DCP_INDIRECT_X: u8 = 0xC3;
+pub const DCP_INDIRECT_Y: u8 = 0xD3;
+
+pub const ISC_ZERO_PAGE: u8 = 0xE7;
+pub const ISC_ZERO_PAGE_X: u8 = 0xF7;
+pub const ISC_ABSOLUTE: u8 = 0xEF;
+pub const ISC_ABSOLUTE_X: u8 = 0xFF;
+pub const ISC_ABSOLUTE_Y: u8 = 0xFB;
+pub const ISC_INDIRECT_X: u8 = 0xE3;
+pub const ISC_INDIRECT_Y: u8 = 0xF3;
+
+pub const ANC_IMMEDIATE: u8 = 0x0B;
+pub const ANC_IMMEDIATE_2B: u8 = 0x2B;
+pub const ALR_IMMEDIATE: u8 = 0x4B;
+pub const ARR_IMMEDIATE: u8 = 0x6B;
+pub const XAA_IMMEDIATE: u8 = 0x8B;
+pub const LXA_IMMEDIATE: u8 = 0xAB;
+pub const SBX_IMMEDIATE: u8 = 0xCB;
+pub const SBC_IMMEDIATE_EB: u8 = 0xEB;
+
+pub const AHX_ABSOLUTE_Y: u8 = 0x9F;
+pub const AHX_INDIRECT_Y: u8 = 0x93;
+pub const SHY_ABSOLUTE_X: u8 = 0x9C;
+pub const SHX_ABSOLUTE_Y: u8 = 0x9E;
+pub const TAS_ABSOLUTE_Y: u8 = 0x9B;
+pub const LAS_ABSOLUTE_Y: u8 = 0xBB;
+
+pub const NOP_IMPLIED_1A: u8 = 0x1A;
+pub const NOP_IMPLIED_3A: u8 = 0x3A;
+pub const NOP_IMPLIED_5A: u8 = 0x5A;
+pub const NOP_IMPLIED_7A: u8 = 0x7A;
+pub const NOP_IMPLIED_DA: u8 = 0xDA;
+pub const NOP_IMPLIED_FA: u8 = 0xFA;
+
+pub const NOP_IMMEDIATE_80: u8 = 0x80;
+pub const NOP_IMMEDIATE_82: u8 = 0x82;
+pub const NOP_IMMEDIATE_89: u8 = 0x89;
+pub const NOP_IMMEDIATE_C2: u8 = 0xC2;
+pub const NOP_IMMEDIATE_E2: u8 = 0xE2;
+
+pub const NOP_ZERO_PAGE_04: u8 = 0x04;
+pub const NOP_ZERO_PAGE_44: u8 = 0x44;
+pub const NOP_ZERO_PAGE_64: u8 = 0x64;
+
+pub const NOP_ZERO_PAGE_X_14: u8 = 0x14;
+pub const NOP_ZERO_PAGE_X_34: u8 = 0x34;
+pub const NOP_ZERO_PAGE_X_54: u8 = 0x54;
+pub const NOP_ZERO_PAGE_X_74: u8 = 0x74;
+pub const NOP_ZERO_PAGE_X_D4: u8 = 0xD4;
+pub const NOP_ZERO_PAGE_X_F4: u8 = 0xF4;
+
+pub const NOP_ABSOLUTE_0C: u8 = 0x0C;
+
+pub const NOP_ABSOLUTE_X_1C: u8 = 0x1C;
+pub const NOP_ABSOLUTE_X_3C: u8 = 0x3C;
+pub const NOP_ABSOLUTE_X_5C: u8 = 0x5C;
+pub const NOP_ABSOLUTE_X_7C: u8 = 0x7C;
+pub const NOP_ABSOLUTE_X_DC: u8 = 0xDC;
+pub const NOP_ABSOLUTE_X_FC: u8 = 0xFC;
+
+pub const JAM_02: u8 = 0x02;
+pub const JAM_12: u8 = 0x12;
+pub const JAM_22: u8 = 0x22;
+pub const JAM_32: u8 = 0x32;
+pub const JAM_42: u8 = 0x42;
+pub const JAM_52: u8 = 0x52;
+pub const JAM_62: u8 = 0x62;
+pub const JAM_72: u8 = 0x72;
+pub const JAM_92: u8 = 0x92;
+pub const JAM_B2: u8 = 0xB2;
+pub const JAM_D2: u8 = 0xD2;
+pub const JAM_F2: u8 = 0xF2;
+
+// 65C02 opcodes
+
+pub const BRA: u8 = 0x80;
+pub const PHX: u8 = 0xDA;
+pub const PHY: u8 = 0x5A;
+pub const PLX: u8 = 0xFA;
+pub const PLY: u8 = 0x7A;
+
+pub const STZ_ZERO_PAGE: u8 = 0x64;
+pub const STZ_ZERO_PAGE_X: u8 = 0x74;
+pub const STZ_ABSOLUTE: u8 = 0x9C;
+pub const STZ_ABSOLUTE_X: u8 = 0x9E;
+
+pub const TRB_ZERO_PAGE: u8 = 0x14;
+pub const TRB_ABSOLUTE: u8 = 0x1C;
+pub const TSB_ZERO_PAGE: u8 = 0x04;
+pub const TSB_ABSOLUTE: u8 = 0x0C;
+
+pub const OR_ZERO_PAGE_INDIRECT: u8 = 0x12;
+pub const AND_ZERO_PAGE_INDIRECT: u8 = 0x32;
+pub const EOR_ZERO_PAGE_INDIRECT: u8 = 0x52;
+pub const ADC_ZERO_PAGE_INDIRECT: u8 = 0x72;
+pub const STA_ZERO_PAGE_INDIRECT: u8 = 0x92;
+pub const LDA_ZERO_PAGE_INDIRECT: u8 = 0xB2;
+pub const CMP_ZERO_PAGE_INDIRECT: u8 = 0xD2;
+pub const SBC_ZERO_PAGE_INDIRECT: u8 = 0xF2;
+
+pub const BIT_IMMEDIATE: u8 = 0x89;
+pub const BIT_ZERO_PAGE_X: u8 = 0x34;
+pub const BIT_ABSOLUTE_X: u8 = 0x3C;
+
+pub const INC_ACCUMULATOR: u8 = 0x1A;
+pub const DEC_ACCUMULATOR: u8 = 0x3A;
+
+pub const JMP_ABSOLUTE_INDEXED_INDIRECT: u8 = 0x7C;
+
+pub const RMB0: u8 = 0x07;
+pub const RMB1: u8 = 0x17;
+pub const RMB2: u8 = 0x27;
+pub const RMB3: u8 = 0x37;
+pub const RMB4: u8 = 0x47;
+pub const RMB5: u8 = 0x57;
+pub const RMB6: u8 = 0x67;
+pub const RMB7: u8 = 0x77;
+
+pub const SMB0: u8 = 0x87;
+pub const SMB1: u8 = 0x97;
+pub const SMB2: u8 = 0xA7;
+pub const SMB3: u8 = 0xB7;
+pub const SMB4: u8 = 0xC7;
+pub const SMB5: u8 = 0xD7;
+pub const SMB6: u8 = 0xE7;
+pub const SMB7: u8 = 0xF7;
+
+pub const BBR0: u8 = 0x0F;
+pub const BBR1: u8 = 0x1F;
+pub const BBR2: u8 = 0x2F;
+pub const BBR3: u8 = 0x3F;
+pub const BBR4: u8 = 0x4F;
+pub const BBR5: u8 = 0x5F;
+pub const BBR6: u8 = 0x6F;
+pub const BBR7: u8 = 0x7F;
+
+pub const BBS0: u8 = 0x8F;
+pub const BBS1: u8 = 0x9F;
+pub const BBS2: u8 = 0xAF;
+pub const BBS3: u8 = 0xBF;
+pub const BBS4: u8 = 0xCF;
+pub const BBS5: u8 = 0xDF;
+pub const BBS6: u8 = 0xEF;
+pub const BBS7: u8 = 0xFF;
+
+pub const WAI: u8 = 0xCB;
+pub const STP: u8 = 0xDB;
+
+// 65C816 opcodes
+
+pub const OR_STACK_RELATIVE: u8 = 0x03;
+pub const OR_STACK_RELATIVE_INDIRECT_Y: u8 = 0x13;
+pub const OR_DIRECT_INDIRECT_LONG: u8 = 0x07;
+pub const OR_DIRECT_INDIRECT_LONG_Y: u8 = 0x17;
+pub const OR_ABSOLUTE_LONG: u8 = 0x0F;
+pub const OR_ABSOLUTE_LONG_X: u8 = 0x1F;
+
+pub const AND_STACK_RELATIVE: u8 = 0x23;
+pub const AND_STACK_RELATIVE_INDIRECT_Y: u8 = 0x33;
+pub const AND_DIRECT_INDIRECT_LONG: u8 = 0x27;
+pub const AND_DIRECT_INDIRECT_LONG_Y: u8 = 0x37;
+pub const AND_ABSOLUTE_LONG: u8 = 0x2F;
+pub const AND_ABSOLUTE_LONG_X: u8 = 0x3F;
+
+pub const EOR_STACK_RELATIVE: u8 = 0x43;
+pub const EOR_STACK_RELATIVE_INDIRECT_Y: u8 = 0x53;
+pub const EOR_DIRECT_INDIRECT_LONG: u8 = 0x47;
+pub const EOR_DIRECT_INDIRECT_LONG_Y: u8 = 0x57;
+pub const EOR_ABSOLUTE_LONG: u8 = 0x4F;
+pub const EOR_ABSOLUTE_LONG_X: u8 = 0x5F;
+
+pub const ADC_STACK_RELATIVE: u8 = 0x63;
+pub const ADC_STACK_RELATIVE_INDIRECT_Y: u8 = 0x73;
+pub const ADC_DIRECT_INDIRECT_LONG: u8 = 0x67;
+pub const ADC_DIRECT_INDIRECT_LONG_Y: u8 = 0x77;
+pub const ADC_ABSOLUTE_LONG: u8 = 0x6F;
+pub const ADC_ABSOLUTE_LONG_X: u8 = 0x7F;
+
+pub const STA_STACK_RELATIVE: u8 = 0x83;
+pub const STA_STACK_RELATIVE_INDIRECT_Y: u8 = 0x93;
+pub const STA_DIRECT_INDIRECT_LONG: u8 = 0x87;
+pub const STA_DIRECT_INDIRECT_LONG_Y: u8 = 0x97;
+pub const STA_ABSOLUTE_LONG: u8 = 0x8F;
+pub const STA_ABSOLUTE_LONG_X: u8 = 0x9F;
+
+pub const LDA_STACK_RELATIVE: u8 = 0xA3;
+pub const LDA_STACK_RELATIVE_INDIRECT_Y: u8 = 0xB3;
+pub const LDA_DIRECT_INDIRECT_LONG: u8 = 0xA7;
+pub const LDA_DIRECT_INDIRECT_LONG_Y: u8 = 0xB7;
+pub const LDA_ABSOLUTE_LONG: u8 = 0xAF;
+pub const LDA_ABSOLUTE_LONG_X: u8 = 0xBF;
+
+pub const CMP_STACK_RELATIVE: u8 = 0xC3;
+pub const CMP_STACK_RELATIVE_INDIRECT_Y: u8 = 0xD3;
+pub const CMP_DIRECT_INDIRECT_LONG: u8 = 0xC7;
+pub const CMP_DIRECT_INDIRECT_LONG_Y: u8 = 0xD7;
+pub const CMP_ABSOLUTE_LONG: u8 = 0xCF;
+pub const CMP_ABSOLUTE_LONG_X: u8 = 0xDF;
+
+pub const SBC_STACK_RELATIVE: u8 = 0xE3;
+pub const SBC_STACK_RELATIVE_INDIRECT_Y: u8 = 0xF3;
+pub const SBC_DIRECT_INDIRECT_LONG: u8 = 0xE7;
+pub const SBC_DIRECT_INDIRECT_LONG_Y: u8 = 0xF7;
+pub const SBC_ABSOLUTE_LONG: u8 = 0xEF;
+pub const SBC_ABSOLUTE_LONG_X: u8 = 0xFF;
+
+pub const COP: u8 = 0x02;
+pub const JSL: u8 = 0x22;
+pub const WDM: u8 = 0x42;
+pub const PER: u8 = 0x62;
+pub const BRL: u8 = 0x82;
+pub const REP: u8 = 0xC2;
+pub const SEP: u8 = 0xE2;
+
+pub const PHD: u8 = 0x0B;
+pub const TCS: u8 = 0x1B;
+pub const PLD: u8 = 0x2B;
+pub const TSC: u8 = 0x3B;
+pub const PHK: u8 = 0x4B;
+pub const TCD: u8 = 0x5B;
+pub const RTL: u8 = 0x6B;
+pub const TDC: u8 = 0x7B;
+pub const PHB: u8 = 0x8B;
+pub const TXY: u8 = 0x9B;
+pub const PLB: u8 = 0xAB;
+pub const TYX: u8 = 0xBB;
+pub const XBA: u8 = 0xEB;
+pub const XCE: u8 = 0xFB;
+
+pub const MVP: u8 = 0x44;
+pub const MVN: u8 = 0x54;
+pub const PEI: u8 = 0xD4;
+pub const PEA: u8 = 0xF4;
+
+pub const JML_ABSOLUTE_LONG: u8 = 0x5C;
+pub const JML_ABSOLUTE_INDIRECT_LONG: u8 = 0xDC;
+pub const JSR_ABSOLUTE_INDEXED_INDIRECT: u8 = 0xFC;
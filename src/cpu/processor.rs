@@ -1,28 +1,71 @@
-use super::instructions;
-use super::instructions::arithmetic::add::AddWithCarry;
-use super::instructions::arithmetic::compare::Compare;
-use super::instructions::arithmetic::subtract::SubtractWithCarry;
-use super::instructions::branches::Branches;
-use super::instructions::shifts::Shifts;
-use super::instructions::system::System;
 use crate::cpu;
 use crate::mem::*;
 use std::fmt;
 
-use instructions::decrement::*; // dec, dex, dey
-use instructions::increment::*; // inc, inx, iny
-use instructions::jumps::*; // jsr, rts, jmp
-use instructions::logical::*; // eor, or, and
-use instructions::registers::load::*; // lda, ldx, ldy
-use instructions::registers::store::*; // sta, stx, sty
-use instructions::stackops::*; // tsx, txs, pha, php, pla, plp
-use instructions::transfers::*; // jsr, rts, jmp
-
+use cpu::blocks::{BlockCache, BlockExecution, Engine};
+use cpu::control::ControlLines;
+use cpu::debugger::Debugger;
+use cpu::decode::execute_instruction;
 use cpu::functions::byte::*;
-use cpu::opcodes::LogicalOperations::*;
+use cpu::functions::word::*;
+use cpu::instructions::system::System;
+use cpu::interrupts::{InterruptLines, InterruptPoll, RESET_VECTOR};
+use cpu::io_port::IoPort;
+use cpu::native::registers::NativeRegisters;
+use cpu::observer::{BusAccess, BusObserver};
 use cpu::opcodes::ProcessorStatus::*;
-use cpu::opcodes::Registers::*;
 use cpu::opcodes::*;
+use cpu::regions::{Regions, Violation};
+use cpu::stepper::{CycleStepper, Stepping};
+use cpu::tracer::{TraceRecord, Tracer};
+use cpu::variant::{Quirk, Variant};
+
+/// What the processor does when it fetches one of the undocumented NMOS opcodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IllegalOpcodePolicy {
+    Emulate,
+    Nop,
+    Halt,
+}
+
+/// Why the processor refused to continue executing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    IllegalOpcode { opcode: u8, address: u16 },
+    Jammed { opcode: u8, address: u16 },
+    AccessViolation { violation: Violation },
+}
+
+/// Why execute() handed control back to the host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+    BudgetExhausted, // Also returned while RDY holds the processor
+    IllegalOpcode { opcode: u8, address: u16 },
+    Jammed { opcode: u8, address: u16 },
+    AccessViolation { violation: Violation },
+    Breakpoint { address: u32 },
+    Watchpoint { access: BusAccess },
+    BreakTrap { address: u32 },
+    HaltRequested,
+    Waiting, // Parked by WAI
+    Stopped, // Parked by STP
+}
+
+/// What a call to execute() did and why it ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExecutionResult {
+    pub cycles: i64, // Includes any cycles the last instruction ran past the budget
+    pub instructions: u64,
+    pub stop: StopReason,
+}
+
+/// Whether the processor is fetching instructions or parked by WAI or STP.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExecutionState {
+    Running,
+    Waiting,
+    Stopped,
+}
 
 #[derive(Debug)]
 pub struct Processor {
@@ -33,6 +76,66 @@ pub struct Processor {
     pub register_y: u8,
     pub status: u8,
     pub cycles: u32,
+    pub clock: u64,        // Every cycle run since power on, RDY stalls aside
+    pub bus_clock: u64,    // How far the bus has been ticked, it catches up before each bus cycle
+    pub data_bus: DataBus, // Left by the last cycle that drove it, what reads nothing answers see
+    pub variant: Variant,
+    pub illegal_opcode_policy: IllegalOpcodePolicy,
+    pub unstable_magic: u8, // ORed into the unstable term of XAA, LXA, AHX, TAS, SHX and SHY
+    pub fault: Option<Fault>,
+    pub state: ExecutionState,
+    pub io_port: Option<IoPort>, // Only present on variants with an on-chip port
+    pub bus_observer: Option<BusObserver>,
+    pub tracer: Option<Tracer>,
+    pub interrupts: InterruptLines, // IRQ and NMI pins, held by the host
+    pub poll: InterruptPoll,
+    pub control: ControlLines,   // RDY and SO, held by the host
+    pub stolen_cycles: u64,      // Cycles spent held by RDY, kept out of the executed count
+    pub native: NativeRegisters, // 65C816 registers, every other variant stays in emulation mode
+    pub stepper: CycleStepper,   // State of the instruction tick() is partway through
+    pub debugger: Debugger,
+    pub regions: Regions, // Attributes the host has given ranges of memory
+    pub engine: Engine,
+    pub blocks: BlockCache, // Only filled while the engine is Engine::Blocks
+}
+
+impl Processor {
+    pub fn new(variant: Variant) -> Processor {
+        /* The variant is fixed for the lifetime of the processor, registers are settled by reset() */
+        return Processor {
+            program_counter: 0,
+            stack_pointer: 0,
+            accumulator: 0,
+            register_x: 0,
+            register_y: 0,
+            status: 0,
+            cycles: 0,
+            clock: 0,
+            bus_clock: 0,
+            data_bus: DataBus::new(),
+            variant,
+            illegal_opcode_policy: IllegalOpcodePolicy::Emulate,
+            unstable_magic: 0x00,
+            fault: None,
+            state: ExecutionState::Running,
+            io_port: match variant.has_io_port() {
+                true => Some(IoPort::new()),
+                false => None,
+            },
+            bus_observer: None,
+            tracer: None,
+            interrupts: InterruptLines::new(),
+            poll: InterruptPoll::new(),
+            control: ControlLines::new(),
+            stolen_cycles: 0,
+            native: NativeRegisters::new(),
+            stepper: CycleStepper::new(),
+            debugger: Debugger::new(),
+            regions: Regions::new(),
+            engine: Engine::Interpreter,
+            blocks: BlockCache::new(),
+        };
+    }
 }
 
 impl fmt::UpperHex for Processor {
@@ -54,16 +157,181 @@ impl fmt::UpperHex for Processor {
     }
 }
 
+fn stop_reason<B: Bus>(processor: &mut Processor, memory: &B) -> Option<StopReason> {
+    /* Looked at on every instruction boundary, faults and parked states come before the debugger */
+    let address: u32 =
+        ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32;
+    if processor.fault.is_none() && processor.state == ExecutionState::Running {
+        if let Some(violation) = processor
+            .regions
+            .check(address, processor.variant.address_mask())
+        {
+            processor.fault = Some(Fault::AccessViolation { violation });
+        }
+    }
+
+    match processor.fault {
+        Some(Fault::IllegalOpcode { opcode, address }) => {
+            return Some(StopReason::IllegalOpcode { opcode, address })
+        }
+        Some(Fault::Jammed { opcode, address }) => {
+            return Some(StopReason::Jammed { opcode, address })
+        }
+        Some(Fault::AccessViolation { violation }) => {
+            return Some(StopReason::AccessViolation { violation })
+        }
+        None => {}
+    }
+
+    match processor.state {
+        ExecutionState::Waiting => return Some(StopReason::Waiting),
+        ExecutionState::Stopped => return Some(StopReason::Stopped),
+        ExecutionState::Running => {}
+    }
+
+    let opcode: u8 = memory.peek(address & processor.variant.address_mask());
+    let bank: Option<u16> = memory.bank(address & processor.variant.address_mask());
+    return processor.debugger.check(address, bank, opcode);
+}
+
+fn execution_result(
+    processor: &mut Processor,
+    start: u64,
+    deadline: u64,
+    instructions: u64,
+    stop: StopReason,
+) -> ExecutionResult {
+    // Whatever the call did not spend stays in the budget, running past it leaves nothing
+    processor.cycles = deadline.saturating_sub(processor.clock) as u32;
+    return ExecutionResult {
+        cycles: (processor.clock - start) as i64,
+        instructions,
+        stop,
+    };
+}
+
+pub fn execute_with<B: Bus, C>(
+    processor: &mut Processor,
+    memory: &mut B,
+    mut compiled: C,
+) -> ExecutionResult
+where
+    C: FnMut(&mut Processor, &mut B, u32, u64) -> Option<u64>,
+{
+    /*
+        The budget in `cycles` becomes a deadline on the clock, what the call ran is how far the clock moved.
+        `compiled` is offered every instruction boundary first, returning how many instructions it ran there.
+    */
+    let start: u64 = processor.clock;
+    let deadline: u64 = start + processor.cycles as u64;
+    let mut instructions: u64 = 0;
+    processor.debugger.begin();
+
+    // An instruction left partway through by tick() is completed before whole instructions resume
+    if processor.finish_instruction(memory) > 0 {
+        instructions += 1;
+    }
+
+    if let Some(Fault::Jammed { opcode, address }) = processor.fault {
+        // A jammed processor only leaves this state through a reset, so the budget simply elapses
+        processor.clock = processor.clock.max(deadline);
+        let stop = StopReason::Jammed { opcode, address };
+        return execution_result(processor, start, deadline, instructions, stop);
+    }
+
+    processor.sync_bus(memory);
+    processor.wake_on_interrupt();
+
+    if processor.state != ExecutionState::Running {
+        processor.clock = processor.clock.max(deadline);
+        let stop = match processor.state {
+            ExecutionState::Waiting => StopReason::Waiting,
+            _ => StopReason::Stopped,
+        };
+        return execution_result(processor, start, deadline, instructions, stop);
+    }
+
+    if processor.control.ready() == false {
+        /*
+            Held by RDY on the opcode fetch, or on a read of the instruction tick() left in flight.
+            The budget passes as stolen cycles and only what ran counts as executed.
+        */
+        let stolen: u64 = deadline.saturating_sub(processor.clock);
+        processor.stolen_cycles += stolen;
+        if let Some(port) = processor.io_port.as_mut() {
+            port.tick(stolen as u32);
+        }
+        memory.tick(stolen as u32); // Devices keep counting while the processor is held
+        let result = execution_result(
+            processor,
+            start,
+            deadline,
+            instructions,
+            StopReason::BudgetExhausted,
+        );
+        processor.cycles = 0;
+        return result;
+    }
+
+    let stop: StopReason = loop {
+        if let Some(reason) = stop_reason(processor, memory) {
+            break reason;
+        }
+        if processor.clock >= deadline {
+            break StopReason::BudgetExhausted;
+        }
+
+        processor.sync_bus(memory);
+        processor.apply_set_overflow();
+        if processor.poll_interrupts(memory) {
+            continue;
+        }
+
+        // Nothing is captured unless a tracer wants this address
+        let address: u32 =
+            ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32;
+        let mut traced: Option<TraceRecord> = match processor.tracer.as_ref() {
+            Some(tracer) if tracer.traces(address) => {
+                Some(TraceRecord::capture(processor, memory, address))
+            }
+            _ => None,
+        };
+        let started: u64 = processor.clock;
+
+        // Recompiled code takes the address if it has a block there it can still vouch for
+        if let Some(executed) = compiled(processor, memory, address, deadline) {
+            instructions += executed;
+            continue;
+        }
+        if processor.engine == Engine::Blocks {
+            instructions += processor.run_block(memory, deadline);
+            continue;
+        }
+
+        let instruction: u8 = processor.fetch_opcode(memory);
+        execute_instruction(processor, memory, instruction);
+        instructions += 1;
+
+        if let Some(record) = traced.as_mut() {
+            record.cycles = (processor.clock - started) as u32;
+            processor.tracer.as_mut().unwrap().record(record);
+        }
+    };
+
+    return execution_result(processor, start, deadline, instructions, stop);
+}
+
 pub trait Functions {
     fn increment_pc(&mut self) -> ();
     fn decrement_cycles(&mut self, amount: u32) -> ();
 
-    fn reset(&mut self, memory: &mut Memory, reset_vector: u16) -> ();
+    fn reset<B: Bus>(&mut self, memory: &mut B) -> ();
+    fn power_on(&mut self, memory: &mut Memory, pattern: RamPattern) -> ();
     fn set_status(&mut self, flag: ProcessorStatus, value: bool) -> ();
     fn fetch_status(&self, flag: ProcessorStatus) -> bool;
 
-    fn load_program(&mut self, memory: &mut Memory, program: &[u8]) -> u16;
-    fn execute(&mut self, memory: &mut Memory) -> i64;
+    fn load_program<B: Bus>(&mut self, memory: &mut B, program: &[u8]) -> u16;
+    fn execute<B: Bus>(&mut self, memory: &mut B) -> ExecutionResult;
 }
 
 impl Functions for Processor {
@@ -72,20 +340,60 @@ impl Functions for Processor {
     }
 
     fn decrement_cycles(&mut self, amount: u32) -> () {
-        if self.cycles == 0 {
-            println!("Cycles overflowed");
+        self.clock += amount as u64;
+
+        let address: u32 = ((self.native.program_bank as u32) << 16) | self.program_counter as u32;
+        let elapsed: u32 = self.stepper.advance(amount, address, self.control.ready());
+        if let Some(port) = self.io_port.as_mut() {
+            port.tick(elapsed);
         }
-        self.cycles = self.cycles.saturating_sub(amount);
     }
 
-    fn reset(&mut self, memory: &mut Memory, reset_vector: u16) -> () {
-        self.program_counter = reset_vector;
-        self.stack_pointer = 0xFF;
-        self.status = 0;
+    fn reset<B: Bus>(&mut self, memory: &mut B) -> () {
+        /*
+            Reset runs the interrupt sequence with its writes turned into reads: two dummy reads at
+            PC, three dummy stack reads that still drop SP by 3, then the vector at $FFFC.
+            A, X and Y keep whatever they held and memory is left alone.
+        */
+        self.fault = None;
+        self.state = ExecutionState::Running;
+        self.stepper.abandon();
+        self.native = NativeRegisters::new();
+        self.interrupts.acknowledge_nmi(); // The pins stay as the host left them, only the NMI latch clears
+        self.poll = InterruptPoll::new();
+        if let Some(port) = self.io_port.as_mut() {
+            port.reset();
+        }
+
+        self.read_byte(memory, self.program_counter);
+        self.read_byte(memory, self.program_counter);
+        for _ in 0..3 {
+            let stack_address: u16 = 0x100 | self.stack_pointer as u16;
+            self.read_byte(memory, stack_address);
+            self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        }
+
+        self.set_status(InterruptDisable, true);
+        if self.variant.has_quirk(Quirk::BreakClearsDecimal) {
+            self.set_status(DecimalMode, false);
+        }
+        self.program_counter = self.read_word(memory, RESET_VECTOR);
+    }
+
+    fn power_on(&mut self, memory: &mut Memory, pattern: RamPattern) -> () {
+        /*
+            Registers come up as zeroes before the reset sequence runs, so SP always ends at $FD.
+            Only flat RAM has a power-on pattern, other buses set up their own RAM and call reset().
+        */
+        memory.fill(pattern);
+
         self.accumulator = 0;
         self.register_x = 0;
         self.register_y = 0;
-        memory.data = [0; MAX_MEMORY]
+        self.stack_pointer = 0;
+        self.status = 0;
+        self.program_counter = 0;
+        self.reset(memory);
     }
 
     fn set_status(&mut self, flag: ProcessorStatus, value: bool) -> () {
@@ -114,7 +422,7 @@ impl Functions for Processor {
         }
     }
 
-    fn load_program(&mut self, memory: &mut Memory, program: &[u8]) -> u16 {
+    fn load_program<B: Bus>(&mut self, memory: &mut B, program: &[u8]) -> u16 {
         let program_length = program.len();
         if program_length > 2 {
             let mut position = 0;
@@ -128,7 +436,7 @@ impl Functions for Processor {
             position = 2;
 
             for index in load_address..final_index {
-                memory.data[index as usize] = program[position];
+                memory.poke(index as u32, program[position]);
                 position += 1;
             }
 
@@ -138,221 +446,7 @@ impl Functions for Processor {
         return 0x200; // returns end of zero page
     }
 
-    fn execute(&mut self, memory: &mut Memory) -> i64 {
-        let origin_cycles: u32 = self.cycles.clone();
-
-        while self.cycles > 0 {
-            let instruction: u8 = self.fetch_byte(&memory);
-            println!("{:X} | INS: {:#X}", self, instruction);
-
-            match instruction {
-                LDA_IMMEDIATE => self.load_immediate(memory, Accumulator),
-                LDA_ZERO_PAGE => self.load_zero_page(memory, Accumulator, None),
-                LDA_ZERO_PAGE_X => self.load_zero_page(memory, Accumulator, Some(RegisterX)),
-                LDA_ABSOLUTE => self.load_absolute(memory, Accumulator, None),
-                LDA_ABSOLUTE_X => self.load_absolute(memory, Accumulator, Some(RegisterX)),
-                LDA_ABSOLUTE_Y => self.load_absolute(memory, Accumulator, Some(RegisterY)),
-                LDA_INDIRECT_X => self.load_indirect_x(&memory),
-                LDA_INDIRECT_Y => self.load_indirect_y(&memory),
-
-                LDX_IMMEDIATE => self.load_immediate(memory, RegisterX),
-                LDX_ZERO_PAGE => self.load_zero_page(memory, RegisterX, None),
-                LDX_ZERO_PAGE_Y => self.load_zero_page(memory, RegisterX, Some(RegisterY)),
-                LDX_ABSOLUTE => self.load_absolute(memory, RegisterX, None),
-                LDX_ABSOLUTE_Y => self.load_absolute(memory, RegisterX, Some(RegisterY)),
-
-                LDY_IMMEDIATE => self.load_immediate(memory, RegisterY),
-                LDY_ZERO_PAGE => self.load_zero_page(memory, RegisterY, None),
-                LDY_ZERO_PAGE_X => self.load_zero_page(memory, RegisterY, Some(RegisterX)),
-                LDY_ABSOLUTE => self.load_absolute(memory, RegisterY, None),
-                LDY_ABSOLUTE_X => self.load_absolute(memory, RegisterY, Some(RegisterX)),
-
-                STA_ZERO_PAGE => self.store_zero_page(memory, Accumulator, None),
-                STA_ZERO_PAGE_X => self.store_zero_page(memory, Accumulator, Some(RegisterX)),
-                STA_ABSOLUTE => self.store_absolute(memory, Accumulator, None),
-                STA_ABSOLUTE_X => self.store_absolute(memory, Accumulator, Some(RegisterX)),
-                STA_ABSOLUTE_Y => self.store_absolute(memory, Accumulator, Some(RegisterY)),
-                STA_INDIRECT_X => self.store_indirect_x(memory),
-                STA_INDIRECT_Y => self.store_indirect_y(memory),
-
-                STX_ZERO_PAGE => self.store_zero_page(memory, RegisterX, None),
-                STX_ZERO_PAGE_Y => self.store_zero_page(memory, RegisterX, Some(RegisterY)),
-                STX_ABSOLUTE => self.store_absolute(memory, RegisterX, None),
-
-                STY_ZERO_PAGE => self.store_zero_page(memory, RegisterY, None),
-                STY_ZERO_PAGE_X => self.store_zero_page(memory, RegisterY, Some(RegisterX)),
-                STY_ABSOLUTE => self.store_absolute(memory, RegisterY, None),
-
-                JSR => self.jsr(memory),
-                RTS => self.rts(memory),
-                JMP_ABSOLUTE => self.jump_absolute(memory),
-                JMP_INDIRECT => self.jump_indirect(memory),
-
-                TSX => self.tsx(),
-                TXS => self.txs(),
-                PHA => self.pha(memory),
-                PHP => self.php(memory),
-                PLA => self.pla(memory),
-                PLP => self.plp(memory),
-
-                AND_IMMEDIATE => self.logic_immediate(memory, And),
-                AND_ZERO_PAGE => self.logic_zero_page(memory, And, None),
-                AND_ZERO_PAGE_X => self.logic_zero_page(memory, And, Some(RegisterX)),
-                AND_ABSOLUTE => self.logic_absolute(memory, And, None),
-                AND_ABSOLUTE_X => self.logic_absolute(memory, And, Some(RegisterX)),
-                AND_ABSOLUTE_Y => self.logic_absolute(memory, And, Some(RegisterY)),
-                AND_INDIRECT_X => self.logic_indirect_x(memory, And),
-                AND_INDIRECT_Y => self.logic_indirect_y(memory, And),
-
-                OR_IMMEDIATE => self.logic_immediate(memory, Or),
-                OR_ZERO_PAGE => self.logic_zero_page(memory, Or, None),
-                OR_ZERO_PAGE_X => self.logic_zero_page(memory, Or, Some(RegisterX)),
-                OR_ABSOLUTE => self.logic_absolute(memory, Or, None),
-                OR_ABSOLUTE_X => self.logic_absolute(memory, Or, Some(RegisterX)),
-                OR_ABSOLUTE_Y => self.logic_absolute(memory, Or, Some(RegisterY)),
-                OR_INDIRECT_X => self.logic_indirect_x(memory, Or),
-                OR_INDIRECT_Y => self.logic_indirect_y(memory, Or),
-
-                EOR_IMMEDIATE => self.logic_immediate(memory, ExclusiveOr),
-                EOR_ZERO_PAGE => self.logic_zero_page(memory, ExclusiveOr, None),
-                EOR_ZERO_PAGE_X => self.logic_zero_page(memory, ExclusiveOr, Some(RegisterX)),
-                EOR_ABSOLUTE => self.logic_absolute(memory, ExclusiveOr, None),
-                EOR_ABSOLUTE_X => self.logic_absolute(memory, ExclusiveOr, Some(RegisterX)),
-                EOR_ABSOLUTE_Y => self.logic_absolute(memory, ExclusiveOr, Some(RegisterY)),
-                EOR_INDIRECT_X => self.logic_indirect_x(memory, ExclusiveOr),
-                EOR_INDIRECT_Y => self.logic_indirect_y(memory, ExclusiveOr),
-
-                BIT_ZERO_PAGE => self.bit_zero_page(memory),
-                BIT_ABSOLUTE => self.bit_absolute(memory),
-
-                TAX => self.transfer_accumulator_to_x(),
-                TAY => self.transfer_accumulator_to_y(),
-                TXA => self.transfer_x_to_accumulator(),
-                TYA => self.transfer_y_to_accumulator(),
-
-                INX => self.increment_x(),
-                INY => self.increment_y(),
-                INC_ZERO_PAGE => self.increment_memory_zero_page(memory, None),
-                INC_ZERO_PAGE_X => self.increment_memory_zero_page(memory, Some(RegisterX)),
-                INC_ABSOLUTE => self.increment_memory_absolute(memory, None),
-                INC_ABSOLUTE_X => self.increment_memory_absolute(memory, Some(RegisterX)),
-
-                DEX => self.decrement_x(),
-                DEY => self.decrement_y(),
-                DEC_ZERO_PAGE => self.decrement_memory_zero_page(memory, None),
-                DEC_ZERO_PAGE_X => self.decrement_memory_zero_page(memory, Some(RegisterX)),
-                DEC_ABSOLUTE => self.decrement_memory_absolute(memory, None),
-                DEC_ABSOLUTE_X => self.decrement_memory_absolute(memory, Some(RegisterX)),
-
-                BEQ => self.branch(memory, self.fetch_status(ZeroFlag)),
-                BNE => self.branch(memory, self.fetch_status(ZeroFlag) == false),
-                BCS => self.branch(memory, self.fetch_status(CarryFlag)),
-                BCC => self.branch(memory, self.fetch_status(CarryFlag) == false),
-                BMI => self.branch(memory, self.fetch_status(NegativeFlag)),
-                BPL => self.branch(memory, self.fetch_status(NegativeFlag) == false),
-                BVS => self.branch(memory, self.fetch_status(OverflowFlag)),
-                BVC => self.branch(memory, self.fetch_status(OverflowFlag) == false),
-
-                NOP => self.decrement_cycles(1),
-                CLC => {
-                    self.set_status(CarryFlag, false);
-                    self.decrement_cycles(1);
-                }
-                CLD => {
-                    self.set_status(DecimalMode, false);
-                    self.decrement_cycles(1);
-                }
-                CLI => {
-                    self.set_status(InterruptDisable, false);
-                    self.decrement_cycles(1);
-                }
-                CLV => {
-                    self.set_status(OverflowFlag, false);
-                    self.decrement_cycles(1);
-                }
-                SEC => {
-                    self.set_status(CarryFlag, true);
-                    self.decrement_cycles(1);
-                }
-                SED => {
-                    self.set_status(DecimalMode, true);
-                    self.decrement_cycles(1);
-                }
-                SEI => {
-                    self.set_status(InterruptDisable, true);
-                    self.decrement_cycles(1);
-                }
-
-                ADC_IMMEDIATE => self.adc_immediate(memory),
-                ADC_ABSOLUTE => self.adc_absolute(memory, None),
-                ADC_ABSOLUTE_X => self.adc_absolute(memory, Some(RegisterX)),
-                ADC_ABSOLUTE_Y => self.adc_absolute(memory, Some(RegisterY)),
-                ADC_ZERO_PAGE => self.adc_zero_page(memory, None),
-                ADC_ZERO_PAGE_X => self.adc_zero_page(memory, Some(RegisterX)),
-                ADC_INDIRECT_X => self.adc_indirect_x(memory),
-                ADC_INDIRECT_Y => self.adc_indirect_y(memory),
-
-                CMP_IMMEDIATE => self.cmp_immediate(memory, Accumulator),
-                CMP_ABSOLUTE => self.cmp_absolute(memory, Accumulator, None),
-                CMP_ABSOLUTE_X => self.cmp_absolute(memory, Accumulator, Some(RegisterX)),
-                CMP_ABSOLUTE_Y => self.cmp_absolute(memory, Accumulator, Some(RegisterY)),
-                CMP_ZERO_PAGE => self.cmp_zero_page(memory, Accumulator, None),
-                CMP_ZERO_PAGE_X => self.cmp_zero_page(memory, Accumulator, Some(RegisterX)),
-                CMP_INDIRECT_X => self.cmp_indirect_x(memory, Accumulator),
-                CMP_INDIRECT_Y => self.cmp_indirect_y(memory, Accumulator),
-
-                CPX_IMMEDIATE => self.cmp_immediate(memory, RegisterX),
-                CPX_ZERO_PAGE => self.cmp_zero_page(memory, RegisterX, None),
-                CPX_ABSOLUTE => self.cmp_absolute(memory, RegisterX, None),
-
-                CPY_IMMEDIATE => self.cmp_immediate(memory, RegisterY),
-                CPY_ZERO_PAGE => self.cmp_zero_page(memory, RegisterY, None),
-                CPY_ABSOLUTE => self.cmp_absolute(memory, RegisterY, None),
-
-                SBC_IMMEDIATE => self.sbc_immediate(memory),
-                SBC_ABSOLUTE => self.sbc_absolute(memory, None),
-                SBC_ABSOLUTE_X => self.sbc_absolute(memory, Some(RegisterX)),
-                SBC_ABSOLUTE_Y => self.sbc_absolute(memory, Some(RegisterY)),
-                SBC_ZERO_PAGE => self.sbc_zero_page(memory, None),
-                SBC_ZERO_PAGE_X => self.sbc_zero_page(memory, Some(RegisterX)),
-                SBC_INDIRECT_X => self.sbc_indirect_x(memory),
-                SBC_INDIRECT_Y => self.sbc_indirect_y(memory),
-
-                ASL_ACCUMULATOR => self.shift_left(memory, ASL_ACCUMULATOR),
-                ASL_ZERO_PAGE => self.shift_left(memory, ASL_ZERO_PAGE),
-                ASL_ZERO_PAGE_X => self.shift_left(memory, ASL_ZERO_PAGE_X),
-                ASL_ABSOLUTE => self.shift_left(memory, ASL_ABSOLUTE),
-                ASL_ABSOLUTE_X => self.shift_left(memory, ASL_ABSOLUTE_X),
-
-                LSR_ACCUMULATOR => self.shift_right(memory, LSR_ACCUMULATOR),
-                LSR_ZERO_PAGE => self.shift_right(memory, LSR_ZERO_PAGE),
-                LSR_ZERO_PAGE_X => self.shift_right(memory, LSR_ZERO_PAGE_X),
-                LSR_ABSOLUTE => self.shift_right(memory, LSR_ABSOLUTE),
-                LSR_ABSOLUTE_X => self.shift_right(memory, LSR_ABSOLUTE_X),
-
-                ROL_ACCUMULATOR => self.rotate_left(memory, ROL_ACCUMULATOR),
-                ROL_ZERO_PAGE => self.rotate_left(memory, ROL_ZERO_PAGE),
-                ROL_ZERO_PAGE_X => self.rotate_left(memory, ROL_ZERO_PAGE_X),
-                ROL_ABSOLUTE => self.rotate_left(memory, ROL_ABSOLUTE),
-                ROL_ABSOLUTE_X => self.rotate_left(memory, ROL_ABSOLUTE_X),
-
-                ROR_ACCUMULATOR => self.rotate_right(memory, ROR_ACCUMULATOR),
-                ROR_ZERO_PAGE => self.rotate_right(memory, ROR_ZERO_PAGE),
-                ROR_ZERO_PAGE_X => self.rotate_right(memory, ROR_ZERO_PAGE_X),
-                ROR_ABSOLUTE => self.rotate_right(memory, ROR_ABSOLUTE),
-                ROR_ABSOLUTE_X => self.rotate_right(memory, ROR_ABSOLUTE_X),
-
-                BRK => self.force_interrupt(memory),
-                RTI => self.return_from_interrupt(memory),
-
-                _ => {
-                    println!("Unknown instruction {:#X}", instruction);
-                    return (origin_cycles as i64 - 1) - self.cycles as i64;
-                }
-            }
-        }
-
-        let cycles_used: i64 = origin_cycles as i64 - self.cycles as i64;
-        return cycles_used;
+    fn execute<B: Bus>(&mut self, memory: &mut B) -> ExecutionResult {
+        return execute_with(self, memory, |_, _, _, _| None);
     }
 }
@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, VecDeque};
+
+pub const LOG_LIMIT: usize = 1024; // Violations Regions::log holds before the oldest make way
+
+/// What a range of addresses is allowed to be used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Attribute {
+    Ram,       // Anything goes, for carving a hole out of a larger region
+    Rom,       // Writes never land
+    WriteOnce, // The first write to each address lands, later ones do not
+    NoExecute, // Opcodes must not be fetched from it
+}
+
+/// What happens when a region is used against its attribute. Blocked writes are dropped whatever
+/// the policy, it only decides whether anyone hears about it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViolationPolicy {
+    Ignore,
+    Log,  // Kept in Regions::log for the host to look at
+    Stop, // Raises Fault::AccessViolation, execute() stops until the host clears it
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub start: u32,
+    pub end: u32, // Inclusive
+    pub attribute: Attribute,
+    pub policy: ViolationPolicy,
+}
+
+/// A write or opcode fetch a region did not allow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Violation {
+    pub program_counter: u32, // The instruction that made it, bank byte included on the 65C816
+    pub address: u32,
+    pub attribute: Attribute,
+    pub data: Option<u8>, // What a blocked write would have stored, None for a fetch
+}
+
+/// What becomes of a write once the regions have seen it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Allow,
+    Drop,
+}
+
+/// Attributes the host has given ranges of the address space, later regions win where they overlap.
+/// Addresses are compared as the variant decodes them, so a 6507 region at $F000 covers $1000 too.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    pub regions: Vec<Region>,
+    pub log: VecDeque<Violation>, // The most recent logged violations, oldest first
+    pub log_limit: usize,
+    pub dropped: u64,       // Logged violations pushed out of a full log
+    written: BTreeSet<u32>, // Write-once addresses that have had their write
+    instruction: u32,       // Where the instruction running now started
+    pending: Option<Violation>,
+}
+
+impl Regions {
+    pub fn new() -> Regions {
+        return Regions {
+            regions: Vec::new(),
+            log: VecDeque::new(),
+            log_limit: LOG_LIMIT,
+            dropped: 0,
+            written: BTreeSet::new(),
+            instruction: 0,
+            pending: None,
+        };
+    }
+
+    pub fn mark(
+        &mut self,
+        start: u32,
+        end: u32,
+        attribute: Attribute,
+        policy: ViolationPolicy,
+    ) -> () {
+        self.regions.push(Region {
+            start,
+            end,
+            attribute,
+            policy,
+        });
+    }
+
+    pub fn find(&self, address: u32, mask: u32) -> Option<Region> {
+        let address: u32 = address & mask;
+        return self
+            .regions
+            .iter()
+            .rev()
+            .find(|region| address >= region.start & mask && address <= region.end & mask)
+            .copied();
+    }
+
+    pub fn passes(&self) -> bool {
+        /* Whether nothing is marked, any region means every write and fetch has to be looked at */
+        return self.regions.is_empty();
+    }
+
+    fn violated(&mut self, region: Region, address: u32, data: Option<u8>) -> () {
+        let violation = Violation {
+            program_counter: self.instruction,
+            address,
+            attribute: region.attribute,
+            data,
+        };
+        match region.policy {
+            ViolationPolicy::Ignore => {}
+            ViolationPolicy::Log => {
+                self.log.push_back(violation);
+                while self.log.len() > self.log_limit {
+                    self.log.pop_front();
+                    self.dropped += 1;
+                }
+            }
+            ViolationPolicy::Stop => {
+                // Only the first of an instruction is kept, like a watchpoint hit
+                if self.pending.is_none() {
+                    self.pending = Some(violation);
+                }
+            }
+        }
+    }
+
+    pub fn write(&mut self, address: u32, data: u8, mask: u32) -> Verdict {
+        let address: u32 = address & mask;
+        let region: Region = match self.find(address, mask) {
+            Some(region) => region,
+            None => return Verdict::Allow,
+        };
+
+        let allowed: bool = match region.attribute {
+            Attribute::Ram | Attribute::NoExecute => true,
+            Attribute::Rom => false,
+            Attribute::WriteOnce => self.written.insert(address),
+        };
+        if allowed {
+            return Verdict::Allow;
+        }
+        self.violated(region, address, Some(data));
+        return Verdict::Drop;
+    }
+
+    pub fn fetch(&mut self, address: u32, mask: u32) -> () {
+        /* Every opcode fetch that reaches the bus, a fetch that stops was already refused by check() */
+        let address: u32 = address & mask;
+        self.instruction = address;
+        match self.find(address, mask) {
+            Some(region)
+                if region.attribute == Attribute::NoExecute
+                    && region.policy != ViolationPolicy::Stop =>
+            {
+                self.violated(region, address, None);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn check(&mut self, address: u32, mask: u32) -> Option<Violation> {
+        /*
+            Looked at before every instruction, the instruction at the address has not run yet.
+            A stopping write from the last instruction comes first, then a stopping fetch from the address.
+        */
+        if let Some(violation) = self.pending.take() {
+            return Some(violation);
+        }
+
+        let address: u32 = address & mask;
+        match self.find(address, mask) {
+            Some(region)
+                if region.attribute == Attribute::NoExecute
+                    && region.policy == ViolationPolicy::Stop =>
+            {
+                return Some(Violation {
+                    program_counter: address,
+                    address,
+                    attribute: region.attribute,
+                    data: None,
+                });
+            }
+            _ => return None,
+        }
+    }
+}
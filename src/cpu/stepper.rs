@@ -0,0 +1,299 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::decode::execute_instruction;
+use cpu::functions::byte::ByteFunctions;
+use cpu::instructions::system::System;
+use cpu::interrupts::InterruptPoll;
+use cpu::native::registers::NativeRegisters;
+use cpu::observer::*;
+use cpu::processor::*;
+
+/// The register file as it stood when the instruction in flight was fetched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    program_counter: u16,
+    stack_pointer: u8,
+    accumulator: u8,
+    register_x: u8,
+    register_y: u8,
+    status: u8,
+    native: NativeRegisters,
+    fault: Option<Fault>,
+    state: ExecutionState,
+    poll: InterruptPoll,
+}
+
+impl Snapshot {
+    pub fn capture(processor: &Processor) -> Snapshot {
+        return Snapshot {
+            program_counter: processor.program_counter,
+            stack_pointer: processor.stack_pointer,
+            accumulator: processor.accumulator,
+            register_x: processor.register_x,
+            register_y: processor.register_y,
+            status: processor.status,
+            native: processor.native,
+            fault: processor.fault,
+            state: processor.state,
+            poll: processor.poll,
+        };
+    }
+
+    pub fn restore(&self, processor: &mut Processor) -> () {
+        processor.program_counter = self.program_counter;
+        processor.stack_pointer = self.stack_pointer;
+        processor.accumulator = self.accumulator;
+        processor.register_x = self.register_x;
+        processor.register_y = self.register_y;
+        processor.status = self.status;
+        processor.native = self.native;
+        processor.fault = self.fault;
+        processor.state = self.state;
+        processor.poll = self.poll;
+    }
+}
+
+/// How the bus answers the cycle about to run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CyclePhase {
+    Direct,            // Whole instructions through execute(), every access is real
+    Replay(BusAccess), // Already happened on an earlier tick, answered from the log
+    Live,              // The cycle the current tick is for
+    Discard,           // Past the current tick, nothing reaches the bus
+}
+
+/// Bookkeeping for tick(), which replays the instruction in flight one cycle further on every call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleStepper {
+    snapshot: Option<Snapshot>,
+    interrupt: Option<bool>, // Set when the instruction in flight is an interrupt sequence, true for NMI
+    completed: Vec<BusAccess>, // Cycles of the instruction in flight that are already on the bus
+    sample: Option<(bool, bool)>, // NMI latch and IRQ line as the last poll cycle saw them
+    hijacked: bool,          // An NMI took over the vector of the BRK or IRQ sequence in flight
+    stalled: Option<BusAccess>, // The read RDY is holding, the live cycle never reached the bus
+    live: usize,
+    position: usize,
+    stepping: bool,
+    elapsed: u64,
+}
+
+impl CycleStepper {
+    pub fn new() -> CycleStepper {
+        return CycleStepper {
+            snapshot: None,
+            interrupt: None,
+            completed: Vec::new(),
+            sample: None,
+            hijacked: false,
+            stalled: None,
+            live: 0,
+            position: 0,
+            stepping: false,
+            elapsed: 0,
+        };
+    }
+
+    pub fn mid_instruction(&self) -> bool {
+        return self.snapshot.is_some();
+    }
+
+    pub fn elapsed(&self) -> u64 {
+        /* Clock cycles run through tick() since the processor was created */
+        return self.elapsed;
+    }
+
+    pub fn abandon(&mut self) -> () {
+        self.complete();
+        self.sample = None;
+    }
+
+    fn complete(&mut self) -> () {
+        /* The instruction in flight is over, the poll it made carries over to the next boundary */
+        self.snapshot = None;
+        self.interrupt = None;
+        self.hijacked = false;
+        self.stalled = None;
+        self.completed.clear();
+    }
+
+    pub fn take_sample(&mut self) -> Option<(bool, bool)> {
+        return self.sample.take();
+    }
+
+    pub fn hijacked(&self) -> bool {
+        return self.hijacked;
+    }
+
+    pub fn record_hijack(&mut self, hijacked: bool) -> () {
+        self.hijacked = hijacked;
+    }
+
+    pub fn stall(&mut self, access: BusAccess) -> () {
+        /* RDY is low on a read, the cycle is repeated on the next tick and everything after it waits */
+        self.stalled = Some(access);
+    }
+
+    pub fn phase(&self) -> CyclePhase {
+        if self.stepping == false {
+            return CyclePhase::Direct;
+        }
+
+        if self.stalled.is_some() && self.position >= self.live {
+            return CyclePhase::Discard;
+        }
+
+        if self.position < self.live {
+            return CyclePhase::Replay(self.completed[self.position]);
+        } else if self.position == self.live {
+            return CyclePhase::Live;
+        }
+        return CyclePhase::Discard;
+    }
+
+    pub fn record(&mut self, access: BusAccess) -> () {
+        if self.phase() == CyclePhase::Live && self.completed.len() == self.live {
+            self.completed.push(access);
+        }
+    }
+
+    pub fn advance(&mut self, amount: u32, address: u32, ready: bool) -> u32 {
+        /*
+            Moves past cycles that are over, returning how many of them really happened.
+            An idle cycle that is the live one gets logged as internal so tick() has something to report.
+            Idle cycles are dummy reads as far as RDY is concerned, so they stall like any other read.
+        */
+        if self.stepping == false {
+            return amount;
+        }
+
+        let end: usize = self.position + amount as usize;
+        let live_within: bool = self.position <= self.live && self.live < end;
+        if live_within && self.stalled.is_none() && self.completed.len() == self.live {
+            let access: BusAccess = BusAccess {
+                address,
+                data: 0x00,
+                operation: BusOperation::Internal,
+                sync: false,
+            };
+            match ready {
+                true => self.completed.push(access),
+                false => self.stall(access),
+            }
+        }
+
+        self.position = end;
+        return live_within as u32;
+    }
+}
+
+pub trait Stepping {
+    fn tick<B: Bus>(&mut self, memory: &mut B) -> BusAccess;
+    fn finish_instruction<B: Bus>(&mut self, memory: &mut B) -> u32;
+}
+
+fn idle_cycle(processor: &mut Processor) -> BusAccess {
+    processor.stepper.elapsed += 1;
+    processor.clock += 1;
+    return BusAccess {
+        address: ((processor.native.program_bank as u32) << 16) | processor.program_counter as u32,
+        data: 0x00,
+        operation: BusOperation::Internal,
+        sync: false,
+    };
+}
+
+impl Stepping for Processor {
+    fn tick<B: Bus>(&mut self, memory: &mut B) -> BusAccess {
+        /*
+            Advances exactly one clock cycle. The instruction in flight is replayed from its opcode
+            fetch with the cycles already done answered from the log, the next cycle goes out on the
+            real bus, and the rest runs against registers that are put back afterwards.
+            Registers only show the result of an instruction once its final cycle has ticked.
+        */
+        if self.stepper.mid_instruction() == false {
+            if let Some(Fault::Jammed { .. }) = self.fault {
+                return idle_cycle(self);
+            }
+
+            self.sync_bus(memory);
+            self.wake_on_interrupt();
+
+            let address: u32 =
+                ((self.native.program_bank as u32) << 16) | self.program_counter as u32;
+            if self.fault.is_none() && self.state == ExecutionState::Running {
+                if let Some(violation) = self.regions.check(address, self.variant.address_mask()) {
+                    self.fault = Some(Fault::AccessViolation { violation });
+                }
+            }
+
+            if self.state != ExecutionState::Running || self.fault.is_some() {
+                return idle_cycle(self);
+            }
+
+            self.apply_set_overflow();
+
+            // The interrupt is decided once per instruction, the replays must not see the lines change
+            self.stepper.interrupt = self.pending_interrupt();
+            self.stepper.snapshot = Some(Snapshot::capture(self));
+        }
+
+        let snapshot: Snapshot = self.stepper.snapshot.unwrap();
+        snapshot.restore(self);
+
+        let clock: u64 = self.clock;
+        self.stepper.live = self.stepper.completed.len();
+        self.stepper.position = 0;
+        self.stepper.stepping = true;
+
+        match self.stepper.interrupt {
+            Some(non_maskable) => self.service_interrupt(memory, non_maskable),
+            None => {
+                let instruction: u8 = self.fetch_opcode(memory);
+                execute_instruction(self, memory, instruction);
+            }
+        }
+
+        self.stepper.stepping = false;
+        self.clock = clock;
+        self.stepper.elapsed += 1;
+
+        if let Some(held) = self.stepper.stalled.take() {
+            // The clock ran but the instruction did not move, whoever holds RDY has the cycle
+            self.stolen_cycles += 1;
+            snapshot.restore(self);
+            return held;
+        }
+        self.clock += 1;
+
+        // The lines are polled on the second to last cycle, a taken branch that stays on its page polls one earlier
+        let lead: usize = if self.poll.branch_delayed { 3 } else { 2 };
+        if self.stepper.live == self.stepper.position.saturating_sub(lead) {
+            self.stepper.sample = Some((
+                self.interrupts.nmi_pending(),
+                self.interrupts.irq_asserted(),
+            ));
+        }
+
+        let access: BusAccess = self.stepper.completed[self.stepper.live];
+        if self.stepper.live + 1 >= self.stepper.position {
+            self.stepper.complete();
+        } else {
+            snapshot.restore(self);
+        }
+        return access;
+    }
+
+    fn finish_instruction<B: Bus>(&mut self, memory: &mut B) -> u32 {
+        /*
+            Ticks until the instruction in flight is done, so whole-instruction execution can take over.
+            Gives up while RDY is low, the instruction then stays in flight.
+        */
+        let mut cycles: u32 = 0;
+        while self.stepper.mid_instruction() && self.control.ready() {
+            self.tick(memory);
+            cycles += 1;
+        }
+        return cycles;
+    }
+}
@@ -0,0 +1,113 @@
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::debugger::BankedAddress;
+use cpu::disassembler::{disassemble, Disassembly};
+use cpu::processor::Processor;
+use std::fmt;
+
+/// One executed instruction with the registers as they stood before it ran.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord {
+    pub disassembly: Disassembly,
+    pub bank: Option<u16>, // Switched in under the opcode, where the bus banks it
+    pub accumulator: u8,
+    pub register_x: u8,
+    pub register_y: u8,
+    pub status: u8,
+    pub stack_pointer: u8,
+    pub cycles: u32, // Spent by this instruction alone
+}
+
+impl TraceRecord {
+    pub fn capture<B: Bus>(processor: &Processor, memory: &B, address: u32) -> TraceRecord {
+        return TraceRecord {
+            disassembly: disassemble(processor, memory, address),
+            bank: memory.bank(address & processor.variant.address_mask()),
+            accumulator: processor.accumulator,
+            register_x: processor.register_x,
+            register_y: processor.register_y,
+            status: processor.status,
+            stack_pointer: processor.stack_pointer,
+            cycles: 0,
+        };
+    }
+}
+
+impl fmt::Display for TraceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /* PC, raw bytes, assembly, registers and cycles, in columns so a log lines up */
+        let disassembly: &Disassembly = &self.disassembly;
+        let bytes: Vec<String> = disassembly
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        let address: String = match (self.bank, disassembly.address > 0xFFFF) {
+            (Some(bank), _) => BankedAddress {
+                bank,
+                address: disassembly.address,
+            }
+            .to_string(),
+            (None, true) => format!("{:06X}", disassembly.address),
+            (None, false) => format!("{:04X}", disassembly.address),
+        };
+
+        return write!(
+            f,
+            "{}  {:<11} {:<15} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+            address,
+            bytes.join(" "),
+            disassembly.to_string(),
+            self.accumulator,
+            self.register_x,
+            self.register_y,
+            self.status,
+            self.stack_pointer,
+            self.cycles
+        );
+    }
+}
+
+/// Host callback that sees every instruction execute() runs, optionally only inside an address range.
+pub struct Tracer {
+    callback: Box<dyn FnMut(&TraceRecord)>,
+    range: Option<(u32, u32)>, // Inclusive, compared against the address of the opcode
+}
+
+impl fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracer")
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+impl Tracer {
+    pub fn new<F: FnMut(&TraceRecord) + 'static>(callback: F) -> Tracer {
+        return Tracer {
+            callback: Box::new(callback),
+            range: None,
+        };
+    }
+
+    pub fn stdout() -> Tracer {
+        // One disassembled line per instruction
+        return Tracer::new(|record| println!("{}", record));
+    }
+
+    pub fn restrict(&mut self, start: u32, end: u32) -> () {
+        self.range = Some((start, end));
+    }
+
+    pub fn traces(&self, address: u32) -> bool {
+        match self.range {
+            Some((start, end)) => return address >= start && address <= end,
+            None => return true,
+        }
+    }
+
+    pub fn record(&mut self, record: &TraceRecord) -> () {
+        (self.callback)(record);
+    }
+}
@@ -0,0 +1,305 @@
+/// Members of the 6502 family that the processor is able to model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    Nmos6502,
+    Nmos6502RevA,
+    Mos6507,
+    Mos6510,
+    Ricoh2A03,
+    Rockwell65C02,
+    Wdc65C02,
+    Wdc65C816,
+}
+
+/// Which decoder a variant hands its opcodes to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstructionSet {
+    Nmos,
+    Cmos,
+    Wdc65C816,
+}
+
+/// How a variant treats ADC and SBC while the decimal flag is set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecimalArithmetic {
+    Nmos,
+    Cmos,
+    Ignored,
+}
+
+/// Behaviour that differs between variants sharing an instruction set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quirk {
+    IndirectJumpPageWrap, // JMP ($xxFF) never carries into the high byte of the pointer
+    IndirectJumpExtraCycle, // The 65C02 spends a cycle fixing up the pointer, the 65C816 does not
+    DecimalExtraCycle,
+    BreakClearsDecimal, // CMOS parts clear D on BRK and on hardware interrupts alike
+    MissingRotateRight, // Pre-1976 masks decode ROR as a shift left that leaves carry alone
+    HalfCarriedIndexRead, // NMOS indexing reads the target before the carry reaches its high byte
+    ModifyWritesTwice,  // NMOS read-modify-write stores the unmodified value before the result
+}
+
+/// Cycles an opcode can spend on top of its base count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Penalty {
+    None,
+    PageCross, // One more when indexing, or BRA, carries into another page
+    Branch,    // One more when taken and another when the target is on a different page
+}
+
+/// Cycle rule for a single opcode, read from the tables below.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    pub base: u8,
+    pub penalty: Penalty,
+}
+
+impl Timing {
+    pub fn cycles(&self, page_crossed: bool, branch_taken: bool) -> u8 {
+        match self.penalty {
+            Penalty::None => return self.base,
+            Penalty::PageCross => return self.base + page_crossed as u8,
+            Penalty::Branch => {
+                if branch_taken == false {
+                    return self.base;
+                }
+                return self.base + 1 + page_crossed as u8;
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+const NMOS_CYCLES: [u8; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    7, 6, 0, 8, 3, 3, 5, 5, 3, 2, 2, 2, 4, 4, 6, 6, // 0
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 1
+    6, 6, 0, 8, 3, 3, 5, 5, 4, 2, 2, 2, 4, 4, 6, 6, // 2
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 3
+    6, 6, 0, 8, 3, 3, 5, 5, 3, 2, 2, 2, 3, 4, 6, 6, // 4
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 5
+    6, 6, 0, 8, 3, 3, 5, 5, 4, 2, 2, 2, 5, 4, 6, 6, // 6
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 7
+    2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4, // 8
+    2, 6, 0, 6, 4, 4, 4, 4, 2, 5, 2, 5, 5, 5, 5, 5, // 9
+    2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4, // A
+    2, 5, 0, 5, 4, 4, 4, 4, 2, 4, 2, 4, 4, 4, 4, 4, // B
+    2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6, // C
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // D
+    2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6, // E
+    2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // F
+];
+
+#[rustfmt::skip]
+const CMOS_CYCLES: [u8; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    7, 6, 2, 1, 5, 3, 5, 5, 3, 2, 2, 1, 6, 4, 6, 5, // 0
+    2, 5, 5, 1, 5, 4, 6, 5, 2, 4, 2, 1, 6, 4, 6, 5, // 1
+    6, 6, 2, 1, 3, 3, 5, 5, 4, 2, 2, 1, 4, 4, 6, 5, // 2
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 2, 1, 4, 4, 6, 5, // 3
+    6, 6, 2, 1, 3, 3, 5, 5, 3, 2, 2, 1, 3, 4, 6, 5, // 4
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 3, 1, 8, 4, 6, 5, // 5
+    6, 6, 2, 1, 3, 3, 5, 5, 4, 2, 2, 1, 6, 4, 6, 5, // 6
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 4, 1, 6, 4, 6, 5, // 7
+    3, 6, 2, 1, 3, 3, 3, 5, 2, 2, 2, 1, 4, 4, 4, 5, // 8
+    2, 6, 5, 1, 4, 4, 4, 5, 2, 5, 2, 1, 4, 5, 5, 5, // 9
+    2, 6, 2, 1, 3, 3, 3, 5, 2, 2, 2, 1, 4, 4, 4, 5, // A
+    2, 5, 5, 1, 4, 4, 4, 5, 2, 4, 2, 1, 4, 4, 4, 5, // B
+    2, 6, 2, 1, 3, 3, 5, 5, 2, 2, 2, 3, 4, 4, 6, 5, // C
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 3, 3, 4, 4, 7, 5, // D
+    2, 6, 2, 1, 3, 3, 5, 5, 2, 2, 2, 1, 4, 4, 6, 5, // E
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 4, 1, 4, 4, 7, 5, // F
+];
+
+#[rustfmt::skip]
+const W65C816_CYCLES: [u8; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    7, 6, 7, 4, 5, 3, 5, 6, 3, 2, 2, 4, 6, 4, 6, 5, // 0
+    2, 5, 5, 7, 5, 4, 6, 6, 2, 4, 2, 2, 6, 4, 7, 5, // 1
+    6, 6, 8, 4, 3, 3, 5, 6, 4, 2, 2, 5, 4, 4, 6, 5, // 2
+    2, 5, 5, 7, 4, 4, 6, 6, 2, 4, 2, 2, 4, 4, 7, 5, // 3
+    6, 6, 2, 4, 7, 3, 5, 6, 3, 2, 2, 3, 3, 4, 6, 5, // 4
+    2, 5, 5, 7, 7, 4, 6, 6, 2, 4, 3, 2, 4, 4, 7, 5, // 5
+    6, 6, 6, 4, 3, 3, 5, 6, 4, 2, 2, 6, 5, 4, 6, 5, // 6
+    2, 5, 5, 7, 4, 4, 6, 6, 2, 4, 4, 2, 6, 4, 7, 5, // 7
+    3, 6, 4, 4, 3, 3, 3, 6, 2, 2, 2, 3, 4, 4, 4, 5, // 8
+    2, 6, 5, 7, 4, 4, 4, 6, 2, 5, 2, 2, 4, 5, 5, 5, // 9
+    2, 6, 2, 4, 3, 3, 3, 6, 2, 2, 2, 4, 4, 4, 4, 5, // A
+    2, 5, 5, 7, 4, 4, 4, 6, 2, 4, 2, 2, 4, 4, 4, 5, // B
+    2, 6, 3, 4, 3, 3, 5, 6, 2, 2, 2, 3, 4, 4, 6, 5, // C
+    2, 5, 5, 7, 6, 4, 6, 6, 2, 4, 3, 3, 6, 4, 7, 5, // D
+    2, 6, 3, 4, 3, 3, 5, 6, 2, 2, 2, 3, 4, 4, 6, 5, // E
+    2, 5, 5, 7, 5, 4, 6, 6, 2, 4, 4, 2, 8, 4, 7, 5, // F
+];
+
+// Short names keep the penalty tables lined up with the cycle tables above
+const N: Penalty = Penalty::None;
+const P: Penalty = Penalty::PageCross;
+const B: Penalty = Penalty::Branch;
+
+#[rustfmt::skip]
+const NMOS_PENALTIES: [Penalty; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 0
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // 1
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 2
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // 3
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 4
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // 5
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 6
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // 7
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 8
+    B, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 9
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // A
+    B, P, N, P, N, N, N, N, N, P, N, P, P, P, P, P, // B
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // C
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // D
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // E
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // F
+];
+
+#[rustfmt::skip]
+const CMOS_PENALTIES: [Penalty; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 0
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, P, B, // 1
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 2
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, P, B, // 3
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 4
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, P, B, // 5
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 6
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, P, B, // 7
+    P, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 8
+    B, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // 9
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // A
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, P, B, // B
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // C
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, B, // D
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, B, // E
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, B, // F
+];
+
+#[rustfmt::skip]
+const W65C816_PENALTIES: [Penalty; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 0
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, N, // 1
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 2
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, N, N, // 3
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 4
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, N, // 5
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 6
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, N, // 7
+    P, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 8
+    B, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // 9
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // A
+    B, P, N, N, N, N, N, N, N, P, N, N, P, P, P, N, // B
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // C
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, N, // D
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, // E
+    B, P, N, N, N, N, N, N, N, P, N, N, N, P, N, N, // F
+];
+
+impl Variant {
+    pub fn instruction_set(&self) -> InstructionSet {
+        match self {
+            Variant::Rockwell65C02 | Variant::Wdc65C02 => InstructionSet::Cmos,
+            Variant::Wdc65C816 => InstructionSet::Wdc65C816,
+            _ => InstructionSet::Nmos,
+        }
+    }
+
+    pub fn decimal_arithmetic(&self) -> DecimalArithmetic {
+        match self {
+            Variant::Nmos6502 | Variant::Nmos6502RevA | Variant::Mos6507 | Variant::Mos6510 => {
+                DecimalArithmetic::Nmos
+            }
+            Variant::Ricoh2A03 => DecimalArithmetic::Ignored, // The decimal flag can still be set, but the adder has no BCD logic
+            Variant::Rockwell65C02 | Variant::Wdc65C02 | Variant::Wdc65C816 => {
+                DecimalArithmetic::Cmos
+            }
+        }
+    }
+
+    pub fn has_quirk(&self, quirk: Quirk) -> bool {
+        match quirk {
+            Quirk::IndirectJumpPageWrap => return self.instruction_set() == InstructionSet::Nmos,
+            Quirk::IndirectJumpExtraCycle => return self.instruction_set() == InstructionSet::Cmos,
+            Quirk::DecimalExtraCycle => return self.instruction_set() == InstructionSet::Cmos,
+            Quirk::BreakClearsDecimal => return self.instruction_set() != InstructionSet::Nmos,
+            Quirk::MissingRotateRight => return *self == Variant::Nmos6502RevA,
+            Quirk::HalfCarriedIndexRead => return self.instruction_set() == InstructionSet::Nmos,
+            Quirk::ModifyWritesTwice => return self.instruction_set() == InstructionSet::Nmos,
+        }
+    }
+
+    pub fn has_bit_instructions(&self) -> bool {
+        // RMB, SMB, BBR and BBS originated with Rockwell and were adopted by WDC
+        return self.instruction_set() == InstructionSet::Cmos;
+    }
+
+    pub fn has_wait_and_stop(&self) -> bool {
+        return *self == Variant::Wdc65C02 || *self == Variant::Wdc65C816;
+    }
+
+    pub fn has_io_port(&self) -> bool {
+        return *self == Variant::Mos6510;
+    }
+
+    pub fn address_mask(&self) -> u32 {
+        match self {
+            Variant::Mos6507 => return 0x1FFF, // Only A0 to A12 are bonded out, so memory mirrors every 8 KB
+            Variant::Wdc65C816 => return 0xFF_FFFF,
+            _ => return 0xFFFF,
+        }
+    }
+
+    pub fn has_interrupt_lines(&self) -> bool {
+        // The 6507 package has no IRQ or NMI pins, BRK still works
+        return *self != Variant::Mos6507;
+    }
+
+    pub fn has_set_overflow_pin(&self) -> bool {
+        // The 6507, 6510 and 2A03 gave the pin up for other signals, the 65C816 dropped it
+        match self {
+            Variant::Nmos6502 | Variant::Nmos6502RevA => return true,
+            Variant::Rockwell65C02 | Variant::Wdc65C02 => return true,
+            _ => return false,
+        }
+    }
+
+    pub fn cycles(&self, opcode: u8) -> u8 {
+        /*
+            Base cycle count of an opcode, before page crossings, taken branches and the
+            CMOS decimal penalty are added. JAM opcodes never finish and are listed as 0.
+            65C816 timings are for emulation mode, native mode adds a cycle per extra byte moved.
+        */
+        match self.instruction_set() {
+            InstructionSet::Nmos => return NMOS_CYCLES[opcode as usize],
+            InstructionSet::Cmos => {
+                if (opcode == 0xCB || opcode == 0xDB) && self.has_wait_and_stop() == false {
+                    return 1;
+                }
+                return CMOS_CYCLES[opcode as usize];
+            }
+            InstructionSet::Wdc65C816 => return W65C816_CYCLES[opcode as usize],
+        }
+    }
+
+    pub fn timing(&self, opcode: u8) -> Timing {
+        /*
+            The authoritative cycle rule for an opcode. Reads indexed across a page, BRA across a
+            page and the 65C02 shifts on abs,X pay one more, stores and the other read-modify-writes
+            never do. A native 65C816 skips the branch page penalty.
+        */
+        let penalty: Penalty = match self.instruction_set() {
+            InstructionSet::Nmos => NMOS_PENALTIES[opcode as usize],
+            InstructionSet::Cmos => CMOS_PENALTIES[opcode as usize],
+            InstructionSet::Wdc65C816 => W65C816_PENALTIES[opcode as usize],
+        };
+        return Timing {
+            base: self.cycles(opcode),
+            penalty,
+        };
+    }
+}
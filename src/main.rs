@@ -1,21 +1,36 @@
 // obelisk.me.uk/6502
+mod banking;
+mod bench;
 mod cpu;
+mod map;
 mod mem;
+mod programs;
+mod recompiler;
 mod tests;
 
 use mem::*;
+use std::env;
 use std::io;
 
 fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    if arguments.first().map(|command| command.as_str()) == Some("recompile") {
+        recompiler::run(&arguments[1..]);
+        return;
+    }
+
     println!("Please enter a key depending on which you wish to run: ");
     println!("1) 6502 Test Suite");
     println!("2) 6502 Example Programs");
+    println!("3) Benchmark");
     let mut stdin_buffer = String::new();
     match io::stdin().read_line(&mut stdin_buffer) {
         Ok(_n) => {
             println!("\n\n");
             if stdin_buffer.contains("1") {
                 tests::main::run();
+            } else if stdin_buffer.contains("3") {
+                bench::run();
             } else {
                 tests::main::run_programs();
             }
@@ -0,0 +1,342 @@
+use crate::mem::*;
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+pub type DeviceId = usize;
+
+/// Something on the bus that answers for a range of addresses. Addresses arrive as offsets into
+/// the mapping, after its mirroring mask.
+pub trait Device {
+    fn read(&mut self, offset: u32) -> u8;
+    fn write(&mut self, offset: u32, data: u8) -> ();
+
+    fn peek(&self, offset: u32) -> u8 {
+        /* A look the device does not notice, registers with read side effects should override it. Floats high unless overridden */
+        let _ = offset;
+        return 0xFF;
+    }
+
+    fn peek_is_authoritative(&self, offset: u32) -> bool {
+        // Whether peek() shows what a read of the offset would return, false unless overridden
+        let _ = offset;
+        return false;
+    }
+
+    fn poke(&mut self, offset: u32, data: u8) -> () {
+        // Loaders use this to set the device up, one with write side effects should bypass them here
+        self.write(offset, data);
+    }
+
+    fn tick(&mut self, cycles: u32) -> () {
+        // Called with the cycles since the last call, before each bus cycle and at instruction boundaries
+        let _ = cycles;
+    }
+
+    fn irq(&self) -> bool {
+        // Wired-OR with every other device, the processor sees IRQ low while any of them holds it
+        return false;
+    }
+
+    fn bank(&self, offset: u32) -> Option<u16> {
+        // Which bank answers for the offset right now, for devices that switch what they show
+        let _ = offset;
+        return None;
+    }
+
+    fn floating(&self, offset: u32) -> Option<Decay> {
+        // Offsets the device leaves undriven, a register file with holes or a chip smaller than its mapping
+        let _ = offset;
+        return None;
+    }
+}
+
+/// Lets the host keep a handle on a device after handing it to the map.
+impl<D: Device> Device for Rc<RefCell<D>> {
+    fn read(&mut self, offset: u32) -> u8 {
+        return self.borrow_mut().read(offset);
+    }
+
+    fn write(&mut self, offset: u32, data: u8) -> () {
+        self.borrow_mut().write(offset, data);
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        return self.borrow().peek(offset);
+    }
+
+    fn peek_is_authoritative(&self, offset: u32) -> bool {
+        return self.borrow().peek_is_authoritative(offset);
+    }
+
+    fn poke(&mut self, offset: u32, data: u8) -> () {
+        self.borrow_mut().poke(offset, data);
+    }
+
+    fn tick(&mut self, cycles: u32) -> () {
+        self.borrow_mut().tick(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        return self.borrow().irq();
+    }
+
+    fn bank(&self, offset: u32) -> Option<u16> {
+        return self.borrow().bank(offset);
+    }
+
+    fn floating(&self, offset: u32) -> Option<Decay> {
+        return self.borrow().floating(offset);
+    }
+}
+
+/// Plain RAM as a device, for memory smaller than the range it is mapped over.
+#[derive(Debug, Clone)]
+pub struct Ram {
+    pub data: Vec<u8>,
+}
+
+impl Ram {
+    pub fn new(size: usize) -> Ram {
+        return Ram {
+            data: vec![0; size],
+        };
+    }
+}
+
+impl Device for Ram {
+    fn read(&mut self, offset: u32) -> u8 {
+        return self.peek(offset);
+    }
+
+    fn write(&mut self, offset: u32, data: u8) -> () {
+        if let Some(byte) = self.data.get_mut(offset as usize) {
+            *byte = data;
+        }
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        return *self.data.get(offset as usize).unwrap_or(&0xFF);
+    }
+
+    fn peek_is_authoritative(&self, offset: u32) -> bool {
+        return (offset as usize) < self.data.len();
+    }
+
+    fn floating(&self, offset: u32) -> Option<Decay> {
+        // Past the end of the chip nothing drives the bus
+        match offset as usize >= self.data.len() {
+            true => return Some(Decay::Hold),
+            false => return None,
+        }
+    }
+}
+
+/// A range where nothing answers, reads see what was last on the data bus decaying as configured.
+#[derive(Debug, Clone, Copy)]
+pub struct OpenBus {
+    pub decay: Decay,
+}
+
+impl OpenBus {
+    pub fn new(decay: Decay) -> OpenBus {
+        return OpenBus { decay };
+    }
+}
+
+impl Device for OpenBus {
+    fn read(&mut self, _offset: u32) -> u8 {
+        // Only reached by a bus that ignores floating(), the processor never reads it
+        return 0xFF;
+    }
+
+    fn write(&mut self, _offset: u32, _data: u8) -> () {}
+
+    fn floating(&self, _offset: u32) -> Option<Decay> {
+        return Some(self.decay);
+    }
+}
+
+/// A range of addresses handed to a device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mapping {
+    pub start: u32,
+    pub end: u32,  // Last address in the range, inclusive
+    pub mask: u32, // Applied to the offset from start, so a small device repeats over a larger range
+    pub device: DeviceId,
+}
+
+impl Mapping {
+    pub fn contains(&self, address: u32) -> bool {
+        return address >= self.start && address <= self.end;
+    }
+
+    pub fn offset(&self, address: u32) -> u32 {
+        return (address - self.start) & self.mask;
+    }
+}
+
+/// Devices mapped over address ranges, with flat RAM answering wherever no mapping does.
+/// Later mappings are laid over earlier ones where they overlap.
+pub struct MemoryMap {
+    pub ram: Memory,
+    pub unmapped: Option<Decay>, // Set to leave the bus floating instead where nothing is mapped
+    devices: Vec<Box<dyn Device>>,
+    mappings: Vec<Mapping>,
+    pages: Vec<Vec<usize>>, // The mappings touching each page of bank 0, most recent first
+}
+
+impl fmt::Debug for MemoryMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryMap")
+            .field("unmapped", &self.unmapped)
+            .field("devices", &self.devices.len())
+            .field("mappings", &self.mappings)
+            .finish()
+    }
+}
+
+impl MemoryMap {
+    pub fn new() -> MemoryMap {
+        return MemoryMap {
+            ram: Memory::new(),
+            unmapped: None,
+            devices: Vec::new(),
+            mappings: Vec::new(),
+            pages: vec![Vec::new(); 0x100],
+        };
+    }
+
+    pub fn add<D: Device + 'static>(&mut self, device: D) -> DeviceId {
+        /* Adding a device maps nothing, the same device can then be mapped over several ranges */
+        self.devices.push(Box::new(device));
+        return self.devices.len() - 1;
+    }
+
+    pub fn map(&mut self, start: u32, end: u32, mask: u32, device: DeviceId) -> () {
+        assert!(device < self.devices.len(), "no device {}", device);
+        assert!(start <= end, "mapping ends before it starts");
+
+        let mapping = Mapping {
+            start,
+            end,
+            mask,
+            device,
+        };
+        let index: usize = self.mappings.len();
+        self.mappings.push(mapping);
+
+        if start <= 0xFFFF {
+            for page in (start >> 8)..=(end.min(0xFFFF) >> 8) {
+                self.pages[page as usize].insert(0, index);
+            }
+        }
+    }
+
+    pub fn attach<D: Device + 'static>(&mut self, start: u32, end: u32, device: D) -> DeviceId {
+        /* Adds a device and maps it once over the range, with no mirroring */
+        let id: DeviceId = self.add(device);
+        self.map(start, end, u32::MAX, id);
+        return id;
+    }
+
+    pub fn mappings(&self) -> &[Mapping] {
+        return &self.mappings;
+    }
+
+    pub fn device(&mut self, id: DeviceId) -> &mut dyn Device {
+        return self.devices[id].as_mut();
+    }
+
+    fn find(&self, address: u32) -> Option<Mapping> {
+        /* Bank 0 goes through the page table, anything above it is rare enough to search */
+        if address <= 0xFFFF {
+            for index in self.pages[(address >> 8) as usize].iter() {
+                if self.mappings[*index].contains(address) {
+                    return Some(self.mappings[*index]);
+                }
+            }
+            return None;
+        }
+
+        return self
+            .mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.contains(address))
+            .copied();
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: u32) -> u8 {
+        match (self.find(address), self.unmapped) {
+            (Some(mapping), _) => {
+                return self.devices[mapping.device].read(mapping.offset(address))
+            }
+            (None, Some(_)) => return 0xFF,
+            (None, None) => return self.ram.read(address),
+        }
+    }
+
+    fn write(&mut self, address: u32, data: u8) -> () {
+        match (self.find(address), self.unmapped) {
+            (Some(mapping), _) => self.devices[mapping.device].write(mapping.offset(address), data),
+            (None, Some(_)) => {}
+            (None, None) => self.ram.write(address, data),
+        }
+    }
+
+    fn peek(&self, address: u32) -> u8 {
+        match (self.find(address), self.unmapped) {
+            (Some(mapping), _) => {
+                return self.devices[mapping.device].peek(mapping.offset(address))
+            }
+            (None, Some(_)) => return 0xFF,
+            (None, None) => return self.ram.peek(address),
+        }
+    }
+
+    fn peek_is_authoritative(&self, address: u32) -> bool {
+        match (self.find(address), self.unmapped) {
+            (Some(mapping), _) => {
+                return self.devices[mapping.device].peek_is_authoritative(mapping.offset(address))
+            }
+            (None, Some(_)) => return false,
+            (None, None) => return self.ram.peek_is_authoritative(address),
+        }
+    }
+
+    fn poke(&mut self, address: u32, data: u8) -> () {
+        match (self.find(address), self.unmapped) {
+            (Some(mapping), _) => self.devices[mapping.device].poke(mapping.offset(address), data),
+            (None, Some(_)) => {}
+            (None, None) => self.ram.poke(address, data),
+        }
+    }
+
+    fn tick(&mut self, cycles: u32) -> () {
+        for device in self.devices.iter_mut() {
+            device.tick(cycles);
+        }
+    }
+
+    fn irq(&self) -> bool {
+        return self.devices.iter().any(|device| device.irq());
+    }
+
+    fn bank(&self, address: u32) -> Option<u16> {
+        match self.find(address) {
+            Some(mapping) => return self.devices[mapping.device].bank(mapping.offset(address)),
+            None => return None,
+        }
+    }
+
+    fn floating(&self, address: u32) -> Option<Decay> {
+        match self.find(address) {
+            Some(mapping) => return self.devices[mapping.device].floating(mapping.offset(address)),
+            None => return self.unmapped,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub const MAX_MEMORY: usize = 1024 * 64; // u32
+pub const MAX_LONG_MEMORY: usize = 1024 * 1024 * 16; // 24 bit address space of the 65C816
 
 pub fn fetch_bit(value: u8, check_bit: u8) -> bool {
     return value & (1 << check_bit) != 0;
@@ -13,7 +14,167 @@ pub fn set_bit(mut value: u8, bit: u8, bit_value: bool) -> u8 {
     return value;
 }
 
+/// What RAM holds when power is first applied, real chips come up with anything but zeroes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RamPattern {
+    Zero,
+    Ones,
+    AlternatingPages, // $00 and $FF on alternate pages, the striped pattern C64 DRAM tends to settle into
+    Random(u64),      // Seeded so a run that trips over uninitialised RAM can be reproduced
+}
+
+/// How a data bus nobody is driving holds on to the last value put on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decay {
+    Hold,                             // For as long as a program could tell
+    After { cycles: u64, value: u8 }, // Reads as `value` once that many cycles pass without anything driving it
+}
+
+/// The value last driven onto the data pins and the cycle it was driven on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataBus {
+    pub value: u8,
+    pub driven: u64,
+}
+
+impl DataBus {
+    pub fn new() -> DataBus {
+        return DataBus {
+            value: 0xFF,
+            driven: 0,
+        };
+    }
+
+    pub fn drive(&mut self, value: u8, clock: u64) -> () {
+        self.value = value;
+        self.driven = clock;
+    }
+
+    pub fn float(&self, clock: u64, decay: Decay) -> u8 {
+        /* What a read nothing answers sees on the given cycle, reading it drives nothing */
+        match decay {
+            Decay::After { cycles, value } if clock.saturating_sub(self.driven) >= cycles => return value,
+            _ => return self.value,
+        }
+    }
+}
+
+/// Whatever sits on the far side of the address and data pins.
+pub trait Bus {
+    fn read(&mut self, address: u32) -> u8;
+    fn write(&mut self, address: u32, data: u8) -> ();
+
+    fn peek(&self, address: u32) -> u8 {
+        /* A look that no device notices, for debuggers and the cycles tick() throws away. Floats high unless overridden */
+        let _ = address;
+        return 0xFF;
+    }
+
+    fn peek_is_authoritative(&self, address: u32) -> bool {
+        // Whether peek() shows what a read of the address would return. Opcodes are only cached where it does
+        let _ = address;
+        return false;
+    }
+
+    fn poke(&mut self, address: u32, data: u8) -> () {
+        // Loaders and tests use this to set memory up, a bus with write side effects should bypass them here
+        self.write(address, data);
+    }
+
+    fn tick(&mut self, cycles: u32) -> () {
+        // The cycles since the last call, made before each bus cycle and at every instruction boundary
+        let _ = cycles;
+    }
+
+    fn irq(&self) -> bool {
+        // Whether anything on the bus is pulling IRQ low, looked at on every instruction boundary
+        return false;
+    }
+
+    fn bank(&self, address: u32) -> Option<u16> {
+        // The bank switched in at the address, None where nothing is banked. Only used to name addresses
+        let _ = address;
+        return None;
+    }
+
+    fn floating(&self, address: u32) -> Option<Decay> {
+        // Where nothing answers the address the processor reads back what is left on the data bus
+        let _ = address;
+        return None;
+    }
+}
+
+/// Flat RAM covering the whole address space, the bus the processor is used with by default.
 #[derive(Debug)]
 pub struct Memory {
-    pub data: [u8; MAX_MEMORY],
+    pub data: Box<[u8]>, // Bank 0, on the heap so the 64 KB is never copied on the stack
+    pub extended: Vec<u8>, // Banks 0x01 to 0xFF, only allocated once something is written above bank 0
+}
+
+impl Bus for Memory {
+    fn read(&mut self, address: u32) -> u8 {
+        return self.peek(address);
+    }
+
+    fn write(&mut self, address: u32, data: u8) -> () {
+        self.poke(address, data);
+    }
+
+    fn peek(&self, address: u32) -> u8 {
+        let address: usize = address as usize;
+        if address < MAX_MEMORY {
+            return self.data[address];
+        }
+
+        return *self.extended.get(address - MAX_MEMORY).unwrap_or(&0);
+    }
+
+    fn peek_is_authoritative(&self, _address: u32) -> bool {
+        return true;
+    }
+
+    fn poke(&mut self, address: u32, data: u8) -> () {
+        let address: usize = address as usize;
+        if address < MAX_MEMORY {
+            self.data[address] = data;
+            return;
+        }
+
+        if self.extended.is_empty() {
+            self.extended = vec![0; MAX_LONG_MEMORY - MAX_MEMORY];
+        }
+        self.extended[address - MAX_MEMORY] = data;
+    }
+}
+
+impl Memory {
+    pub fn new() -> Memory {
+        return Memory {
+            data: vec![0; MAX_MEMORY].into_boxed_slice(),
+            extended: Vec::new(),
+        };
+    }
+
+    pub fn fill(&mut self, pattern: RamPattern) -> () {
+        /* Only bank 0 is filled, higher banks are allocated on demand and start out as zeroes */
+        match pattern {
+            RamPattern::Zero => self.data.fill(0x00),
+            RamPattern::Ones => self.data.fill(0xFF),
+            RamPattern::AlternatingPages => {
+                for (address, byte) in self.data.iter_mut().enumerate() {
+                    *byte = if (address >> 8) & 1 == 0 { 0x00 } else { 0xFF };
+                }
+            }
+            RamPattern::Random(seed) => {
+                // xorshift64, which is plenty for garbage and needs no external crate
+                let mut state: u64 = seed | 1;
+                for byte in self.data.iter_mut() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    *byte = (state >> 32) as u8;
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,64 @@
+use crate::recompiler::generator::{Image, Target};
+
+/*      TEST PROGRAM ASM
+* = $1000
+
+lda #$FF    ; load 0xFF into accumulator
+
+start       ; label
+sta $90     ; stores accumulator at 0x90 (zero page 0x00 to 0xFF)
+sta $8000   ; stores accumulator at 0x8000 (absolute sta)
+eor #$CC    ; immediate eor (performs EOR on accumulator with 0xCC, either 0xFF ^ 0xCC = 0x33 or 0x33 ^ 0xCC = 0xFF)
+jmp start   ; jumps to start label - causes infinite loop
+*/
+
+pub const TEST_PROGRAM: [u8; 14] = [
+    0x00, 0x10, 0xA9, 0xFF, 0x85, 0x90, 0x8D, 0x00, 0x80, 0x49, 0xCC, 0x4C, 0x02, 0x10,
+];
+
+pub const FUNCTIONAL_TEST: &[u8] = include_bytes!("../binaries/functional_test.bin");
+pub const FUNCTIONAL_TEST_OFFSET: usize = 0x0A; // The image starts ten bytes into the zero page
+pub const FUNCTIONAL_TEST_START: u16 = 0x0400;
+
+fn vector(bytes: &[u8], load_address: u32, vector: u32) -> u32 {
+    let offset: usize = (vector - load_address) as usize;
+    return bytes[offset] as u32 | ((bytes[offset + 1] as u32) << 8);
+}
+
+pub fn bundled() -> Vec<(&'static str, Image)> {
+    /*
+        The programs the tests and the benchmark run compiled, with the module build.rs writes each to.
+        Both start at fixed addresses and the functional test handles its own interrupts.
+    */
+    let test_load: u32 = TEST_PROGRAM[0] as u32 | ((TEST_PROGRAM[1] as u32) << 8);
+    let functional_load: u32 = FUNCTIONAL_TEST_OFFSET as u32;
+    let target: Target = Target::named("Nmos6502").unwrap();
+
+    return vec![
+        (
+            "test_program",
+            Image {
+                name: String::from("TestProgram"),
+                target,
+                bytes: TEST_PROGRAM[2..].to_vec(),
+                load_address: test_load,
+                entry_points: vec![test_load],
+            },
+        ),
+        (
+            "functional_test",
+            Image {
+                name: String::from("FunctionalTest"),
+                target,
+                bytes: FUNCTIONAL_TEST.to_vec(),
+                load_address: functional_load,
+                entry_points: vec![
+                    FUNCTIONAL_TEST_START as u32,
+                    vector(FUNCTIONAL_TEST, functional_load, 0xFFFA),
+                    vector(FUNCTIONAL_TEST, functional_load, 0xFFFC),
+                    vector(FUNCTIONAL_TEST, functional_load, 0xFFFE),
+                ],
+            },
+        ),
+    ];
+}
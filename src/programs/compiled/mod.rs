@@ -0,0 +1,10 @@
+// Written into OUT_DIR by build.rs, one module for each of the programs in bundled.rs
+#[allow(clippy::all)]
+pub mod functional_test {
+    include!(concat!(env!("OUT_DIR"), "/functional_test.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod test_program {
+    include!(concat!(env!("OUT_DIR"), "/test_program.rs"));
+}
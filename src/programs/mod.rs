@@ -0,0 +1,4 @@
+pub mod bundled;
+pub mod compiled;
+
+pub const FUNCTIONAL_TEST_SUCCESS: u16 = 0x3469; // The JMP * the functional test parks on once all pass
@@ -0,0 +1,114 @@
+pub mod generator;
+
+use generator::{recompile, Image, Target};
+use std::fs;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "\
+usage: emu-6502 recompile <binary> --load <address> --variant <variant> --entry <address>...
+                          [--name <type>] [--output <file>]
+
+Addresses are hex, with or without a $ or 0x. --entry can be given more than once, vectors
+inside the image have to be listed as well. The module goes to standard output unless --output
+names a file, and the type it exports is named after the binary unless --name is given. Only
+the NMOS variants can be recompiled: Nmos6502, Nmos6502RevA, Mos6507, Mos6510 and Ricoh2A03.";
+
+fn address(text: &str) -> Result<u32, String> {
+    let digits: &str = text.trim_start_matches('$').trim_start_matches("0x");
+    return u32::from_str_radix(digits, 16).map_err(|_| format!("{} is not a hex address", text));
+}
+
+fn type_name(path: &str) -> String {
+    /* functional_test.bin exports FunctionalTest */
+    let stem: &str = Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Program");
+    let mut name = String::new();
+    for word in stem.split(|c: char| c.is_alphanumeric() == false) {
+        let mut characters = word.chars();
+        if let Some(first) = characters.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(characters);
+        }
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, 'P');
+    }
+    return name;
+}
+
+pub fn parse_arguments(arguments: &[String]) -> Result<(Image, Option<String>), String> {
+    /* The binary, where it loads, the part it runs on and where it is entered. Returns the image and where to write the module */
+    let mut binary: Option<&str> = None;
+    let mut load_address: Option<u32> = None;
+    let mut target: Option<Target> = None;
+    let mut entry_points: Vec<u32> = Vec::new();
+    let mut name: Option<String> = None;
+    let mut output: Option<String> = None;
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let mut value = || -> Result<&String, String> {
+            return arguments
+                .next()
+                .ok_or_else(|| format!("{} needs a value", argument));
+        };
+        match argument.as_str() {
+            "--load" => load_address = Some(address(value()?)?),
+            "--entry" => entry_points.push(address(value()?)?),
+            "--name" => name = Some(value()?.clone()),
+            "--output" => output = Some(value()?.clone()),
+            "--variant" => {
+                let text: &String = value()?;
+                match Target::named(text) {
+                    Some(found) => target = Some(found),
+                    None => return Err(format!("{} is not an NMOS variant", text)),
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            path if binary.is_none() => binary = Some(path),
+            extra => return Err(format!("more than one binary given, {}", extra)),
+        }
+    }
+
+    let binary: &str = binary.ok_or("no binary given")?;
+    let load_address: u32 = load_address.ok_or("no --load address given")?;
+    let target: Target = target.ok_or("no --variant given")?;
+    if entry_points.is_empty() {
+        return Err(String::from("no --entry given"));
+    }
+    let bytes: Vec<u8> =
+        fs::read(binary).map_err(|error| format!("could not read {}: {}", binary, error))?;
+
+    let image = Image {
+        name: name.unwrap_or_else(|| type_name(binary)),
+        target,
+        bytes,
+        load_address,
+        entry_points,
+    };
+    return Ok((image, output));
+}
+
+pub fn run(arguments: &[String]) -> () {
+    let (image, output) = match parse_arguments(arguments) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let source: String = recompile(&image);
+    match output {
+        Some(path) => {
+            if let Err(error) = fs::write(&path, source) {
+                eprintln!("{} could not be written to {}: {}", image.name, path, error);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", source),
+    }
+}
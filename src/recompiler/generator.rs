@@ -0,0 +1,949 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+// Everything in a generated module before its blocks
+#[rustfmt::skip]
+const HEADER: &str = "\
+// Generated by src/recompiler/generator.rs, do not edit.
+// {variant} image of {size} bytes at ${load}, entry points {entry_points}: {blocks} blocks, {instructions} instructions.
+
+use crate::cpu;
+use crate::mem::*;
+
+{uses}
+pub struct {name};
+
+impl CompiledProgram for {name} {
+    const VARIANT: Variant = Variant::{variant};
+
+    fn run_block<B: Bus>(
+        processor: &mut Processor,
+        memory: &mut B,
+        address: u32,
+        deadline: u64,
+    ) -> Option<u64> {
+        return chain(processor, memory, address, deadline, dispatch::<B>);
+    }
+}
+
+fn dispatch<B: Bus>(
+    processor: &mut Processor,
+    memory: &mut B,
+    address: u32,
+    deadline: u64,
+    executed: u64,
+) -> Result<u64, u64> {
+    match address {
+{arms}        _ => return Err(executed),
+    }
+}
+";
+
+// What generated code calls into, each imported only by the modules that use it
+const BYTE_FUNCTIONS: &str = "cpu::functions::byte::*";
+const ADD: &str = "cpu::instructions::arithmetic::add::add";
+const SUBTRACT: &str = "cpu::instructions::arithmetic::subtract::subtract";
+const SYSTEM: &str = "cpu::instructions::system::System";
+const VECTORS: &str = "cpu::interrupts::{IRQ_VECTOR, NMI_VECTOR}";
+const FLAGS: &str = "cpu::opcodes::ProcessorStatus::*";
+
+/// How an instruction finds its operand, as far as the generator needs to know.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Implied,
+    Accumulator,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,
+    IndirectX,
+    IndirectY,
+    Relative,
+}
+
+use Mode::*;
+
+// Short names keep the mode table lined up with the mnemonic table
+const IMP: Mode = Implied;
+const ACC: Mode = Accumulator;
+const IMM: Mode = Immediate;
+const ZPG: Mode = ZeroPage;
+const ZPX: Mode = ZeroPageX;
+const ZPY: Mode = ZeroPageY;
+const ABS: Mode = Absolute;
+const ABX: Mode = AbsoluteX;
+const ABY: Mode = AbsoluteY;
+const IND: Mode = Indirect;
+const IZX: Mode = IndirectX;
+const IZY: Mode = IndirectY;
+const REL: Mode = Relative;
+
+// The documented NMOS instructions. Undocumented opcodes are ---, no code is written for them
+#[rustfmt::skip]
+const MNEMONICS: [&str; 256] = [
+//   0      1      2      3      4      5      6      7      8      9      A      B      C      D      E      F
+    "BRK", "ORA", "---", "---", "---", "ORA", "ASL", "---", "PHP", "ORA", "ASL", "---", "---", "ORA", "ASL", "---", // 0
+    "BPL", "ORA", "---", "---", "---", "ORA", "ASL", "---", "CLC", "ORA", "---", "---", "---", "ORA", "ASL", "---", // 1
+    "JSR", "AND", "---", "---", "BIT", "AND", "ROL", "---", "PLP", "AND", "ROL", "---", "BIT", "AND", "ROL", "---", // 2
+    "BMI", "AND", "---", "---", "---", "AND", "ROL", "---", "SEC", "AND", "---", "---", "---", "AND", "ROL", "---", // 3
+    "RTI", "EOR", "---", "---", "---", "EOR", "LSR", "---", "PHA", "EOR", "LSR", "---", "JMP", "EOR", "LSR", "---", // 4
+    "BVC", "EOR", "---", "---", "---", "EOR", "LSR", "---", "CLI", "EOR", "---", "---", "---", "EOR", "LSR", "---", // 5
+    "RTS", "ADC", "---", "---", "---", "ADC", "ROR", "---", "PLA", "ADC", "ROR", "---", "JMP", "ADC", "ROR", "---", // 6
+    "BVS", "ADC", "---", "---", "---", "ADC", "ROR", "---", "SEI", "ADC", "---", "---", "---", "ADC", "ROR", "---", // 7
+    "---", "STA", "---", "---", "STY", "STA", "STX", "---", "DEY", "---", "TXA", "---", "STY", "STA", "STX", "---", // 8
+    "BCC", "STA", "---", "---", "STY", "STA", "STX", "---", "TYA", "STA", "TXS", "---", "---", "STA", "---", "---", // 9
+    "LDY", "LDA", "LDX", "---", "LDY", "LDA", "LDX", "---", "TAY", "LDA", "TAX", "---", "LDY", "LDA", "LDX", "---", // A
+    "BCS", "LDA", "---", "---", "LDY", "LDA", "LDX", "---", "CLV", "LDA", "TSX", "---", "LDY", "LDA", "LDX", "---", // B
+    "CPY", "CMP", "---", "---", "CPY", "CMP", "DEC", "---", "INY", "CMP", "DEX", "---", "CPY", "CMP", "DEC", "---", // C
+    "BNE", "CMP", "---", "---", "---", "CMP", "DEC", "---", "CLD", "CMP", "---", "---", "---", "CMP", "DEC", "---", // D
+    "CPX", "SBC", "---", "---", "CPX", "SBC", "INC", "---", "INX", "SBC", "NOP", "---", "CPX", "SBC", "INC", "---", // E
+    "BEQ", "SBC", "---", "---", "---", "SBC", "INC", "---", "SED", "SBC", "---", "---", "---", "SBC", "INC", "---", // F
+];
+
+#[rustfmt::skip]
+const MODES: [Mode; 256] = [
+//   0    1    2    3    4    5    6    7    8    9    A    B    C    D    E    F
+    IMP, IZX, IMP, IMP, IMP, ZPG, ZPG, IMP, IMP, IMM, ACC, IMP, IMP, ABS, ABS, IMP, // 0
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // 1
+    ABS, IZX, IMP, IMP, ZPG, ZPG, ZPG, IMP, IMP, IMM, ACC, IMP, ABS, ABS, ABS, IMP, // 2
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // 3
+    IMP, IZX, IMP, IMP, IMP, ZPG, ZPG, IMP, IMP, IMM, ACC, IMP, ABS, ABS, ABS, IMP, // 4
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // 5
+    IMP, IZX, IMP, IMP, IMP, ZPG, ZPG, IMP, IMP, IMM, ACC, IMP, IND, ABS, ABS, IMP, // 6
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // 7
+    IMP, IZX, IMP, IMP, ZPG, ZPG, ZPG, IMP, IMP, IMP, IMP, IMP, ABS, ABS, ABS, IMP, // 8
+    REL, IZY, IMP, IMP, ZPX, ZPX, ZPY, IMP, IMP, ABY, IMP, IMP, IMP, ABX, IMP, IMP, // 9
+    IMM, IZX, IMM, IMP, ZPG, ZPG, ZPG, IMP, IMP, IMM, IMP, IMP, ABS, ABS, ABS, IMP, // A
+    REL, IZY, IMP, IMP, ZPX, ZPX, ZPY, IMP, IMP, ABY, IMP, IMP, ABX, ABX, ABY, IMP, // B
+    IMM, IZX, IMP, IMP, ZPG, ZPG, ZPG, IMP, IMP, IMM, IMP, IMP, ABS, ABS, ABS, IMP, // C
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // D
+    IMM, IZX, IMP, IMP, ZPG, ZPG, ZPG, IMP, IMP, IMM, IMP, IMP, ABS, ABS, ABS, IMP, // E
+    REL, IZY, IMP, IMP, IMP, ZPX, ZPX, IMP, IMP, ABY, IMP, IMP, IMP, ABX, ABX, IMP, // F
+];
+
+impl Mode {
+    pub fn length(&self) -> u32 {
+        match self {
+            Implied | Accumulator => return 1,
+            Absolute | AbsoluteX | AbsoluteY | Indirect => return 3,
+            _ => return 2,
+        }
+    }
+}
+
+/// Which dummy read an indexed operand costs, reads only pay for it when a page is crossed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Access {
+    Read,
+    Write,
+    Modify,
+}
+
+/// An NMOS part the generator writes code for, under the name cpu::variant::Variant gives it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    pub name: &'static str,
+    pub rotate_right: bool, // Rev A parts shift left for ROR and leave carry alone
+}
+
+// Every part that runs the documented NMOS instructions as the interpreter does
+const TARGETS: [Target; 5] = [
+    Target {
+        name: "Nmos6502",
+        rotate_right: true,
+    },
+    Target {
+        name: "Nmos6502RevA",
+        rotate_right: false,
+    },
+    Target {
+        name: "Mos6507",
+        rotate_right: true,
+    },
+    Target {
+        name: "Mos6510",
+        rotate_right: true,
+    },
+    Target {
+        name: "Ricoh2A03",
+        rotate_right: true,
+    },
+];
+
+impl Target {
+    pub fn named(name: &str) -> Option<Target> {
+        return TARGETS
+            .iter()
+            .find(|target| target.name.eq_ignore_ascii_case(name))
+            .cloned();
+    }
+}
+
+/// A fixed binary and what is known about where its code starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub name: String, // Type the generated module exports
+    pub target: Target,
+    pub bytes: Vec<u8>,
+    pub load_address: u32,
+    pub entry_points: Vec<u32>, // Where control arrives from outside, vectors included
+}
+
+impl Image {
+    pub fn contains(&self, address: u32) -> bool {
+        return address >= self.load_address
+            && address < self.load_address + self.bytes.len() as u32;
+    }
+
+    fn byte(&self, address: u32) -> u8 {
+        return self.bytes[(address - self.load_address) as usize];
+    }
+
+    fn instruction(&self, address: u32) -> Option<Instruction> {
+        /* A documented instruction that lies wholly inside the image and does not wrap past $FFFF */
+        if self.contains(address) == false || address > 0xFFFF {
+            return None;
+        }
+        let opcode: u8 = self.byte(address);
+        let mnemonic: &'static str = MNEMONICS[opcode as usize];
+        let mode: Mode = MODES[opcode as usize];
+        let end: u32 = address + mode.length();
+        if mnemonic == "---" || end > 0x1_0000 || self.contains(end - 1) == false {
+            return None;
+        }
+
+        return Some(Instruction {
+            address,
+            bytes: (address..end).map(|address| self.byte(address)).collect(),
+            mnemonic,
+            mode,
+        });
+    }
+}
+
+/// One instruction as it sits in the image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub address: u32,
+    pub bytes: Vec<u8>, // Opcode then operand
+    pub mnemonic: &'static str,
+    pub mode: Mode,
+}
+
+impl Instruction {
+    fn byte(&self) -> u8 {
+        return self.bytes[1];
+    }
+
+    fn word(&self) -> u16 {
+        return self.bytes[1] as u16 | ((self.bytes[2] as u16) << 8);
+    }
+
+    fn end(&self) -> u32 {
+        return self.address + self.bytes.len() as u32;
+    }
+
+    fn branch_target(&self) -> u16 {
+        return (self.end() as u16).wrapping_add(self.byte() as i8 as u16);
+    }
+
+    fn ends_block(&self) -> bool {
+        // Anything that can leave the straight line, a branch or a jump only knows where at run time
+        match self.mnemonic {
+            "JMP" | "JSR" | "RTS" | "RTI" | "BRK" => return true,
+            _ => return self.mode == Relative,
+        }
+    }
+
+    fn assembly(&self) -> String {
+        let operand: String = match self.mode {
+            Implied => String::new(),
+            Accumulator => String::from(" A"),
+            Immediate => format!(" #${:02X}", self.byte()),
+            ZeroPage => format!(" ${:02X}", self.byte()),
+            ZeroPageX => format!(" ${:02X},X", self.byte()),
+            ZeroPageY => format!(" ${:02X},Y", self.byte()),
+            Absolute => format!(" ${:04X}", self.word()),
+            AbsoluteX => format!(" ${:04X},X", self.word()),
+            AbsoluteY => format!(" ${:04X},Y", self.word()),
+            Indirect => format!(" (${:04X})", self.word()),
+            IndirectX => format!(" (${:02X},X)", self.byte()),
+            IndirectY => format!(" (${:02X}),Y", self.byte()),
+            Relative => format!(" ${:04X}", self.branch_target()),
+        };
+        return format!("{}{}", self.mnemonic, operand);
+    }
+}
+
+/// A straight run of instructions, only ever entered at its start.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub start: u32,
+    pub end: u32, // One past the last byte of the last instruction
+    pub instructions: Vec<Instruction>,
+}
+
+fn successors(image: &Image, last: &Instruction) -> Vec<u32> {
+    /*
+        Where control can go from the instruction that ended a block, as far as the bytes say.
+        An indirect JMP through a pointer in the image is followed to where the pointer points
+        when the program starts, anything else computed is left to the interpreter to find.
+    */
+    let end: u32 = last.end();
+    match (last.mnemonic, last.mode) {
+        (_, Relative) => return vec![last.branch_target() as u32, end],
+        ("JMP", Absolute) => return vec![last.word() as u32],
+        ("JSR", _) => return vec![last.word() as u32, end], // The return lands after it
+        ("JMP", Indirect) => {
+            let pointer: u32 = last.word() as u32;
+            let high: u32 = (pointer & 0xFF00) | ((pointer + 1) & 0x00FF);
+            if image.contains(pointer) && image.contains(high) {
+                return vec![image.byte(pointer) as u32 | ((image.byte(high) as u32) << 8)];
+            }
+            return vec![];
+        }
+        ("BRK", _) => return vec![end + 1], // RTI comes back past the signature byte
+        ("RTS" | "RTI", _) => return vec![],
+        // Cut short by an opcode there is no code for, carries straight on
+        _ => return vec![end],
+    }
+}
+
+pub fn find_blocks(image: &Image) -> BTreeMap<u32, Block> {
+    /*
+        Follows the static control flow out from the entry points, decoding a block at every
+        address it reaches inside the image, then splits them where a branch lands mid-block.
+    */
+    let mut blocks: BTreeMap<u32, Block> = BTreeMap::new();
+    let mut pending: Vec<u32> = image.entry_points.clone();
+
+    while let Some(start) = pending.pop() {
+        if blocks.contains_key(&start) {
+            continue;
+        }
+
+        let mut instructions: Vec<Instruction> = Vec::new();
+        let mut address: u32 = start;
+        while let Some(instruction) = image.instruction(address) {
+            address = instruction.end();
+            let ends: bool = instruction.ends_block();
+            instructions.push(instruction);
+            if ends {
+                break;
+            }
+        }
+        let last: &Instruction = match instructions.last() {
+            Some(last) => last,
+            None => continue,
+        };
+
+        pending.extend(successors(image, last));
+        let block = Block {
+            start,
+            end: address,
+            instructions,
+        };
+        blocks.insert(start, block);
+    }
+
+    // Cut each block where the next one starts, so every instruction is compiled once
+    let starts: Vec<u32> = blocks.keys().cloned().collect();
+    for pair in starts.windows(2) {
+        let block: &mut Block = blocks.get_mut(&pair[0]).unwrap();
+        if let Some(cut) = block
+            .instructions
+            .iter()
+            .position(|instruction| instruction.address >= pair[1])
+        {
+            block.end = block.instructions[cut].address;
+            block.instructions.truncate(cut);
+        }
+    }
+
+    return blocks;
+}
+
+fn function_name(address: u32) -> String {
+    return format!("block_{:04x}", address);
+}
+
+fn register(letter: u8) -> &'static str {
+    match letter {
+        b'A' => return "accumulator",
+        b'X' => return "register_x",
+        _ => return "register_y",
+    }
+}
+
+fn index(mode: Mode) -> &'static str {
+    match mode {
+        ZeroPageX | AbsoluteX | IndirectX => return "register_x",
+        _ => return "register_y",
+    }
+}
+
+fn fix_up(code: &mut Vec<String>, page: String, crossed: &str, access: Access) -> () {
+    // NMOS parts read the target before the carry reaches its high byte
+    let read: String = format!(
+        "read_unobserved(processor, memory, {} | (address & 0x00FF));",
+        page
+    );
+    if access != Access::Read {
+        code.push(read);
+        return;
+    }
+    code.push(format!("if {} {{", crossed));
+    code.push(format!("    {}", read));
+    code.push(String::from("}"));
+}
+
+fn address(code: &mut Vec<String>, instruction: &Instruction, access: Access) -> String {
+    /* The operand's address worked out as addr_mode() does it, dummy reads and all. Constant where the mode allows */
+    let byte: u8 = instruction.byte();
+    match instruction.mode {
+        ZeroPage => return format!("{:#06X}", byte),
+        Absolute => return format!("{:#06X}", instruction.word()),
+        ZeroPageX | ZeroPageY => {
+            code.push(format!(
+                "read_unobserved(processor, memory, {:#06X});",
+                byte
+            ));
+            code.push(format!(
+                "let address: u16 = {:#04X}u8.wrapping_add(processor.{}) as u16;",
+                byte,
+                index(instruction.mode)
+            ));
+        }
+        AbsoluteX | AbsoluteY => {
+            let base: u16 = instruction.word();
+            code.push(format!(
+                "let address: u16 = {:#06X}u16.wrapping_add(processor.{} as u16);",
+                base,
+                index(instruction.mode)
+            ));
+            let crossed: String = format!("address >> 8 != {:#04X}", base >> 8);
+            fix_up(code, format!("{:#06X}", base & 0xFF00), &crossed, access);
+        }
+        IndirectX => {
+            code.push(format!(
+                "read_unobserved(processor, memory, {:#06X});",
+                byte
+            ));
+            code.push(format!(
+                "let pointer: u8 = {:#04X}u8.wrapping_add(processor.register_x);",
+                byte
+            ));
+            code.push(String::from(
+                "let low: u8 = read_unobserved(processor, memory, pointer as u16);",
+            ));
+            code.push(String::from(
+                "let high: u8 = read_unobserved(processor, memory, pointer.wrapping_add(1) as u16);",
+            ));
+            code.push(String::from(
+                "let address: u16 = low as u16 | ((high as u16) << 8);",
+            ));
+        }
+        _ => {
+            code.push(format!(
+                "let low: u8 = read_unobserved(processor, memory, {:#06X});",
+                byte
+            ));
+            code.push(format!(
+                "let high: u8 = read_unobserved(processor, memory, {:#06X});",
+                byte.wrapping_add(1)
+            ));
+            code.push(String::from(
+                "let base: u16 = low as u16 | ((high as u16) << 8);",
+            ));
+            code.push(String::from(
+                "let address: u16 = base.wrapping_add(processor.register_y as u16);",
+            ));
+            let crossed: &str = "address >> 8 != base >> 8";
+            fix_up(code, String::from("(base & 0xFF00)"), crossed, access);
+        }
+    }
+    return String::from("address");
+}
+
+fn operand(code: &mut Vec<String>, instruction: &Instruction) -> String {
+    /* The byte a reading instruction works on, a constant in the immediate forms */
+    if instruction.mode == Immediate {
+        return format!("{:#04X}", instruction.byte());
+    }
+    let address: String = address(code, instruction, Access::Read);
+    code.push(format!(
+        "let value: u8 = read_unobserved(processor, memory, {});",
+        address
+    ));
+    return String::from("value");
+}
+
+fn read_modify_write(code: &mut Vec<String>, instruction: &Instruction, target: &Target) -> () {
+    let (result, carry) = match instruction.mnemonic {
+        "ASL" => ("value << 1", Some("value & 0x80 != 0")),
+        "LSR" => ("value >> 1", Some("value & 0x01 != 0")),
+        "ROL" => (
+            "(value << 1) | processor.fetch_status(CarryFlag) as u8",
+            Some("value & 0x80 != 0"),
+        ),
+        "ROR" if target.rotate_right => (
+            "(value >> 1) | ((processor.fetch_status(CarryFlag) as u8) << 7)",
+            Some("value & 0x01 != 0"),
+        ),
+        "ROR" => ("value << 1", None),
+        "INC" => ("value.wrapping_add(1)", None),
+        _ => ("value.wrapping_sub(1)", None),
+    };
+
+    if instruction.mode == Accumulator {
+        code.push(String::from("processor.decrement_cycles(1);"));
+        code.push(String::from("let value: u8 = processor.accumulator;"));
+        code.push(format!("let result: u8 = {};", result));
+        code.push(String::from("processor.accumulator = result;"));
+    } else {
+        let address: String = address(code, instruction, Access::Modify);
+        code.push(format!(
+            "let value: u8 = read_unobserved(processor, memory, {});",
+            address
+        ));
+        code.push(format!("let result: u8 = {};", result));
+        // NMOS parts write the byte back unchanged while the result is worked out
+        code.push(format!(
+            "write_unobserved(processor, memory, value, {});",
+            address
+        ));
+        code.push(format!(
+            "write_unobserved(processor, memory, result, {});",
+            address
+        ));
+    }
+    code.push(String::from("set_zero_negative(processor, result);"));
+    if let Some(carry) = carry {
+        code.push(format!("processor.set_status(CarryFlag, {});", carry));
+    }
+}
+
+fn branch(code: &mut Vec<String>, instruction: &Instruction) -> () {
+    /* The target is known, so is whether reaching it crosses a page */
+    let condition: &str = match instruction.mnemonic {
+        "BPL" => "processor.fetch_status(NegativeFlag) == false",
+        "BMI" => "processor.fetch_status(NegativeFlag)",
+        "BVC" => "processor.fetch_status(OverflowFlag) == false",
+        "BVS" => "processor.fetch_status(OverflowFlag)",
+        "BCC" => "processor.fetch_status(CarryFlag) == false",
+        "BCS" => "processor.fetch_status(CarryFlag)",
+        "BNE" => "processor.fetch_status(ZeroFlag) == false",
+        _ => "processor.fetch_status(ZeroFlag)",
+    };
+    let next: u16 = instruction.end() as u16;
+    let taken: u16 = instruction.branch_target();
+
+    code.push(format!("if {} {{", condition));
+    code.push(format!(
+        "    read_unobserved(processor, memory, {:#06X});",
+        next
+    ));
+    code.push(format!("    processor.program_counter = {:#06X};", taken));
+    if taken >> 8 != next >> 8 {
+        let uncarried: u16 = (next & 0xFF00) | (taken & 0x00FF);
+        code.push(format!(
+            "    read_unobserved(processor, memory, {:#06X});",
+            uncarried
+        ));
+    } else {
+        code.push(String::from("    processor.delay_interrupt_poll();"));
+    }
+    code.push(String::from("}"));
+}
+
+fn statements(instruction: &Instruction, target: &Target) -> Vec<String> {
+    /*
+        The Rust for one instruction once its opcode is fetched, making the same bus accesses in
+        the same order as its handler. Operand bytes were checked along with the opcode, so their
+        fetch cycles are charged without reading them again.
+    */
+    let mut code: Vec<String> = Vec::new();
+    let name: &str = instruction.mnemonic;
+    if name != "JSR" {
+        for byte in instruction.bytes[1..].iter() {
+            code.push(format!("fetch_cached(processor, {:#04X});", byte));
+        }
+    }
+
+    match name {
+        "LDA" | "LDX" | "LDY" => {
+            let value: String = operand(&mut code, instruction);
+            let register: &str = register(name.as_bytes()[2]);
+            code.push(format!("processor.{} = {};", register, value));
+            code.push(format!("set_zero_negative(processor, {});", value));
+        }
+        "STA" | "STX" | "STY" => {
+            let address: String = address(&mut code, instruction, Access::Write);
+            let register: &str = register(name.as_bytes()[2]);
+            code.push(format!("let value: u8 = processor.{};", register));
+            code.push(format!(
+                "write_unobserved(processor, memory, value, {});",
+                address
+            ));
+        }
+        "AND" | "ORA" | "EOR" => {
+            let value: String = operand(&mut code, instruction);
+            let operator: &str = match name {
+                "AND" => "&",
+                "ORA" => "|",
+                _ => "^",
+            };
+            code.push(format!(
+                "let result: u8 = processor.accumulator {} {};",
+                operator, value
+            ));
+            code.push(String::from("processor.accumulator = result;"));
+            code.push(String::from("set_zero_negative(processor, result);"));
+        }
+        "ADC" => {
+            let value: String = operand(&mut code, instruction);
+            code.push(format!("add(processor, {} as u16);", value));
+        }
+        "SBC" => {
+            let value: String = operand(&mut code, instruction);
+            code.push(format!("subtract(processor, {});", value));
+        }
+        "CMP" | "CPX" | "CPY" => {
+            let value: String = operand(&mut code, instruction);
+            let register: &str = match name {
+                "CMP" => "accumulator",
+                _ => register(name.as_bytes()[2]),
+            };
+            code.push(format!(
+                "let result: u8 = processor.{}.wrapping_sub({});",
+                register, value
+            ));
+            let carry: String = match value.as_str() {
+                "0x00" => String::from("true"), // Every register compares at least equal to zero
+                _ => format!("processor.{} >= {}", register, value),
+            };
+            code.push(format!("processor.set_status(CarryFlag, {});", carry));
+            code.push(String::from("set_zero_negative(processor, result);"));
+        }
+        "BIT" => {
+            let value: String = operand(&mut code, instruction);
+            code.push(format!(
+                "processor.set_status(ZeroFlag, processor.accumulator & {} == 0);",
+                value
+            ));
+            code.push(format!(
+                "processor.set_status(OverflowFlag, {} & 0x40 != 0);",
+                value
+            ));
+            code.push(format!(
+                "processor.set_status(NegativeFlag, {} & 0x80 != 0);",
+                value
+            ));
+        }
+        "ASL" | "LSR" | "ROL" | "ROR" | "INC" | "DEC" => {
+            read_modify_write(&mut code, instruction, target);
+        }
+        "INX" | "INY" | "DEX" | "DEY" => {
+            let register: &str = register(name.as_bytes()[2]);
+            let step: &str = match name.as_bytes()[0] {
+                b'I' => "wrapping_add",
+                _ => "wrapping_sub",
+            };
+            code.push(format!(
+                "let result: u8 = processor.{}.{}(1);",
+                register, step
+            ));
+            code.push(format!("processor.{} = result;", register));
+            code.push(String::from("set_zero_negative(processor, result);"));
+            code.push(String::from("processor.decrement_cycles(1);"));
+        }
+        "TAX" | "TAY" | "TXA" | "TYA" | "TSX" => {
+            let from: &str = match name.as_bytes()[1] {
+                b'S' => "stack_pointer",
+                letter => register(letter),
+            };
+            code.push(format!("let value: u8 = processor.{};", from));
+            code.push(format!(
+                "processor.{} = value;",
+                register(name.as_bytes()[2])
+            ));
+            code.push(String::from("set_zero_negative(processor, value);"));
+            code.push(String::from("processor.decrement_cycles(1);"));
+        }
+        "TXS" => {
+            code.push(String::from(
+                "processor.stack_pointer = processor.register_x;",
+            ));
+            code.push(String::from("processor.decrement_cycles(1);"));
+        }
+        "CLC" | "SEC" | "CLD" | "SED" | "CLI" | "SEI" | "CLV" => {
+            let flag: &str = match name.as_bytes()[2] {
+                b'C' => "CarryFlag",
+                b'D' => "DecimalMode",
+                b'I' => "InterruptDisable",
+                _ => "OverflowFlag",
+            };
+            if flag == "InterruptDisable" {
+                code.push(String::from("processor.latch_interrupt_disable();"));
+            }
+            code.push(format!(
+                "processor.set_status({}, {});",
+                flag,
+                name.starts_with("SE")
+            ));
+            code.push(String::from("processor.decrement_cycles(1);"));
+        }
+        "NOP" => code.push(String::from("processor.decrement_cycles(1);")),
+        "PHA" | "PHP" => {
+            let value: &str = match name {
+                "PHA" => "processor.accumulator",
+                _ => "processor.status | 0x30", // B and the unused bit go out set
+            };
+            code.push(String::from("let next: u16 = processor.program_counter;"));
+            code.push(String::from("read_unobserved(processor, memory, next);"));
+            code.push(format!("let value: u8 = {};", value));
+            code.push(String::from("push(processor, memory, value);"));
+        }
+        "PLA" => {
+            code.push(String::from("pull_dummy_reads(processor, memory);"));
+            code.push(String::from("let value: u8 = pull(processor, memory);"));
+            code.push(String::from("processor.accumulator = value;"));
+            code.push(String::from("set_zero_negative(processor, value);"));
+        }
+        "PLP" => {
+            code.push(String::from("pull_dummy_reads(processor, memory);"));
+            code.push(String::from("let value: u8 = pull(processor, memory);"));
+            code.push(String::from("processor.latch_interrupt_disable();"));
+            code.push(String::from("processor.status = value & 0xCF;"));
+        }
+        "JMP" if instruction.mode == Absolute => {
+            code.push(format!(
+                "processor.program_counter = {:#06X};",
+                instruction.word()
+            ));
+        }
+        "JMP" => {
+            // The pointer's high byte never carries into the next page
+            let pointer: u16 = instruction.word();
+            let high: u16 = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+            code.push(format!(
+                "let low: u8 = read_unobserved(processor, memory, {:#06X});",
+                pointer
+            ));
+            code.push(format!(
+                "let high: u8 = read_unobserved(processor, memory, {:#06X});",
+                high
+            ));
+            code.push(String::from(
+                "processor.program_counter = low as u16 | ((high as u16) << 8);",
+            ));
+        }
+        "JSR" => {
+            // The high byte of the target is read after the return address is pushed, and read
+            // through the bus since a stack running into the code can have just overwritten it
+            code.push(format!(
+                "fetch_cached(processor, {:#04X});",
+                instruction.bytes[1]
+            ));
+            code.push(String::from(
+                "let stack: u16 = 0x0100 | processor.stack_pointer as u16;",
+            ));
+            code.push(String::from("read_unobserved(processor, memory, stack);"));
+            code.push(String::from("push_program_counter(processor, memory);"));
+            code.push(String::from("let next: u16 = processor.program_counter;"));
+            code.push(String::from(
+                "let high: u8 = read_unobserved(processor, memory, next);",
+            ));
+            code.push(format!(
+                "processor.program_counter = {:#06X} | ((high as u16) << 8);",
+                instruction.bytes[1]
+            ));
+        }
+        "RTS" => {
+            code.push(String::from("pull_dummy_reads(processor, memory);"));
+            code.push(String::from("let low: u8 = pull(processor, memory);"));
+            code.push(String::from("let high: u8 = pull(processor, memory);"));
+            code.push(String::from(
+                "let address: u16 = low as u16 | ((high as u16) << 8);",
+            ));
+            code.push(String::from("read_unobserved(processor, memory, address);"));
+            code.push(String::from(
+                "processor.program_counter = address.wrapping_add(1);",
+            ));
+        }
+        "RTI" => {
+            code.push(String::from("pull_dummy_reads(processor, memory);"));
+            code.push(String::from("let status: u8 = pull(processor, memory);"));
+            code.push(String::from("let low: u8 = pull(processor, memory);"));
+            code.push(String::from("let high: u8 = pull(processor, memory);"));
+            code.push(String::from(
+                "processor.program_counter = low as u16 | ((high as u16) << 8);",
+            ));
+            code.push(String::from("processor.status = status & 0xCF;"));
+        }
+        "BRK" => {
+            // The signature byte is read through the bus, it is not part of the instruction
+            code.push(String::from("let next: u16 = processor.program_counter;"));
+            code.push(String::from("read_unobserved(processor, memory, next);"));
+            code.push(String::from("processor.increment_pc();"));
+            code.push(String::from("push_program_counter(processor, memory);"));
+            code.push(String::from("let status: u8 = processor.status | 0x30;"));
+            code.push(String::from(
+                "let vector: u16 = processor.hijack_vector(IRQ_VECTOR, NMI_VECTOR);",
+            ));
+            code.push(String::from("push(processor, memory, status);"));
+            code.push(String::from(
+                "let low: u8 = read_unobserved(processor, memory, vector);",
+            ));
+            code.push(String::from(
+                "let high: u8 = read_unobserved(processor, memory, vector.wrapping_add(1));",
+            ));
+            code.push(String::from(
+                "processor.program_counter = low as u16 | ((high as u16) << 8);",
+            ));
+            code.push(String::from("processor.set_status(BreakCommand, true);"));
+            code.push(String::from(
+                "processor.set_status(InterruptDisable, true);",
+            ));
+        }
+        _ => branch(&mut code, instruction),
+    }
+
+    return code;
+}
+
+fn imports(code: &[String], uses: &mut BTreeSet<&'static str>) -> () {
+    /* What the statements call decides which of the crate's modules the generated one imports */
+    let needs = |calls: &[&str]| -> bool {
+        return code
+            .iter()
+            .any(|line| calls.iter().any(|call| line.contains(call)));
+    };
+
+    if needs(&["fetch_cached(", "_unobserved("]) {
+        uses.insert(BYTE_FUNCTIONS);
+    }
+    if needs(&["add(processor"]) {
+        uses.insert(ADD);
+    }
+    if needs(&["subtract(processor"]) {
+        uses.insert(SUBTRACT);
+    }
+    if needs(&[
+        "latch_interrupt_disable(",
+        "delay_interrupt_poll(",
+        "hijack_vector(",
+    ]) {
+        uses.insert(SYSTEM);
+    }
+    if needs(&["_VECTOR"]) {
+        uses.insert(VECTORS);
+    }
+    if needs(&["_status("]) {
+        uses.insert(FLAGS);
+    }
+}
+
+pub fn recompile(image: &Image) -> String {
+    /*
+        Writes a module with one function per block. Each instruction is checked against memory
+        and given the boundary checks of execute(), then runs as straight Rust with its operand
+        and branch targets filled in. Where the code in memory has changed, or control goes
+        somewhere no block starts, the interpreter takes over.
+    */
+    let blocks: BTreeMap<u32, Block> = find_blocks(image);
+    let instructions: usize = blocks.values().map(|block| block.instructions.len()).sum();
+    let entry_points: Vec<String> = image
+        .entry_points
+        .iter()
+        .map(|address| format!("${:04X}", address))
+        .collect();
+
+    let mut uses: BTreeSet<&'static str> = BTreeSet::new();
+    uses.insert("cpu::compiled::*");
+    uses.insert("cpu::processor::*");
+    uses.insert("cpu::variant::Variant");
+
+    let mut arms = String::new();
+    let mut functions = String::new();
+    for block in blocks.values() {
+        let name: String = function_name(block.start);
+        let _ = writeln!(
+            arms,
+            "        {:#06X} => return {}(processor, memory, deadline, executed),",
+            block.start, name
+        );
+
+        let _ = writeln!(functions);
+        let _ = writeln!(functions, "fn {}<B: Bus>(", name);
+        let _ = writeln!(functions, "    processor: &mut Processor,");
+        let _ = writeln!(functions, "    memory: &mut B,");
+        let _ = writeln!(functions, "    deadline: u64,");
+        let _ = writeln!(functions, "    executed: u64,");
+        let _ = writeln!(functions, ") -> Result<u64, u64> {{");
+        let _ = writeln!(functions, "    let mut executed: u64 = executed;");
+        let _ = writeln!(
+            functions,
+            "    if enter::<{}>(processor, {:#06X}, {:#06X}) == false {{",
+            image.name, block.start, block.end
+        );
+        let _ = writeln!(functions, "        return Err(executed);");
+        let _ = writeln!(functions, "    }}");
+
+        for instruction in block.instructions.iter() {
+            let bytes: Vec<String> = instruction
+                .bytes
+                .iter()
+                .map(|byte| format!("{:#04X}", byte))
+                .collect();
+            let code: Vec<String> = statements(instruction, &image.target);
+            imports(&code, &mut uses);
+
+            let _ = writeln!(functions);
+            let _ = writeln!(
+                functions,
+                "    // ${:04X} {}",
+                instruction.address,
+                instruction.assembly()
+            );
+            let _ = writeln!(
+                functions,
+                "    if step(processor, memory, {:#06X}, &[{}], deadline, executed) == false {{",
+                instruction.address,
+                bytes.join(", ")
+            );
+            let _ = writeln!(functions, "        return Err(executed);");
+            let _ = writeln!(functions, "    }}");
+            for line in code.iter() {
+                let _ = writeln!(functions, "    {}", line);
+            }
+            let _ = writeln!(functions, "    executed += 1;");
+        }
+
+        let _ = writeln!(functions);
+        let _ = writeln!(functions, "    return Ok(executed);");
+        let _ = writeln!(functions, "}}");
+    }
+
+    let uses: String = uses
+        .iter()
+        .map(|import| format!("use {};\n", import))
+        .collect();
+    let mut out: String = HEADER
+        .replace("{variant}", image.target.name)
+        .replace("{size}", &image.bytes.len().to_string())
+        .replace("{load}", &format!("{:04X}", image.load_address))
+        .replace("{entry_points}", &entry_points.join(", "))
+        .replace("{blocks}", &blocks.len().to_string())
+        .replace("{instructions}", &instructions.to_string())
+        .replace("{name}", &image.name)
+        .replace("{uses}", &uses)
+        .replace("{arms}", &arms);
+    out.push_str(&functions);
+
+    return out;
+}
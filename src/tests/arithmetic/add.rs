@@ -21,7 +21,7 @@ fn test_add(data: AddStruct, opcode: u8) -> () {
     let mut expected_cycles: u32 = 4;
     let (mut memory, mut processor) = setup();
 
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     processor.accumulator = data.accumulator;
     processor.set_status(ZeroFlag, !data.expect_zero);
@@ -83,7 +83,7 @@ fn test_add(data: AddStruct, opcode: u8) -> () {
     }
 
     processor.cycles = expected_cycles;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, data.answer);
     verify_flag(&processor, CarryFlag, data.expect_carry);
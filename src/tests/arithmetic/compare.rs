@@ -19,7 +19,7 @@ fn test_compare(data: CompareStruct, opcode: u8) -> () {
     let mut expected_cycles: u32 = 4;
     let (mut memory, mut processor) = setup();
 
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     match data.register {
         Accumulator => processor.accumulator = data.register_value,
@@ -81,7 +81,7 @@ fn test_compare(data: CompareStruct, opcode: u8) -> () {
     }
 
     processor.cycles = expected_cycles;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_flag(&processor, CarryFlag, data.expect_carry);
     verify_flag(&processor, NegativeFlag, data.expect_negative);
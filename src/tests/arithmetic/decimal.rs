@@ -0,0 +1,222 @@
+use crate::cpu;
+use crate::tests::common::*;
+
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+pub struct DecimalStruct {
+    carry: bool,
+    accumulator: u8,
+    operand: u8,
+    answer: u8,
+    expect_carry: bool,
+    expect_negative: bool,
+    expect_overflow: bool,
+    expect_zero: bool,
+}
+
+fn test_decimal(data: DecimalStruct, opcode: u8, variant: Variant) -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup();
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.variant = variant;
+
+    processor.accumulator = data.accumulator;
+    processor.set_status(DecimalMode, true);
+    processor.set_status(ZeroFlag, !data.expect_zero);
+    processor.set_status(NegativeFlag, !data.expect_negative);
+    processor.set_status(OverflowFlag, !data.expect_overflow);
+    processor.set_status(CarryFlag, data.carry);
+
+    memory.data[0xFF00] = opcode;
+    memory.data[0xFF01] = data.operand;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, data.answer);
+    verify_flag(&processor, CarryFlag, data.expect_carry);
+    verify_flag(&processor, NegativeFlag, data.expect_negative);
+    verify_flag(&processor, OverflowFlag, data.expect_overflow);
+    verify_flag(&processor, ZeroFlag, data.expect_zero);
+    verify_flag(&processor, DecimalMode, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn test_add_decimal() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: false,
+            accumulator: 0x09,
+            operand: 0x01,
+            answer: 0x10,
+            expect_carry: false,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_add_decimal_carry() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: true,
+            accumulator: 0x58,
+            operand: 0x46,
+            answer: 0x05, // 58 + 46 + 1 = 105 - N and V come from the intermediate sum 0xA5
+            expect_carry: true,
+            expect_negative: true,
+            expect_overflow: true,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_add_decimal_nmos_flags() -> () {
+    // 99 + 01 = 00 with carry, but the NMOS Z flag follows the binary sum (0x9A) and N follows the unadjusted high nibble
+    test_decimal(
+        DecimalStruct {
+            carry: false,
+            accumulator: 0x99,
+            operand: 0x01,
+            answer: 0x00,
+            expect_carry: true,
+            expect_negative: true,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_add_decimal_overflow() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: true,
+            accumulator: 0x79,
+            operand: 0x00,
+            answer: 0x80,
+            expect_carry: false,
+            expect_negative: true,
+            expect_overflow: true,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_add_decimal_invalid_digits() -> () {
+    // 0x0F is not valid BCD - the low nibble sum (0x14) is adjusted once by 6 and truncated, leaving 0x1A as on hardware
+    test_decimal(
+        DecimalStruct {
+            carry: false,
+            accumulator: 0x0F,
+            operand: 0x05,
+            answer: 0x1A,
+            expect_carry: false,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_add_decimal_ignored() -> () {
+    // The 2A03 has no BCD logic, so the sum stays binary even with the decimal flag set
+    test_decimal(
+        DecimalStruct {
+            carry: false,
+            accumulator: 0x09,
+            operand: 0x01,
+            answer: 0x0A,
+            expect_carry: false,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        ADC_IMMEDIATE,
+        Variant::Ricoh2A03,
+    );
+}
+
+pub fn test_subtract_decimal() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: true,
+            accumulator: 0x46,
+            operand: 0x12,
+            answer: 0x34,
+            expect_carry: true,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        SBC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_subtract_decimal_borrow() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: false,
+            accumulator: 0x32,
+            operand: 0x02,
+            answer: 0x29, // 32 - 02 - 1 = 29
+            expect_carry: true,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        SBC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_subtract_decimal_negative() -> () {
+    // 12 - 21 = 91 with borrow - N, V, Z and C all follow the binary result (0xF1)
+    test_decimal(
+        DecimalStruct {
+            carry: true,
+            accumulator: 0x12,
+            operand: 0x21,
+            answer: 0x91,
+            expect_carry: false,
+            expect_negative: true,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        SBC_IMMEDIATE,
+        Variant::Nmos6502,
+    );
+}
+
+pub fn test_subtract_decimal_ignored() -> () {
+    test_decimal(
+        DecimalStruct {
+            carry: true,
+            accumulator: 0x10,
+            operand: 0x01,
+            answer: 0x0F,
+            expect_carry: true,
+            expect_negative: false,
+            expect_overflow: false,
+            expect_zero: false,
+        },
+        SBC_IMMEDIATE,
+        Variant::Ricoh2A03,
+    );
+}
@@ -1,3 +1,4 @@
 pub mod add;
 pub mod compare;
+pub mod decimal;
 pub mod subtract;
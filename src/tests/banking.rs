@@ -0,0 +1,244 @@
+use super::common::*;
+use crate::banking::*;
+use crate::cpu;
+use crate::map::*;
+use crate::mem::*;
+
+use cpu::blocks::Engine;
+use cpu::debugger::BankedAddress;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::tracer::{TraceRecord, Tracer};
+use cpu::variant::Variant;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const BANK_SIZE: u32 = 0x2000;
+const BANK_REGISTER: u32 = 0xDE00;
+
+fn cartridge() -> Vec<u8> {
+    /*
+        Four 8KB banks. Each starts with LDY #bank; RTS. At $8010 bank 0 switches to bank 1
+        under its own feet, where the next opcode is an INX instead of a NOP.
+    */
+    let mut image: Vec<u8> = vec![0xFF; 4 * BANK_SIZE as usize];
+    for bank in 0..4 {
+        let base: usize = bank * BANK_SIZE as usize;
+        image[base] = LDY_IMMEDIATE;
+        image[base + 1] = bank as u8;
+        image[base + 2] = RTS;
+        image[base + 0x10..base + 0x17].copy_from_slice(&[
+            LDA_IMMEDIATE,
+            0x01,
+            STA_ABSOLUTE,
+            0x00,
+            0xDE,
+            NOP,
+            RTS,
+        ]);
+    }
+    image[BANK_SIZE as usize + 0x15] = INX;
+    return image;
+}
+
+fn setup_cartridge() -> (MemoryMap, Processor, Rc<RefCell<Banked>>) {
+    /* The cartridge seen through $8000-$9FFF, its bank latched from the low two bits written to $DE00 */
+    let mut map = MemoryMap::new();
+    let rom = Rc::new(RefCell::new(Banked::rom(&cartridge(), BANK_SIZE)));
+    let mut register = BankRegister::new();
+    register.controls(
+        rom.clone(),
+        Select::Data {
+            mask: 0x03,
+            shift: 0,
+        },
+    );
+    map.attach(0x8000, 0x9FFF, rom.clone());
+    map.attach(BANK_REGISTER, BANK_REGISTER, register);
+
+    let mut processor = Processor::new(Variant::Nmos6502);
+    reset_to(&mut processor, &mut map, 0x0200);
+    return (map, processor, rom);
+}
+
+fn load_caller(map: &mut MemoryMap) -> () {
+    /* Calls bank 0 and bank 1 at $8000, then the self-switching code in bank 0, forever */
+    let program: [u8; 30] = [
+        LDA_IMMEDIATE,
+        0x00,
+        STA_ABSOLUTE,
+        0x00,
+        0xDE,
+        JSR,
+        0x00,
+        0x80,
+        STY_ZERO_PAGE,
+        0x10,
+        LDA_IMMEDIATE,
+        0x01,
+        STA_ABSOLUTE,
+        0x00,
+        0xDE,
+        JSR,
+        0x00,
+        0x80,
+        STY_ZERO_PAGE,
+        0x11,
+        LDA_IMMEDIATE,
+        0x00,
+        STA_ABSOLUTE,
+        0x00,
+        0xDE,
+        JSR,
+        0x10,
+        0x80,
+        JMP_ABSOLUTE,
+        0x00,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        map.poke(0x0200 + offset as u32, *byte);
+    }
+    map.poke(0x021E, 0x02);
+}
+
+pub fn switched_windows() -> () {
+    let (mut map, mut processor, rom) = setup_cartridge();
+    assert_eq!(rom.borrow().banks(), 4);
+
+    // Bank 6 does not exist, the select lines past bank 3 are not connected
+    map.poke(0x0200, LDA_IMMEDIATE);
+    map.poke(0x0201, 0x02);
+    map.poke(0x0202, STA_ABSOLUTE);
+    map.poke(0x0203, 0x00);
+    map.poke(0x0204, 0xDE);
+    map.poke(0x0205, LDX_ABSOLUTE);
+    map.poke(0x0206, 0x01);
+    map.poke(0x0207, 0x80);
+    map.poke(0x0208, STX_ABSOLUTE);
+    map.poke(0x0209, 0x01);
+    map.poke(0x020A, 0x80);
+    processor.cycles = 2 + 4 + 4 + 4;
+    processor.execute(&mut map);
+
+    verify_register(&processor, RegisterX, 0x02);
+    assert_eq!(rom.borrow().selected(), 2);
+    assert_eq!(map.peek(BANK_REGISTER), 0x02);
+    assert_eq!(map.peek(0x8001), 0x02); // The store into ROM went nowhere
+    rom.borrow_mut().select(6);
+    assert_eq!(map.peek(0x8001), 0x02);
+
+    // 8KB of extra RAM in two banks at $C000, switched like soft switches by touching $C004 or $C005
+    let ram = Rc::new(RefCell::new(Banked::ram(2, 0x1000)));
+    let mut switches = BankRegister::new();
+    switches.controls(ram.clone(), Select::Offset);
+    map.attach(0xC000, 0xCFFF, ram.clone());
+    map.attach(0xC004, 0xC005, switches);
+
+    map.poke(0x0300, LDA_IMMEDIATE);
+    map.poke(0x0301, 0x11);
+    map.poke(0x0302, STA_ABSOLUTE);
+    map.poke(0x0303, 0x00);
+    map.poke(0x0304, 0xC1);
+    map.poke(0x0305, STA_ABSOLUTE);
+    map.poke(0x0306, 0x05);
+    map.poke(0x0307, 0xC0);
+    map.poke(0x0308, LDA_IMMEDIATE);
+    map.poke(0x0309, 0x22);
+    map.poke(0x030A, STA_ABSOLUTE);
+    map.poke(0x030B, 0x00);
+    map.poke(0x030C, 0xC1);
+    map.poke(0x030D, STA_ABSOLUTE);
+    map.poke(0x030E, 0x04);
+    map.poke(0x030F, 0xC0);
+    map.poke(0x0310, LDY_ABSOLUTE);
+    map.poke(0x0311, 0x00);
+    map.poke(0x0312, 0xC1);
+    processor.program_counter = 0x0300;
+    processor.cycles = 2 + 4 + 4 + 2 + 4 + 4 + 4;
+    processor.execute(&mut map);
+
+    verify_register(&processor, RegisterY, 0x11);
+    assert_eq!(ram.borrow().data[0x0100], 0x11);
+    assert_eq!(ram.borrow().data[0x1100], 0x22);
+    assert_eq!(map.ram.data[0xC100], 0x00);
+}
+
+pub fn bank_names() -> () {
+    assert_eq!(
+        BankedAddress::parse("03:8000"),
+        Some(BankedAddress {
+            bank: 3,
+            address: 0x8000
+        })
+    );
+    assert_eq!(
+        BankedAddress::parse(" $1F:$A000 "),
+        Some(BankedAddress {
+            bank: 0x1F,
+            address: 0xA000
+        })
+    );
+    assert_eq!(BankedAddress::parse("8000"), None);
+    assert_eq!(BankedAddress::parse("03:80G0"), None);
+    assert_eq!(
+        BankedAddress::parse("03:8000").unwrap().to_string(),
+        "03:8000"
+    );
+
+    // The breakpoint only stops the call that lands in bank 1
+    let (mut map, mut processor, _) = setup_cartridge();
+    load_caller(&mut map);
+    assert_eq!(map.bank(0x8000), Some(0));
+    assert_eq!(map.bank(0x0200), None);
+    let breakpoint: BankedAddress = BankedAddress::parse("01:8000").unwrap();
+    processor.debugger.banked_breakpoints.insert(breakpoint);
+
+    let lines: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    let sink = lines.clone();
+    processor.tracer = Some(Tracer::new(move |record: &TraceRecord| {
+        sink.borrow_mut().push(record.to_string());
+    }));
+
+    processor.cycles = 1000;
+    let result: ExecutionResult = processor.execute(&mut map);
+    assert_eq!(result.stop, StopReason::Breakpoint { address: 0x8000 });
+    assert_eq!(map.peek(0x0010), 0x00);
+    assert_eq!(map.bank(0x8000), Some(1));
+
+    // Opcodes under a switched window are traced by bank, everything else by address alone
+    let lines = lines.borrow();
+    assert!(lines[0].starts_with("0200  A9 00"), "{}", lines[0]);
+    assert!(lines[3].starts_with("00:8000  A0 00"), "{}", lines[3]);
+    assert!(lines[4].starts_with("00:8002  60"), "{}", lines[4]);
+}
+
+fn run_caller(engine: Engine, budgets: &[u32]) -> (MemoryMap, Processor) {
+    let (mut map, mut processor, _) = setup_cartridge();
+    load_caller(&mut map);
+    processor.engine = engine;
+    for budget in budgets.iter() {
+        processor.cycles = *budget;
+        processor.execute(&mut map);
+    }
+    return (map, processor);
+}
+
+pub fn engines_switch_banks() -> () {
+    // The blocks decoded from one bank must not run once another is switched in
+    let budgets: [u32; 5] = [50, 3, 200, 17, 500];
+    let (interpreted_map, interpreted) = run_caller(Engine::Interpreter, &budgets);
+    let (map, processor) = run_caller(Engine::Blocks, &budgets);
+
+    assert_eq!(map.peek(0x0010), 0x00);
+    assert_eq!(map.peek(0x0011), 0x01);
+    assert!(processor.register_x > 3);
+    assert!(processor.blocks.statistics.invalidated > 0);
+
+    assert_eq!(processor.clock, interpreted.clock);
+    assert_eq!(processor.program_counter, interpreted.program_counter);
+    verify_register(&processor, RegisterX, interpreted.register_x);
+    verify_register(&processor, RegisterY, interpreted.register_y);
+    assert_eq!(map.peek(0x0010), interpreted_map.peek(0x0010));
+    assert_eq!(map.peek(0x0011), interpreted_map.peek(0x0011));
+}
@@ -0,0 +1,216 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::blocks::*;
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+fn run_with(
+    variant: Variant,
+    engine: Engine,
+    program: &[(u16, u8)],
+    budgets: &[u32],
+    irq: bool,
+) -> (Memory, Processor, u64) {
+    /* Loads the program, starts at its first byte, then hands execute() one budget after another */
+    let (mut memory, mut processor) = setup_variant(variant);
+    reset_to(&mut processor, &mut memory, program[0].0);
+    processor.engine = engine;
+    if irq {
+        processor.interrupts.assert_irq(0);
+        processor.set_status(InterruptDisable, true);
+    }
+    for (address, byte) in program.iter() {
+        memory.data[(*address as u32 & variant.address_mask()) as usize] = *byte;
+    }
+
+    let mut instructions: u64 = 0;
+    for budget in budgets.iter() {
+        processor.cycles = *budget;
+        instructions += processor.execute(&mut memory).instructions;
+    }
+    return (memory, processor, instructions);
+}
+
+fn verify_engines_agree(
+    variant: Variant,
+    program: &[(u16, u8)],
+    budgets: &[u32],
+    irq: bool,
+) -> (Memory, Processor) {
+    let (interpreted_memory, interpreted, interpreted_count) =
+        run_with(variant, Engine::Interpreter, program, budgets, irq);
+    let (memory, processor, count) = run_with(variant, Engine::Blocks, program, budgets, irq);
+
+    assert_eq!(
+        count, interpreted_count,
+        "the engines ran a different number of instructions"
+    );
+    assert_eq!(
+        processor.clock, interpreted.clock,
+        "the engines ran a different number of cycles"
+    );
+    assert_eq!(processor.cycles, interpreted.cycles);
+    verify_program_counter(&processor, interpreted.program_counter);
+    verify_register(&processor, Accumulator, interpreted.accumulator);
+    verify_register(&processor, RegisterX, interpreted.register_x);
+    verify_register(&processor, RegisterY, interpreted.register_y);
+    assert_eq!(processor.status, interpreted.status);
+    assert_eq!(processor.stack_pointer, interpreted.stack_pointer);
+    assert!(
+        memory.data == interpreted_memory.data,
+        "the engines left memory different"
+    );
+
+    return (memory, processor);
+}
+
+pub fn block_decoding() -> () {
+    #[rustfmt::skip]
+    let program: [(u16, u8); 8] = [
+        (0x0200, LDA_IMMEDIATE), (0x0201, 0x01),
+        (0x0202, STA_ZERO_PAGE), (0x0203, 0x10),
+        (0x0204, INX),
+        (0x0205, BNE), (0x0206, 0xF9),
+        (0x0207, NOP),
+    ];
+    let (_, processor, _) = run_with(
+        Variant::Nmos6502,
+        Engine::Blocks,
+        &program,
+        &[2 * (2 + 3 + 2 + 3)],
+        false,
+    );
+
+    // The branch ends the block, the NOP after it is never part of it
+    let block: &Block = processor.blocks.block(0x0200).unwrap();
+    assert_eq!(block.end, 0x0207);
+    assert_eq!(
+        block.instructions,
+        vec![
+            (0x0200, LDA_IMMEDIATE),
+            (0x0202, STA_ZERO_PAGE),
+            (0x0204, INX),
+            (0x0205, BNE)
+        ]
+    );
+    // Decoded on the first pass and taken from the cache on the second
+    assert_eq!(processor.blocks.statistics.decoded, 1);
+    assert_eq!(processor.blocks.statistics.entered, 1);
+}
+
+pub fn engines_agree() -> () {
+    #[rustfmt::skip]
+    let program: [(u16, u8); 30] = [
+        (0x0200, LDX_IMMEDIATE), (0x0201, 0x08),
+        (0x0202, LDA_ABSOLUTE_X), (0x0203, 0x00), (0x0204, 0x03),
+        (0x0205, ADC_IMMEDIATE), (0x0206, 0x11),
+        (0x0207, STA_ABSOLUTE_X), (0x0208, 0x10), (0x0209, 0x03),
+        (0x020A, DEX),
+        (0x020B, BNE), (0x020C, 0xF5),
+        (0x020D, CLI),
+        (0x020E, NOP),
+        (0x020F, NOP),
+        (0x0210, JMP_ABSOLUTE), (0x0211, 0x0D), (0x0212, 0x02),
+        (0x0400, INC_ABSOLUTE), (0x0401, 0x20), (0x0402, 0x03),
+        (0x0403, PLA),
+        (0x0404, OR_IMMEDIATE), (0x0405, 0x04), // Return with I set, the next CLI lets one more in
+        (0x0406, PHA),
+        (0x0407, RTI),
+        (0xFFFE, 0x00), (0xFFFF, 0x04),
+        (0x0305, 0x40),
+    ];
+
+    // Odd budgets leave the deadline in the middle of blocks
+    let budgets: [u32; 8] = [37, 1, 53, 7, 101, 3, 64, 200];
+    let (memory, _) = verify_engines_agree(Variant::Nmos6502, &program, &budgets, false);
+    verify_memory(&memory, 0x0315, 0x51);
+    verify_memory(&memory, 0x0320, 0x00);
+
+    // IRQ is held the whole time, so interrupts land between instructions of a running block
+    let (memory, _) = verify_engines_agree(Variant::Nmos6502, &program, &budgets, true);
+    assert!(memory.data[0x0320] > 0, "the handler never ran");
+}
+
+pub fn self_modifying() -> () {
+    #[rustfmt::skip]
+    let program: [(u16, u8); 18] = [
+        (0x0200, LDA_IMMEDIATE), (0x0201, INX),
+        (0x0202, STA_ABSOLUTE), (0x0203, 0x06), (0x0204, 0x02),
+        (0x0205, NOP),
+        (0x0206, NOP),
+        (0x0207, NOP),
+        (0x0208, LDA_IMMEDIATE), (0x0209, INY),
+        (0x020A, STA_ABSOLUTE), (0x020B, 0x07), (0x020C, 0x02),
+        (0x020D, CPX_IMMEDIATE), (0x020E, 0x02),
+        (0x020F, BNE), (0x0210, 0xEF),
+        (0x0211, NOP),
+    ];
+
+    // The first store lands inside the block that is running, the second in one that ran before
+    let (_, processor) = verify_engines_agree(Variant::Nmos6502, &program, &[200], false);
+    verify_register(&processor, RegisterX, 0x02);
+    verify_register(&processor, RegisterY, 0x01);
+    assert!(processor.blocks.statistics.invalidated >= 2);
+
+    // A 6507 running from $F000 rewrites a loop it has cached through the $1000 mirror the bus sees
+    #[rustfmt::skip]
+    let program: [(u16, u8); 16] = [
+        (0xF000, LDY_IMMEDIATE), (0xF001, 0x0A),
+        (0xF002, NOP),
+        (0xF003, DEY),
+        (0xF004, BNE), (0xF005, 0xFC),
+        (0xF006, LDA_IMMEDIATE), (0xF007, INX),
+        (0xF008, STA_ABSOLUTE), (0xF009, 0x02), (0xF00A, 0x10),
+        (0xF00B, LDY_IMMEDIATE), (0xF00C, 0x0A),
+        (0xF00D, JMP_ABSOLUTE), (0xF00E, 0x02), (0xF00F, 0xF0),
+    ];
+    const ROUND: u32 = 10 * (2 + 2 + 3) - 1;
+    let budgets: [u32; 1] = [2 + ROUND + (2 + 4 + 2 + 3) + ROUND];
+    let (_, processor) = verify_engines_agree(Variant::Mos6507, &program, &budgets, false);
+    verify_register(&processor, RegisterX, 0x0A);
+}
+
+pub fn host_changes() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor.engine = Engine::Blocks;
+
+    memory.data[0x0200] = INX;
+    memory.data[0x0201] = JMP_ABSOLUTE;
+    memory.data[0x0202] = 0x00;
+    memory.data[0x0203] = 0x02;
+
+    processor.cycles = 2 + 3;
+    processor.execute(&mut memory);
+    verify_register(&processor, RegisterX, 0x01);
+    assert_eq!(processor.blocks.len(), 1);
+
+    // Memory changed behind the processor's back is only seen once the host drops the cache
+    memory.data[0x0200] = INY;
+    processor.blocks.clear();
+    processor.cycles = 2 + 3;
+    processor.execute(&mut memory);
+    verify_register(&processor, RegisterX, 0x01);
+    verify_register(&processor, RegisterY, 0x01);
+}
+
+pub fn breakpoint_in_block() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor.engine = Engine::Blocks;
+    processor.debugger.breakpoints.insert(0x0203);
+    for address in 0x0200..0x0210 {
+        memory.data[address] = INX;
+    }
+
+    processor.cycles = 100;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::Breakpoint { address: 0x0203 });
+    assert_eq!(result.instructions, 3);
+    verify_register(&processor, RegisterX, 0x03);
+}
@@ -13,14 +13,14 @@ pub fn branch_if_equal() -> () {
     processor.set_status(ZeroFlag, true);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
 }
 
 pub fn branch_if_equal_cross() -> () {
-    const EXPECTED_CYCLES: u32 = 5;
+    const EXPECTED_CYCLES: u32 = 4; // One dummy read for the taken branch, one for the page fix-up
     const VALUE: i8 = 0x7f;
     const PROGRAM: [u8; 4] = [0x7F, 0xF0, BEQ, VALUE as u8];
     let (mut memory, mut processor) = setup();
@@ -28,7 +28,7 @@ pub fn branch_if_equal_cross() -> () {
     processor.set_status(ZeroFlag, true);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xF100);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -43,7 +43,7 @@ pub fn branch_if_equal_backwards() -> () {
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.set_status(ZeroFlag, true);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFFCC);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -56,7 +56,7 @@ pub fn branch_if_not_equal() -> () {
 
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -70,7 +70,7 @@ pub fn branch_if_carry_set() -> () {
     processor.set_status(CarryFlag, true);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -84,7 +84,7 @@ pub fn branch_if_carry_clear() -> () {
     processor.set_status(CarryFlag, false);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -98,7 +98,7 @@ pub fn branch_if_overflow_set() -> () {
     processor.set_status(OverflowFlag, true);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -112,7 +112,7 @@ pub fn branch_if_overflow_clear() -> () {
     processor.set_status(OverflowFlag, false);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -126,7 +126,7 @@ pub fn branch_if_negative_set() -> () {
     processor.set_status(NegativeFlag, true);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -140,7 +140,7 @@ pub fn branch_if_negative_clear() -> () {
     processor.set_status(NegativeFlag, false);
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF05);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
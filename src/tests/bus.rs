@@ -0,0 +1,105 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::stepper::Stepping;
+use cpu::variant::Variant;
+
+const STATUS_REGISTER: u32 = 0xD000;
+
+/// RAM with one device register that counts how often it has been read, like a status latch cleared on read.
+struct MappedBus {
+    ram: Memory,
+    reads: u8,
+    written: Vec<u8>,
+}
+
+impl MappedBus {
+    fn new() -> MappedBus {
+        return MappedBus {
+            ram: Memory::new(),
+            reads: 0,
+            written: Vec::new(),
+        };
+    }
+}
+
+impl Bus for MappedBus {
+    fn read(&mut self, address: u32) -> u8 {
+        if address == STATUS_REGISTER {
+            self.reads += 1;
+            return self.reads;
+        }
+        return self.ram.read(address);
+    }
+
+    fn write(&mut self, address: u32, data: u8) -> () {
+        if address == STATUS_REGISTER {
+            self.written.push(data);
+            return;
+        }
+        self.ram.write(address, data);
+    }
+
+    fn peek(&self, address: u32) -> u8 {
+        if address == STATUS_REGISTER {
+            return self.reads;
+        }
+        return self.ram.peek(address);
+    }
+}
+
+pub fn mapped_register() -> () {
+    const EXPECTED_CYCLES: u32 = 4 + 4;
+    let mut bus = MappedBus::new();
+    let mut processor = Processor::new(Variant::Nmos6502);
+    reset_to(&mut processor, &mut bus, 0xFF00);
+
+    bus.poke(0xFF00, LDA_ABSOLUTE);
+    bus.poke(0xFF01, 0x00);
+    bus.poke(0xFF02, 0xD0);
+    bus.poke(0xFF03, STA_ABSOLUTE);
+    bus.poke(0xFF04, 0x00);
+    bus.poke(0xFF05, 0xD0);
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut bus).cycles;
+
+    verify_register(&processor, Accumulator, 0x01);
+    assert_eq!(bus.written, vec![0x01]);
+    assert_eq!(bus.reads, 1);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn stepped_side_effects() -> () {
+    let mut bus = MappedBus::new();
+    let mut processor = Processor::new(Variant::Nmos6502);
+    reset_to(&mut processor, &mut bus, 0xFF00);
+
+    bus.poke(0xFF00, LDA_ABSOLUTE);
+    bus.poke(0xFF01, 0x00);
+    bus.poke(0xFF02, 0xD0);
+
+    // Replayed and discarded cycles only peek, so the device sees the one real read
+    for _ in 0..4 {
+        processor.tick(&mut bus);
+    }
+
+    verify_register(&processor, Accumulator, 0x01);
+    assert_eq!(bus.reads, 1);
+}
+
+pub fn flat_ram() -> () {
+    let mut memory = Memory::new();
+
+    memory.poke(0x1234, 0x56);
+    memory.write(0x02_0000, 0x78); // Above bank 0, allocated on first use
+
+    assert_eq!(memory.peek(0x1234), 0x56);
+    assert_eq!(memory.read(0x02_0000), 0x78);
+    assert_eq!(memory.data[0x1234], 0x56);
+    assert_eq!(memory.data.len(), MAX_MEMORY);
+}
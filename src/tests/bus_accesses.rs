@@ -0,0 +1,193 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::observer::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn record(processor: &mut Processor) -> Rc<RefCell<Vec<BusAccess>>> {
+    let accesses: Rc<RefCell<Vec<BusAccess>>> = Rc::new(RefCell::new(Vec::new()));
+    let recorder = accesses.clone();
+    processor.bus_observer = Some(BusObserver::new(move |access| {
+        recorder.borrow_mut().push(access)
+    }));
+    return accesses;
+}
+
+fn verify_accesses(accesses: &[BusAccess], expected: &[(u32, BusOperation, u8)]) -> () {
+    assert_eq!(accesses.len(), expected.len(), "Wrong number of bus cycles");
+    for (cycle, (address, operation, data)) in expected.iter().enumerate() {
+        assert_eq!(
+            (
+                accesses[cycle].address,
+                accesses[cycle].operation,
+                accesses[cycle].data
+            ),
+            (*address, *operation, *data),
+            "Cycle {} does not match",
+            cycle
+        );
+    }
+}
+
+pub fn indexed_dummy_read() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x20;
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE_X;
+    memory.data[0xFF01] = 0xF0;
+    memory.data[0xFF02] = 0x20;
+    memory.data[0x2010] = 0x11;
+    memory.data[0x2110] = 0x22;
+
+    processor.cycles = 5;
+    processor.execute(&mut memory);
+
+    // The fix-up cycle reads $2010 before the carry reaches the high byte
+    verify_accesses(
+        &accesses.borrow(),
+        &[
+            (0xFF00, BusOperation::Read, LDA_ABSOLUTE_X),
+            (0xFF01, BusOperation::Read, 0xF0),
+            (0xFF02, BusOperation::Read, 0x20),
+            (0x2010, BusOperation::Read, 0x11),
+            (0x2110, BusOperation::Read, 0x22),
+        ],
+    );
+}
+
+pub fn cmos_indexed_dummy_read() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x20;
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE_X;
+    memory.data[0xFF01] = 0xF0;
+    memory.data[0xFF02] = 0x20;
+
+    processor.cycles = 5;
+    processor.execute(&mut memory);
+
+    // CMOS parts stay off the half-carried address and re-read the operand instead
+    assert_eq!(accesses.borrow()[3].address, 0xFF02);
+    assert_eq!(accesses.borrow()[4].address, 0x2110);
+}
+
+pub fn modify_accesses() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x01;
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = ASL_ABSOLUTE_X;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x30;
+    memory.data[0x3001] = 0x21;
+
+    processor.cycles = 7;
+    processor.execute(&mut memory);
+
+    verify_accesses(
+        &accesses.borrow(),
+        &[
+            (0xFF00, BusOperation::Read, ASL_ABSOLUTE_X),
+            (0xFF01, BusOperation::Read, 0x00),
+            (0xFF02, BusOperation::Read, 0x30),
+            (0x3001, BusOperation::Read, 0x21), // Writes and read-modify-writes always take the fix-up read
+            (0x3001, BusOperation::Read, 0x21),
+            (0x3001, BusOperation::Write, 0x21),
+            (0x3001, BusOperation::Write, 0x42),
+        ],
+    );
+
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = INC_ZERO_PAGE;
+    memory.data[0xFF01] = 0x10;
+    memory.data[0x0010] = 0x7F;
+
+    processor.cycles = 5;
+    processor.execute(&mut memory);
+
+    // The 65C02 reads the location twice and only writes the result
+    verify_accesses(
+        &accesses.borrow(),
+        &[
+            (0xFF00, BusOperation::Read, INC_ZERO_PAGE),
+            (0xFF01, BusOperation::Read, 0x10),
+            (0x0010, BusOperation::Read, 0x7F),
+            (0x0010, BusOperation::Read, 0x7F),
+            (0x0010, BusOperation::Write, 0x80),
+        ],
+    );
+}
+
+pub fn subroutine_accesses() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = JSR;
+    memory.data[0xFF01] = 0x34;
+    memory.data[0xFF02] = 0x12;
+    memory.data[0x1234] = RTS;
+    memory.data[0x1235] = NOP;
+
+    processor.cycles = 12;
+    processor.execute(&mut memory);
+
+    verify_program_counter(&processor, 0xFF03);
+    verify_accesses(
+        &accesses.borrow(),
+        &[
+            (0xFF00, BusOperation::Read, JSR),
+            (0xFF01, BusOperation::Read, 0x34),
+            (0x01FF, BusOperation::Read, 0x00),
+            (0x01FF, BusOperation::Write, 0xFF), // High byte of the return address first
+            (0x01FE, BusOperation::Write, 0x02),
+            (0xFF02, BusOperation::Read, 0x12),
+            (0x1234, BusOperation::Read, RTS),
+            (0x1235, BusOperation::Read, NOP),
+            (0x01FD, BusOperation::Read, 0x00),
+            (0x01FE, BusOperation::Read, 0x02),
+            (0x01FF, BusOperation::Read, 0xFF),
+            (0xFF02, BusOperation::Read, 0x12),
+        ],
+    );
+}
+
+pub fn break_accesses() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    let accesses = record(&mut processor);
+
+    memory.data[0xFF00] = BRK;
+    memory.data[0xFF01] = 0xEA;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.cycles = 7;
+    processor.execute(&mut memory);
+
+    verify_accesses(
+        &accesses.borrow(),
+        &[
+            (0xFF00, BusOperation::Read, BRK),
+            (0xFF01, BusOperation::Read, 0xEA),
+            (0x01FF, BusOperation::Write, 0xFF),
+            (0x01FE, BusOperation::Write, 0x02),
+            (0x01FD, BusOperation::Write, 0x30),
+            (0xFFFE, BusOperation::Read, 0x00),
+            (0xFFFF, BusOperation::Read, 0x80),
+        ],
+    );
+}
@@ -0,0 +1,370 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::Memory;
+
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+fn setup_cmos() -> (Memory, Processor) {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    return (memory, processor);
+}
+
+pub fn branch_always() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = BRA;
+    memory.data[0xFF01] = 0x10;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0xFF12);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn push_x_pull_y() -> () {
+    const EXPECTED_CYCLES: u32 = 3 + 4;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.register_x = 0x84;
+
+    memory.data[0xFF00] = PHX;
+    memory.data[0xFF01] = PLY;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x01FF, 0x84);
+    verify_register(&processor, RegisterY, 0x84);
+    verify_flag(&processor, NegativeFlag, true);
+    assert_eq!(
+        processor.stack_pointer, 0xFF,
+        "Stack pointer was not restored"
+    );
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn push_y_pull_x() -> () {
+    const EXPECTED_CYCLES: u32 = 3 + 4;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.register_y = 0x42;
+
+    memory.data[0xFF00] = PHY;
+    memory.data[0xFF01] = PLX;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, RegisterX, 0x42);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn store_zero() -> () {
+    const EXPECTED_CYCLES: u32 = 3 + 5;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.register_x = 0x02;
+
+    memory.data[0xFF00] = STZ_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = STZ_ABSOLUTE_X;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = 0x80;
+    memory.data[0x0042] = 0xFF;
+    memory.data[0x8002] = 0xFF;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0x00);
+    verify_memory(&memory, 0x8002, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn test_and_reset_bits() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0b0000_1111;
+
+    memory.data[0xFF00] = TRB_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0x0042] = 0b1111_0000;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0b1111_0000);
+    verify_flag(&processor, ZeroFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn test_and_set_bits() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0b0000_1111;
+
+    memory.data[0xFF00] = TSB_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8000] = 0b0001_1000;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8000, 0b0001_1111);
+    verify_flag(&processor, ZeroFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn zero_page_indirect() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = LDA_ZERO_PAGE_INDIRECT;
+    memory.data[0xFF01] = 0xFF; // pointer wraps around the zero page: low byte at 0xFF, high byte at 0x00
+    memory.data[0x00FF] = 0x34;
+    memory.data[0x0000] = 0x12;
+    memory.data[0x1234] = 0x42;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x42);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn bit_immediate() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0x01;
+
+    memory.data[0xFF00] = BIT_IMMEDIATE;
+    memory.data[0xFF01] = 0xC0;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_flag(&processor, ZeroFlag, true);
+    verify_flag(&processor, NegativeFlag, false);
+    verify_flag(&processor, OverflowFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn bit_absolute_x() -> () {
+    const EXPECTED_CYCLES: u32 = 4;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0x01;
+    processor.register_x = 0x01;
+
+    memory.data[0xFF00] = BIT_ABSOLUTE_X;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8001] = 0xC1;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_flag(&processor, ZeroFlag, false);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_flag(&processor, OverflowFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn increment_decrement_accumulator() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 2 + 2;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0xFF;
+
+    memory.data[0xFF00] = INC_ACCUMULATOR;
+    memory.data[0xFF01] = DEC_ACCUMULATOR;
+    memory.data[0xFF02] = DEC_ACCUMULATOR;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0xFE);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn jump_indexed_indirect() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.register_x = 0x04;
+
+    memory.data[0xFF00] = JMP_ABSOLUTE_INDEXED_INDIRECT;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8004] = 0x00;
+    memory.data[0x8005] = 0x90;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x9000);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn jump_indirect_page_boundary() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = JMP_INDIRECT;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0x80; // the 65C02 reads the high byte from 0x8100, not 0x8000
+    memory.data[0x80FF] = 0x00;
+    memory.data[0x8100] = 0x90;
+    memory.data[0x8000] = 0x70;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x9000);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn reset_and_set_memory_bits() -> () {
+    const EXPECTED_CYCLES: u32 = 5 + 5;
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = RMB7;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = SMB0;
+    memory.data[0xFF03] = 0x42;
+    memory.data[0x0042] = 0b1000_0000;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0b0000_0001);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn branch_on_bits() -> () {
+    const EXPECTED_CYCLES: u32 = 6 + 5;
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = BBS3;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = 0x04; // taken: 0xFF03 + 4
+    memory.data[0xFF07] = BBR3;
+    memory.data[0xFF08] = 0x42;
+    memory.data[0xFF09] = 0x10; // not taken
+    memory.data[0x0042] = 0b0000_1000;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0xFF0A);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn wait_and_stop() -> () {
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = WAI;
+    memory.data[0xFF01] = LDA_IMMEDIATE;
+    memory.data[0xFF02] = 0x42;
+
+    processor.cycles = 10;
+    processor.execute(&mut memory);
+
+    assert_eq!(processor.state, ExecutionState::Waiting, "WAI did not wait");
+    verify_program_counter(&processor, 0xFF01);
+    verify_register(&processor, Accumulator, 0x00);
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    memory.data[0xFF00] = STP;
+    memory.data[0xFF01] = LDA_IMMEDIATE;
+    memory.data[0xFF02] = 0x42;
+
+    processor.cycles = 10;
+    verify_cycles(processor.execute(&mut memory).cycles, 3);
+    assert_eq!(processor.state, ExecutionState::Stopped, "STP did not stop");
+
+    processor.cycles = 10; // a stopped processor lets the whole budget elapse
+    verify_cycles(processor.execute(&mut memory).cycles, 10);
+    verify_register(&processor, Accumulator, 0x00);
+
+    processor.variant = Variant::Rockwell65C02; // Rockwell parts treat WAI and STP as single cycle NOPs
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    memory.data[0xFF00] = STP;
+    memory.data[0xFF01] = LDA_IMMEDIATE;
+    memory.data[0xFF02] = 0x42;
+
+    processor.cycles = 3;
+    verify_cycles(processor.execute(&mut memory).cycles, 3);
+    verify_register(&processor, Accumulator, 0x42);
+}
+
+pub fn decimal_flags() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0x99;
+    processor.set_status(DecimalMode, true);
+
+    memory.data[0xFF00] = ADC_IMMEDIATE;
+    memory.data[0xFF01] = 0x01;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x00);
+    verify_flag(&processor, ZeroFlag, true);
+    verify_flag(&processor, NegativeFlag, false);
+    verify_flag(&processor, CarryFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+
+    let (mut memory, mut processor) = setup_cmos();
+    processor.accumulator = 0x12;
+    processor.set_status(DecimalMode, true);
+    processor.set_status(CarryFlag, true);
+
+    memory.data[0xFF00] = SBC_IMMEDIATE;
+    memory.data[0xFF01] = 0x21;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x91);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_flag(&processor, CarryFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn interrupt_clears_decimal() -> () {
+    const EXPECTED_CYCLES: u32 = 7;
+    let (mut memory, mut processor) = setup_cmos();
+    processor.set_status(DecimalMode, true);
+
+    memory.data[0xFF00] = BRK;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_flag(&processor, DecimalMode, false);
+    verify_memory(&memory, 0x01FD, 0b0011_1000); // the pushed status still has D set
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn undefined_nops() -> () {
+    let (mut memory, mut processor) = setup_cmos();
+
+    memory.data[0xFF00] = 0x03; // 1 byte, 1 cycle
+    memory.data[0xFF01] = 0x02; // 2 bytes, 2 cycles
+    memory.data[0xFF03] = 0xDC; // 3 bytes, 4 cycles
+    memory.data[0xFF06] = 0x5C; // 3 bytes, 8 cycles
+
+    processor.cycles = 1 + 2 + 4 + 8;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0xFF09);
+    verify_cycles(cycles, 1 + 2 + 4 + 8);
+}
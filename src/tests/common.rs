@@ -4,27 +4,57 @@ use crate::mem::*;
 use cpu::opcodes::ProcessorStatus::{self, *};
 use cpu::opcodes::Registers::{self, *};
 use cpu::processor::*;
+use cpu::variant::Variant;
+
+use std::cell::Cell;
+
+pub const VARIANTS: [Variant; 8] = [
+    Variant::Nmos6502,
+    Variant::Nmos6502RevA,
+    Variant::Mos6507,
+    Variant::Mos6510,
+    Variant::Ricoh2A03,
+    Variant::Rockwell65C02,
+    Variant::Wdc65C02,
+    Variant::Wdc65C816,
+];
+
+/// The variants the instruction groups are run against, the 65C816 in emulation mode.
+pub const SUITE_VARIANTS: [Variant; 2] = [Variant::Nmos6502, Variant::Wdc65C816];
+
+thread_local! {
+    static SUITE_VARIANT: Cell<Variant> = const { Cell::new(Variant::Nmos6502) };
+}
+
+pub fn suite_variant() -> Variant {
+    return SUITE_VARIANT.with(|variant| variant.get());
+}
+
+pub fn set_suite_variant(variant: Variant) -> () {
+    SUITE_VARIANT.with(|current| current.set(variant));
+}
 
 pub fn setup() -> (Memory, Processor) {
-    let mut memory = Memory {
-        data: [0; MAX_MEMORY],
-    };
-
-    let mut processor = Processor {
-        program_counter: 0,
-        stack_pointer: 0,
-        accumulator: 0,
-        register_x: 0,
-        register_y: 0,
-        status: 0,
-        cycles: 0,
-    };
-
-    processor.reset(&mut memory, 0xFFFC);
+    return setup_variant(suite_variant());
+}
+
+pub fn setup_variant(variant: Variant) -> (Memory, Processor) {
+    let mut memory = Memory::new();
+
+    let mut processor = Processor::new(variant);
+    reset_to(&mut processor, &mut memory, 0xFFFC);
 
     return (memory, processor);
 }
 
+pub fn reset_to<B: Bus>(processor: &mut Processor, memory: &mut B, address: u16) -> () {
+    /* Runs a real reset, then settles the registers into the clean state the tests are written against */
+    processor.reset(memory);
+    processor.program_counter = address;
+    processor.stack_pointer = 0xFF;
+    processor.status = 0;
+}
+
 pub fn verify_register(processor: &Processor, register: Registers, expected: u8) -> () {
     match register {
         Accumulator => {
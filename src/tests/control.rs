@@ -0,0 +1,141 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::observer::*;
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::stepper::Stepping;
+use cpu::variant::Variant;
+
+pub fn ready_holds_reads() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x02;
+    memory.data[0x0200] = 0x42;
+
+    processor.tick(&mut memory);
+    processor.tick(&mut memory);
+    processor.control.set_ready(false);
+
+    // The fetch of the high byte is repeated for as long as RDY stays low
+    for _ in 0..3 {
+        let access: BusAccess = processor.tick(&mut memory);
+        assert_eq!(access.address, 0xFF02);
+        assert_eq!(access.operation, BusOperation::Read);
+    }
+    assert_eq!(processor.stolen_cycles, 3);
+    verify_register(&processor, Accumulator, 0x00);
+
+    processor.control.set_ready(true);
+    processor.tick(&mut memory);
+    processor.tick(&mut memory);
+
+    verify_register(&processor, Accumulator, 0x42);
+    verify_program_counter(&processor, 0xFF03);
+    assert_eq!(processor.stepper.elapsed(), 7);
+    assert!(processor.stepper.mid_instruction() == false);
+}
+
+pub fn writes_continue() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x42;
+
+    memory.data[0xFF00] = STA_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x02;
+    memory.data[0xFF03] = NOP;
+
+    for _ in 0..3 {
+        processor.tick(&mut memory);
+    }
+    processor.control.set_ready(false);
+
+    // RDY only stops the processor on a read, so the store still lands
+    let access: BusAccess = processor.tick(&mut memory);
+    assert_eq!(access.operation, BusOperation::Write);
+    verify_memory(&memory, 0x0200, 0x42);
+    assert_eq!(processor.stolen_cycles, 0);
+
+    let access: BusAccess = processor.tick(&mut memory);
+    assert_eq!(access.address, 0xFF03);
+    assert_eq!(processor.stolen_cycles, 1);
+    verify_program_counter(&processor, 0xFF03);
+}
+
+pub fn stolen_budget() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = NOP;
+
+    // A DMA transfer holding RDY for its whole length takes the budget without running anything
+    processor.control.set_ready(false);
+    processor.cycles = 513;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_cycles(cycles, 0);
+    verify_program_counter(&processor, 0xFF00);
+    assert_eq!(processor.stolen_cycles, 513);
+
+    processor.control.set_ready(true);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+    verify_program_counter(&processor, 0xFF01);
+    assert_eq!(processor.stolen_cycles, 513);
+}
+
+pub fn set_overflow_edge() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    // The 1541 waits for a byte from its read head with BVC * and lets SO set V
+    memory.data[0xFF00] = BVC;
+    memory.data[0xFF01] = 0xFE;
+    memory.data[0xFF02] = CLV;
+    memory.data[0xFF03] = BVC;
+    memory.data[0xFF04] = 0xFE;
+
+    processor.cycles = 9;
+    processor.execute(&mut memory);
+    verify_program_counter(&processor, 0xFF00);
+
+    processor.control.assert_set_overflow();
+    processor.cycles = 2 + 2;
+    processor.execute(&mut memory);
+    verify_program_counter(&processor, 0xFF03);
+    verify_flag(&processor, OverflowFlag, false);
+
+    // Holding the pin low does not set V again after CLV
+    assert!(processor.control.set_overflow_asserted());
+    processor.cycles = 9;
+    processor.execute(&mut memory);
+    verify_program_counter(&processor, 0xFF03);
+
+    processor.control.release_set_overflow();
+    processor.control.assert_set_overflow();
+    processor.cycles = 2;
+    processor.execute(&mut memory);
+    verify_program_counter(&processor, 0xFF05);
+}
+
+pub fn missing_set_overflow() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = NOP;
+
+    processor.control.assert_set_overflow();
+    processor.cycles = 2;
+    processor.execute(&mut memory);
+
+    verify_flag(&processor, OverflowFlag, false);
+}
@@ -0,0 +1,225 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::observer::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+pub fn budget_exhausted() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = NOP;
+    memory.data[0xFF01] = LDA_ABSOLUTE;
+    memory.data[0xFF02] = 0x00;
+    memory.data[0xFF03] = 0x02;
+
+    // The load starts with one cycle of budget left and runs three past it
+    let clock: u64 = processor.clock;
+    processor.cycles = 3;
+    let result: ExecutionResult = processor.execute(&mut memory);
+
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+    assert_eq!(result.instructions, 2);
+    verify_cycles(result.cycles, 6);
+    verify_program_counter(&processor, 0xFF04);
+
+    // The clock keeps every cycle run, the budget has nothing left
+    assert_eq!(processor.clock, clock + 6);
+    assert_eq!(processor.cycles, 0);
+}
+
+pub fn faults() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = NOP;
+    memory.data[0xFF01] = JAM_02;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(
+        result.stop,
+        StopReason::Jammed {
+            opcode: JAM_02,
+            address: 0xFF01
+        }
+    );
+    assert_eq!(result.instructions, 2);
+
+    // Later calls keep reporting the jam while the budget elapses
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert!(matches!(result.stop, StopReason::Jammed { .. }));
+    assert_eq!(result.instructions, 0);
+    verify_cycles(result.cycles, 20);
+
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.illegal_opcode_policy = IllegalOpcodePolicy::Halt;
+
+    memory.data[0xFF00] = LAX_ZERO_PAGE;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(
+        result.stop,
+        StopReason::IllegalOpcode {
+            opcode: LAX_ZERO_PAGE,
+            address: 0xFF00
+        }
+    );
+    verify_cycles(result.cycles, 1);
+}
+
+pub fn breakpoint() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.debugger.breakpoints.insert(0xFF01);
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = NOP;
+
+    // The operand byte is not an instruction boundary, so nothing stops there
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.debugger.breakpoints.insert(0xFF02);
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = JMP_ABSOLUTE;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = 0xFF;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::Breakpoint { address: 0xFF02 });
+    assert_eq!(result.instructions, 1);
+    verify_cycles(result.cycles, 2);
+    verify_program_counter(&processor, 0xFF02);
+
+    // Resuming runs the instruction under the breakpoint, the loop then comes back around to it
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::Breakpoint { address: 0xFF02 });
+    assert_eq!(result.instructions, 2);
+    verify_cycles(result.cycles, 3 + 2);
+}
+
+pub fn watchpoint() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x42;
+    processor
+        .debugger
+        .watch(0x0200, 0x02FF, BusOperation::Write);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE;
+    memory.data[0xFF01] = 0x10;
+    memory.data[0xFF02] = 0x02;
+    memory.data[0xFF03] = STA_ABSOLUTE;
+    memory.data[0xFF04] = 0x20;
+    memory.data[0xFF05] = 0x02;
+    memory.data[0xFF06] = NOP;
+    memory.data[0x0210] = 0x99;
+
+    // Reads of the watched range pass, the store is reported once it has finished
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(
+        result.stop,
+        StopReason::Watchpoint {
+            access: BusAccess {
+                address: 0x0220,
+                data: 0x99,
+                operation: BusOperation::Write,
+                sync: false,
+            }
+        }
+    );
+    assert_eq!(result.instructions, 2);
+    verify_cycles(result.cycles, 4 + 4);
+    verify_program_counter(&processor, 0xFF06);
+    verify_memory(&memory, 0x0220, 0x99);
+}
+
+pub fn break_trap() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.debugger.trap_break = true;
+
+    memory.data[0xFF00] = LDX_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = BRK;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::BreakTrap { address: 0xFF02 });
+    assert_eq!(
+        processor.stack_pointer, 0xFF,
+        "the trapped BRK pushed to the stack"
+    );
+    verify_register(&processor, RegisterX, 0x42);
+    verify_program_counter(&processor, 0xFF02);
+
+    // Resuming without the trap lets the BRK through to its vector
+    processor.debugger.trap_break = false;
+    processor.cycles = 7;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+    verify_program_counter(&processor, 0x8000);
+}
+
+pub fn halt_requested() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = NOP;
+    memory.data[0xFF01] = NOP;
+
+    // The handle is meant to be moved out to whatever decides the host wants control back
+    let handle = processor.debugger.halt_handle();
+    std::thread::spawn(move || handle.request()).join().unwrap();
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::HaltRequested);
+    assert_eq!(result.instructions, 0);
+    verify_cycles(result.cycles, 0);
+
+    // A request is used up by the stop it causes
+    processor.cycles = 4;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+    assert_eq!(result.instructions, 2);
+}
+
+pub fn parked() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = WAI;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::Waiting);
+    verify_cycles(result.cycles, 3);
+
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = STP;
+
+    processor.cycles = 20;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::Stopped);
+}
@@ -11,7 +11,7 @@ pub fn check_flag_change(opcode: u8) -> () {
     let flag: ProcessorStatus;
     let expected: bool;
 
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     match opcode {
         CLC => {
@@ -50,7 +50,7 @@ pub fn check_flag_change(opcode: u8) -> () {
     memory.data[0xFF00] = opcode;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_flag(&processor, flag, expected);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
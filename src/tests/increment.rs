@@ -9,13 +9,13 @@ use cpu::processor::*;
 pub fn increment_x() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 132;
 
     memory.data[0xFF00] = INX;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterX, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -26,13 +26,13 @@ pub fn increment_x() -> () {
 pub fn increment_y() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_y = 132;
 
     memory.data[0xFF00] = INY;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterY, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -43,14 +43,14 @@ pub fn increment_y() -> () {
 pub fn increment_memory_zero_page() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = INC_ZERO_PAGE;
     memory.data[0xFF01] = 0x42;
     memory.data[0x42] = 132;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x42, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -61,7 +61,7 @@ pub fn increment_memory_zero_page() -> () {
 pub fn increment_memory_zero_page_x() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = INC_ZERO_PAGE_X;
@@ -69,7 +69,7 @@ pub fn increment_memory_zero_page_x() -> () {
     memory.data[0x47] = 132;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x47, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -80,7 +80,7 @@ pub fn increment_memory_zero_page_x() -> () {
 pub fn increment_memory_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = INC_ABSOLUTE;
     memory.data[0xFF01] = 0x00;
@@ -88,7 +88,7 @@ pub fn increment_memory_absolute() -> () {
     memory.data[0x8000] = 132;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8000, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -99,7 +99,7 @@ pub fn increment_memory_absolute() -> () {
 pub fn increment_memory_absolute_x() -> () {
     const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = INC_ABSOLUTE_X;
@@ -108,7 +108,7 @@ pub fn increment_memory_absolute_x() -> () {
     memory.data[0x8005] = 132;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8005, 133);
     verify_flag(&processor, ZeroFlag, false);
@@ -0,0 +1,347 @@
+use super::common::*;
+use crate::cpu;
+use crate::set_bit;
+
+use cpu::observer::*;
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::stepper::Stepping;
+use cpu::variant::Variant;
+
+pub fn irq_sequence() -> () {
+    const EXPECTED_CYCLES: u32 = 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(CarryFlag, true);
+
+    memory.data[0xFF00] = NOP;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    let mut pushed_status: u8 = processor.status;
+    pushed_status = set_bit(pushed_status, 2, false);
+    pushed_status = set_bit(pushed_status, 5, true);
+
+    verify_program_counter(&processor, 0x8000);
+    verify_memory(&memory, 0x01FF, 0xFF);
+    verify_memory(&memory, 0x01FE, 0x00); // The interrupted instruction, not the one after it
+    verify_memory(&memory, 0x01FD, pushed_status); // B stays clear for hardware interrupts
+    verify_flag(&processor, InterruptDisable, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn irq_masked() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true);
+
+    memory.data[0xFF00] = NOP;
+
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0xFF01);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn wired_or_sources() -> () {
+    let (_memory, mut processor) = setup();
+
+    processor.interrupts.assert_irq(3);
+    processor.interrupts.assert_irq(1);
+    processor.interrupts.assert_irq(3);
+    assert_eq!(processor.interrupts.irq_sources(), vec![1, 3]);
+
+    processor.interrupts.release_irq(3);
+    assert!(
+        processor.interrupts.irq_asserted(),
+        "IRQ was released while source 1 still held it"
+    );
+    assert_eq!(processor.interrupts.irq_sources(), vec![1]);
+
+    processor.interrupts.release_irq(1);
+    assert!(
+        processor.interrupts.irq_asserted() == false,
+        "IRQ is held with no sources left"
+    );
+}
+
+pub fn nmi_edge() -> () {
+    const EXPECTED_CYCLES: u32 = 7 + 2;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true); // NMI cannot be masked
+
+    memory.data[0xFFFA] = 0x00;
+    memory.data[0xFFFB] = 0x90;
+    memory.data[0x9000] = NOP;
+
+    processor.interrupts.assert_nmi();
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    // The line is still held, but only the falling edge interrupts
+    assert!(
+        processor.interrupts.nmi_asserted(),
+        "NMI was released by servicing it"
+    );
+    verify_program_counter(&processor, 0x9001);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+
+    processor.interrupts.release_nmi();
+    processor.interrupts.assert_nmi();
+    processor.cycles = 7;
+    processor.execute(&mut memory);
+    verify_program_counter(&processor, 0x9000);
+}
+
+pub fn nmi_priority() -> () {
+    const EXPECTED_CYCLES: u32 = 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFFFA] = 0x00;
+    memory.data[0xFFFB] = 0x90;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.interrupts.assert_irq(0);
+    processor.interrupts.assert_nmi();
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x9000);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn wake_from_wait() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true);
+
+    memory.data[0xFF00] = WAI;
+    memory.data[0xFF01] = INX;
+
+    processor.cycles = 3;
+    processor.execute(&mut memory);
+    assert_eq!(processor.state, ExecutionState::Waiting);
+
+    // A masked IRQ still ends WAI, execution carries on without entering the handler
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert_eq!(processor.state, ExecutionState::Running);
+    verify_program_counter(&processor, 0xFF02);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn unconnected_lines() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6507);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0x1F00] = NOP;
+
+    processor.interrupts.assert_irq(0);
+    processor.interrupts.assert_nmi();
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0xFF01);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn cli_latency() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 2 + 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true);
+
+    memory.data[0xFF00] = CLI;
+    memory.data[0xFF01] = NOP;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    // CLI clears I after the poll, so the NOP behind it still runs before the handler
+    verify_program_counter(&processor, 0x8000);
+    verify_memory(&memory, 0x01FE, 0x02);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn sei_latency() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 2 + 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true);
+
+    memory.data[0xFF00] = CLI;
+    memory.data[0xFF01] = SEI;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    processor.execute(&mut memory);
+
+    // The poll at the end of SEI still sees I clear, the handler is entered with I already set on the stack
+    verify_program_counter(&processor, 0x8000);
+    verify_memory(&memory, 0x01FE, 0x02);
+    assert!(
+        memory.data[0x01FD] & 0x04 != 0,
+        "SEI had not set I in the pushed status"
+    );
+}
+
+pub fn plp_latency() -> () {
+    const EXPECTED_CYCLES: u32 = 4 + 2 + 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_status(InterruptDisable, true);
+    processor.stack_pointer = 0xFE;
+
+    memory.data[0xFF00] = PLP;
+    memory.data[0xFF01] = NOP;
+    memory.data[0x01FF] = 0x00;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    processor.interrupts.assert_irq(0);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x8000);
+    verify_memory(&memory, 0x01FE, 0x02);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn poll_before_last_cycle() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x02;
+    memory.data[0xFF03] = NOP;
+
+    // An IRQ that first shows on the final cycle is missed, the next instruction is fetched
+    for _ in 0..3 {
+        processor.tick(&mut memory);
+    }
+    processor.interrupts.assert_irq(0);
+    processor.tick(&mut memory);
+    let access: BusAccess = processor.tick(&mut memory);
+    assert!(access.sync, "The IRQ was seen on the last cycle of LDA");
+    assert_eq!(access.address, 0xFF03);
+
+    // One cycle earlier it is in time, the cycle after LDA starts the interrupt sequence
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.interrupts.release_irq(0);
+    for _ in 0..2 {
+        processor.tick(&mut memory);
+    }
+    processor.interrupts.assert_irq(0);
+    processor.tick(&mut memory);
+    processor.tick(&mut memory);
+    let access: BusAccess = processor.tick(&mut memory);
+    assert!(
+        access.sync == false,
+        "The IRQ was missed on the second to last cycle of LDA"
+    );
+    assert_eq!(access.address, 0xFF03);
+}
+
+pub fn branch_delay() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = BNE;
+    memory.data[0xFF01] = 0x02;
+    memory.data[0xFF04] = NOP;
+
+    // A taken branch on the same page polls before its second cycle, the IRQ waits out one more instruction
+    processor.tick(&mut memory);
+    processor.interrupts.assert_irq(0);
+    processor.tick(&mut memory);
+    processor.tick(&mut memory);
+    let access: BusAccess = processor.tick(&mut memory);
+    assert!(access.sync, "The IRQ was taken straight after the branch");
+    assert_eq!(access.address, 0xFF04);
+
+    processor.tick(&mut memory);
+    let access: BusAccess = processor.tick(&mut memory);
+    assert!(access.sync == false, "The IRQ was not taken after the NOP");
+
+    // Crossing a page costs a cycle and polls as usual
+    reset_to(&mut processor, &mut memory, 0xFFF0);
+    processor.interrupts.release_irq(0);
+    memory.data[0xFFF0] = BNE;
+    memory.data[0xFFF1] = 0x80;
+
+    processor.tick(&mut memory);
+    processor.interrupts.assert_irq(0);
+    for _ in 0..3 {
+        processor.tick(&mut memory);
+    }
+    let access: BusAccess = processor.tick(&mut memory);
+    assert!(
+        access.sync == false,
+        "The IRQ was delayed by a page crossing branch"
+    );
+}
+
+pub fn nmi_hijacks_break() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = BRK;
+    memory.data[0xFFFA] = 0x00;
+    memory.data[0xFFFB] = 0x90;
+    memory.data[0xFFFE] = 0x00;
+    memory.data[0xFFFF] = 0x80;
+
+    // Latched before the status is pushed, the NMI takes the vector and B still goes out set
+    for _ in 0..4 {
+        processor.tick(&mut memory);
+    }
+    processor.interrupts.assert_nmi();
+    for _ in 0..3 {
+        processor.tick(&mut memory);
+    }
+
+    verify_program_counter(&processor, 0x9000);
+    assert!(
+        memory.data[0x01FD] & 0x10 != 0,
+        "B was cleared by the hijack"
+    );
+    assert!(
+        processor.interrupts.nmi_pending() == false,
+        "The hijacking NMI is still pending"
+    );
+
+    // One cycle later BRK keeps its own vector and the NMI waits
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.interrupts.release_nmi();
+    for _ in 0..5 {
+        processor.tick(&mut memory);
+    }
+    processor.interrupts.assert_nmi();
+    for _ in 0..2 {
+        processor.tick(&mut memory);
+    }
+
+    verify_program_counter(&processor, 0x8000);
+    assert!(processor.interrupts.nmi_pending(), "The late NMI was lost");
+}
@@ -0,0 +1,147 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::io_port::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+pub fn port_registers() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 3 + 2 + 3 + 3;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6510);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x2F;
+    memory.data[0xFF02] = STA_ZERO_PAGE;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = LDA_IMMEDIATE;
+    memory.data[0xFF05] = 0x37;
+    memory.data[0xFF06] = STA_ZERO_PAGE;
+    memory.data[0xFF07] = 0x01;
+    memory.data[0xFF08] = LDX_ZERO_PAGE;
+    memory.data[0xFF09] = 0x00;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    let port = processor.io_port.as_ref().unwrap();
+    assert_eq!(
+        port.direction, 0x2F,
+        "Data direction register was not written"
+    );
+    assert_eq!(port.output, 0x37, "Port register was not written");
+    verify_register(&processor, RegisterX, 0x2F);
+    verify_memory(&memory, 0x0000, 0x00); // The port sits in front of RAM
+    verify_memory(&memory, 0x0001, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn port_inputs() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6510);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    let port = processor.io_port.as_mut().unwrap();
+    port.write(DATA_DIRECTION_ADDRESS, 0x07);
+    port.write(PORT_ADDRESS, 0x05);
+    port.input = 0xEF; // Something is holding P4 low
+
+    memory.data[0xFF00] = LDA_ZERO_PAGE;
+    memory.data[0xFF01] = 0x01;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x2D);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn output_callback() -> () {
+    const EXPECTED_CYCLES: u32 = 5 + 5;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6510);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    let lines = Rc::new(Cell::new(0x00));
+    let observed = lines.clone();
+    processor
+        .io_port
+        .as_mut()
+        .unwrap()
+        .on_output(move |value| observed.set(value));
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x07;
+    memory.data[0xFF02] = STA_ZERO_PAGE;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = LDA_IMMEDIATE;
+    memory.data[0xFF05] = 0x06;
+    memory.data[0xFF06] = STA_ZERO_PAGE;
+    memory.data[0xFF07] = 0x01;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert_eq!(
+        lines.get(),
+        0xFE,
+        "Banking lines were {:#X} when they should be 0xFE",
+        lines.get()
+    );
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn fading_bits() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6510);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    let port = processor.io_port.as_mut().unwrap();
+    port.fade_cycles = 10;
+    port.write(DATA_DIRECTION_ADDRESS, 0xC0);
+    port.write(PORT_ADDRESS, 0xC0);
+    port.write(DATA_DIRECTION_ADDRESS, 0x00);
+
+    assert_eq!(
+        port.read(PORT_ADDRESS),
+        0xFF,
+        "Released pins did not hold their charge"
+    );
+
+    memory.data[0xFF00] = LDA_ZERO_PAGE;
+    memory.data[0xFF01] = 0x01;
+    for index in 0..4 {
+        memory.data[0xFF02 + index] = NOP;
+    }
+    memory.data[0xFF06] = LDX_ZERO_PAGE;
+    memory.data[0xFF07] = 0x01;
+
+    processor.cycles = 3 + 8 + 3;
+    processor.execute(&mut memory);
+
+    verify_register(&processor, Accumulator, 0xFF);
+    verify_register(&processor, RegisterX, 0x3F);
+}
+
+pub fn without_port() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup_variant(Variant::Nmos6502);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x42;
+
+    memory.data[0xFF00] = STA_ZERO_PAGE;
+    memory.data[0xFF01] = 0x01;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert!(
+        processor.io_port.is_none(),
+        "Only the 6510 has an on-chip port"
+    );
+    verify_memory(&memory, 0x0001, 0x42);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
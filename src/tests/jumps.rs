@@ -8,7 +8,7 @@ use cpu::processor::*;
 pub fn jump_subroutine_return() -> () {
     const EXPECTED_CYCLES: u32 = 14;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = JSR; // Jump to 0x8000
     memory.data[0xFF01] = 0x00;
@@ -18,7 +18,7 @@ pub fn jump_subroutine_return() -> () {
     memory.data[0xFF04] = 0x42;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     assert_eq!(
         processor.stack_pointer, 0xff,
@@ -32,14 +32,14 @@ pub fn jump_subroutine_return() -> () {
 pub fn jump_subroutine() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = JSR; // Jump to 0x8000
     memory.data[0xFF01] = 0x00;
     memory.data[0xFF02] = 0x80;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0x8000);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -48,14 +48,14 @@ pub fn jump_subroutine() -> () {
 pub fn jump_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 3;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = JMP_ABSOLUTE; // Jump to 0x8000
     memory.data[0xFF01] = 0x00;
     memory.data[0xFF02] = 0x80;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0x8000);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -64,7 +64,7 @@ pub fn jump_absolute() -> () {
 pub fn jump_indirect() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = JMP_INDIRECT; // Takes next two bytes and combines into word to form pointer
     memory.data[0xFF01] = 0x00;
@@ -73,7 +73,7 @@ pub fn jump_indirect() -> () {
     memory.data[0x8001] = 0x90; // Sets program counter to 0x9000
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0x9000);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -30,7 +30,7 @@ pub fn test_logic_immediate(operation: LogicalOperations) -> () {
     memory.data[0xFFFC] = opcode;
     memory.data[0xFFFD] = 0x84;
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -56,7 +56,7 @@ pub fn test_logic_zero_page(operation: LogicalOperations) -> () {
 
     processor.cycles = EXPECTED_CYCLES;
     processor.accumulator = 0xCC;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -84,7 +84,7 @@ pub fn test_logic_zero_page_x(operation: LogicalOperations) -> () {
     processor.register_x = 5;
     processor.accumulator = 0xCC;
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -111,7 +111,7 @@ pub fn test_logic_absolute(operation: LogicalOperations) -> () {
 
     processor.cycles = EXPECTED_CYCLES;
     processor.accumulator = 0xCC;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -145,7 +145,7 @@ pub fn test_logic_absolute_register(opcode: u8, register_to_set: Registers) -> (
 
     processor.accumulator = 0xCC;
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -175,7 +175,7 @@ pub fn test_logic_indirect_x(operation: LogicalOperations) -> () {
     memory.data[0x0007] = 0x80;
     memory.data[0x8000] = 0x84; // takes pointers at 0x06 and 0x07 to form address 0x8000
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -205,7 +205,7 @@ pub fn test_logic_indirect_y(operation: LogicalOperations) -> () {
     memory.data[0x0003] = 0x80;
     memory.data[0x8004] = 0x84; // 0x8000 + 0x4
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(
         &processor,
@@ -226,7 +226,7 @@ pub fn test_bit_zero_page() -> () {
 
     processor.cycles = EXPECTED_CYCLES;
     processor.accumulator = 0xCC;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
     verify_flag(&processor, ZeroFlag, false);
@@ -245,7 +245,7 @@ pub fn test_bit_absolute() -> () {
 
     processor.cycles = EXPECTED_CYCLES;
     processor.accumulator = 0xCC;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
     verify_flag(&processor, ZeroFlag, true);
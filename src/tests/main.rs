@@ -4,23 +4,46 @@ use crate::tests;
 use cpu::opcodes::LogicalOperations::*;
 use cpu::opcodes::Registers::*;
 use cpu::opcodes::*;
+use cpu::variant::Variant;
 
 use load::*;
 use store::*;
 use tests::arithmetic::*;
+use tests::common::{set_suite_variant, SUITE_VARIANTS};
 use tests::registers::*;
 
+use tests::banking;
+use tests::blocks;
 use tests::branches;
+use tests::bus;
+use tests::bus_accesses;
+use tests::cmos;
+use tests::control;
+use tests::debugger;
 use tests::decrement;
 use tests::flags;
 use tests::increment;
+use tests::interrupts;
+use tests::io_port;
 use tests::jumps;
 use tests::logical;
+use tests::memory_map;
+use tests::mos6507;
+use tests::open_bus;
+use tests::recompiler;
+use tests::regions;
 use tests::rotates;
 use tests::shifts;
 use tests::stackops;
+use tests::stepping;
 use tests::system;
+use tests::timing;
+use tests::tracer;
 use tests::transfers;
+use tests::undocumented;
+use tests::variants;
+use tests::w65c816;
+use tests::wraparound;
 
 use tests::programs::{functional_test::*, test::*};
 
@@ -31,12 +54,15 @@ pub fn run_programs() {
     println!("Please enter a key depending on which you wish to run: ");
     println!("1) 6502 Test Program");
     println!("2) 6502 Functional Test");
+    println!("3) 6502 Test Program, traced");
     let mut stdin_buffer = String::new();
     match io::stdin().read_line(&mut stdin_buffer) {
         Ok(_n) => {
             println!("\n\n");
             if stdin_buffer.contains("1") {
-                test_program();
+                test_program(false);
+            } else if stdin_buffer.contains("3") {
+                test_program(true);
             } else {
                 functional_program_test()
             }
@@ -47,9 +73,8 @@ pub fn run_programs() {
     println!("TEST PROGRAM      PASSED");
 }
 
-pub fn run() {
-    // Rust inbuilt tests not used as they clutter the output and are hard to read if a test fails
-    println!("6502 TEST SUITE");
+fn run_instructions() -> () {
+    /* The instruction groups, run once for each variant setup() builds for them */
     lda::immediate();
     println!("LDA IMMEDIATE     PASSED");
     lda::zero_page();
@@ -332,6 +357,21 @@ pub fn run() {
     println!("SBC INDIRECT      PASSED");
     println!("   SUBTRACT FULL PASS \n");
 
+    decimal::test_add_decimal();
+    decimal::test_add_decimal_carry();
+    decimal::test_add_decimal_nmos_flags();
+    decimal::test_add_decimal_overflow();
+    decimal::test_add_decimal_invalid_digits();
+    println!("ADC DECIMAL       PASSED");
+    decimal::test_subtract_decimal();
+    decimal::test_subtract_decimal_borrow();
+    decimal::test_subtract_decimal_negative();
+    println!("SBC DECIMAL       PASSED");
+    decimal::test_add_decimal_ignored();
+    decimal::test_subtract_decimal_ignored();
+    println!("DECIMAL IGNORED   PASSED");
+    println!("    DECIMAL FULL PASS \n");
+
     shifts::shift_left_accumulator();
     println!("ASL ACCUMULATOR   PASSED");
     shifts::shift_left_zero_page();
@@ -367,6 +407,8 @@ pub fn run() {
     rotates::rotate_right_zero_page();
     rotates::rotate_right_zero_page_x();
     println!("ROR ZERO PAGE     PASSED");
+    rotates::rotate_right_carry_in();
+    println!("ROR CARRY IN      PASSED");
     rotates::rotate_right_absolute();
     rotates::rotate_right_absolute_x();
     println!("ROR ABSOLUTE      PASSED");
@@ -377,3 +419,311 @@ pub fn run() {
     system::return_from_interrupt();
     println!("RTI               PASSED");
 }
+
+pub fn run() {
+    // Rust inbuilt tests not used as they clutter the output and are hard to read if a test fails
+    println!("6502 TEST SUITE");
+    for variant in SUITE_VARIANTS {
+        set_suite_variant(variant);
+        println!("{:?} INSTRUCTIONS \n", variant);
+        run_instructions();
+    }
+    set_suite_variant(Variant::Nmos6502);
+
+    system::reset_sequence();
+    println!("RESET             PASSED");
+    system::cmos_reset();
+    println!("CMOS RESET        PASSED");
+    system::power_on_patterns();
+    println!("POWER ON          PASSED");
+
+    undocumented::lax_zero_page();
+    undocumented::lax_indirect_y_page_cross();
+    println!("LAX               PASSED");
+    undocumented::sax_absolute();
+    println!("SAX               PASSED");
+    undocumented::slo_absolute_x();
+    undocumented::rra_zero_page();
+    undocumented::dcp_zero_page();
+    undocumented::isc_indirect_y();
+    println!("RMW COMBINED      PASSED");
+    undocumented::immediate_operations();
+    println!("IMMEDIATE COMBINED PASSED");
+    undocumented::unstable_magic();
+    undocumented::shx_page_cross();
+    undocumented::ahx_absolute_y();
+    println!("UNSTABLE          PASSED");
+    undocumented::multi_byte_nops();
+    println!("NOP VARIANTS      PASSED");
+    undocumented::jam();
+    println!("JAM               PASSED");
+    undocumented::policy_nop();
+    undocumented::policy_halt();
+    println!("ILLEGAL POLICY    PASSED");
+    println!("UNDOCUMENTED FULL PASS \n");
+
+    cmos::branch_always();
+    println!("BRA               PASSED");
+    cmos::push_x_pull_y();
+    cmos::push_y_pull_x();
+    println!("PHX PHY PLX PLY   PASSED");
+    cmos::store_zero();
+    println!("STZ               PASSED");
+    cmos::test_and_reset_bits();
+    cmos::test_and_set_bits();
+    println!("TRB TSB           PASSED");
+    cmos::zero_page_indirect();
+    println!("ZERO PAGE INDIRECT PASSED");
+    cmos::bit_immediate();
+    cmos::bit_absolute_x();
+    println!("BIT EXTENDED      PASSED");
+    cmos::increment_decrement_accumulator();
+    println!("INC DEC A         PASSED");
+    cmos::jump_indexed_indirect();
+    cmos::jump_indirect_page_boundary();
+    println!("JMP CMOS          PASSED");
+    cmos::reset_and_set_memory_bits();
+    cmos::branch_on_bits();
+    println!("RMB SMB BBR BBS   PASSED");
+    cmos::wait_and_stop();
+    println!("WAI STP           PASSED");
+    cmos::decimal_flags();
+    println!("DECIMAL CMOS      PASSED");
+    cmos::interrupt_clears_decimal();
+    println!("BRK CMOS          PASSED");
+    cmos::undefined_nops();
+    println!("CMOS NOPS         PASSED");
+    println!("65C02 FULL PASS \n");
+
+    variants::construction();
+    println!("CONSTRUCTION      PASSED");
+    variants::rev_a_rotate_right();
+    println!("REV A ROR         PASSED");
+    variants::indirect_jump_page_wrap();
+    println!("JMP PAGE WRAP     PASSED");
+    variants::cycle_tables();
+    println!("CYCLE TABLES      PASSED");
+    println!("VARIANTS FULL PASS \n");
+
+    io_port::port_registers();
+    println!("PORT REGISTERS    PASSED");
+    io_port::port_inputs();
+    println!("PORT INPUTS       PASSED");
+    io_port::output_callback();
+    println!("PORT CALLBACK     PASSED");
+    io_port::fading_bits();
+    println!("PORT FADING       PASSED");
+    io_port::without_port();
+    println!("NO PORT           PASSED");
+    println!("6510 FULL PASS \n");
+
+    mos6507::mirrored_addresses();
+    println!("MIRRORING         PASSED");
+    mos6507::observed_addresses();
+    println!("BUS OBSERVER      PASSED");
+    mos6507::interrupt_lines();
+    println!("INTERRUPT LINES   PASSED");
+    println!("6507 FULL PASS \n");
+
+    w65c816::exchange_carry_and_emulation();
+    println!("XCE               PASSED");
+    w65c816::register_widths();
+    println!("REGISTER WIDTHS   PASSED");
+    w65c816::wide_arithmetic();
+    println!("WIDE ARITHMETIC   PASSED");
+    w65c816::long_addressing();
+    println!("LONG ADDRESSING   PASSED");
+    w65c816::stack_relative();
+    println!("STACK RELATIVE    PASSED");
+    w65c816::block_move();
+    println!("BLOCK MOVE        PASSED");
+    w65c816::native_interrupts();
+    println!("NATIVE VECTORS    PASSED");
+    w65c816::exchange_accumulator();
+    println!("XBA               PASSED");
+    w65c816::relocated_direct_page();
+    println!("DIRECT PAGE       PASSED");
+    println!("65C816 FULL PASS \n");
+
+    interrupts::irq_sequence();
+    println!("IRQ SEQUENCE      PASSED");
+    interrupts::irq_masked();
+    println!("IRQ MASKED        PASSED");
+    interrupts::wired_or_sources();
+    println!("IRQ SOURCES       PASSED");
+    interrupts::nmi_edge();
+    println!("NMI EDGE          PASSED");
+    interrupts::nmi_priority();
+    println!("NMI PRIORITY      PASSED");
+    interrupts::wake_from_wait();
+    println!("WAI WAKE          PASSED");
+    interrupts::unconnected_lines();
+    println!("6507 LINES        PASSED");
+    interrupts::cli_latency();
+    println!("CLI LATENCY       PASSED");
+    interrupts::sei_latency();
+    println!("SEI LATENCY       PASSED");
+    interrupts::plp_latency();
+    println!("PLP LATENCY       PASSED");
+    interrupts::poll_before_last_cycle();
+    println!("POLL TIMING       PASSED");
+    interrupts::branch_delay();
+    println!("BRANCH DELAY      PASSED");
+    interrupts::nmi_hijacks_break();
+    println!("NMI HIJACK        PASSED");
+    println!("INTERRUPTS FULL PASS \n");
+
+    stepping::store_cycle();
+    println!("STORE CYCLE       PASSED");
+    stepping::live_reads();
+    println!("LIVE READS        PASSED");
+    stepping::internal_cycles();
+    println!("INTERNAL CYCLES   PASSED");
+    stepping::matches_execute();
+    println!("TICK VS EXECUTE   PASSED");
+    println!("STEPPING FULL PASS \n");
+
+    bus_accesses::indexed_dummy_read();
+    println!("INDEXED DUMMY     PASSED");
+    bus_accesses::cmos_indexed_dummy_read();
+    println!("CMOS DUMMY        PASSED");
+    bus_accesses::modify_accesses();
+    println!("MODIFY ACCESSES   PASSED");
+    bus_accesses::subroutine_accesses();
+    println!("JSR/RTS ACCESSES  PASSED");
+    bus_accesses::break_accesses();
+    println!("BRK ACCESSES      PASSED");
+    println!("BUS ACCESSES FULL PASS \n");
+
+    wraparound::indirect_jump_page();
+    println!("JMP ($xxFF)       PASSED");
+    wraparound::indirect_x_wrap();
+    println!("(ZP,X) WRAP       PASSED");
+    wraparound::indirect_y_wrap();
+    println!("(ZP),Y WRAP       PASSED");
+    wraparound::absolute_index_wrap();
+    println!("ABS,X WRAP        PASSED");
+    wraparound::stack_wrap();
+    println!("STACK WRAP        PASSED");
+    wraparound::return_address_wrap();
+    println!("RTS WRAP          PASSED");
+    println!("WRAPAROUND FULL PASS \n");
+
+    timing::penalty_rules();
+    println!("PENALTY RULES     PASSED");
+    timing::every_opcode();
+    println!("EVERY OPCODE      PASSED");
+    println!("TIMING FULL PASS \n");
+
+    control::ready_holds_reads();
+    println!("RDY HOLDS READS   PASSED");
+    control::writes_continue();
+    println!("RDY WRITES        PASSED");
+    control::stolen_budget();
+    println!("STOLEN CYCLES     PASSED");
+    control::set_overflow_edge();
+    println!("SO EDGE           PASSED");
+    control::missing_set_overflow();
+    println!("NO SO PIN         PASSED");
+    println!("CONTROL LINES FULL PASS \n");
+
+    bus::mapped_register();
+    println!("MAPPED REGISTER   PASSED");
+    bus::stepped_side_effects();
+    println!("STEPPED READS     PASSED");
+    bus::flat_ram();
+    println!("FLAT RAM          PASSED");
+    println!("BUS FULL PASS \n");
+
+    memory_map::mirrored_ram();
+    println!("MIRRORED RAM      PASSED");
+    memory_map::device_side_effects();
+    println!("DEVICE REGISTERS  PASSED");
+    memory_map::timer_interrupts();
+    println!("DEVICE IRQ        PASSED");
+    memory_map::read_only_device();
+    println!("READ-ONLY DEVICE  PASSED");
+    println!("MEMORY MAP FULL PASS \n");
+
+    regions::rom_writes();
+    println!("ROM WRITES        PASSED");
+    regions::log_limit();
+    println!("LOG LIMIT         PASSED");
+    regions::mirrored_regions();
+    println!("MIRRORED REGIONS  PASSED");
+    regions::write_once_stop();
+    println!("WRITE ONCE        PASSED");
+    regions::no_execute();
+    println!("NO EXECUTE        PASSED");
+    println!("REGIONS FULL PASS \n");
+
+    banking::switched_windows();
+    println!("SWITCHED WINDOWS  PASSED");
+    banking::bank_names();
+    println!("BANK:ADDR NAMES   PASSED");
+    banking::engines_switch_banks();
+    println!("BANKED BLOCKS     PASSED");
+    println!("BANKING FULL PASS \n");
+
+    open_bus::unmapped_reads();
+    println!("UNMAPPED READS    PASSED");
+    open_bus::write_leaves_value();
+    println!("BUS LEFT BEHIND   PASSED");
+    open_bus::region_decay();
+    println!("OPEN BUS DECAY    PASSED");
+    println!("OPEN BUS FULL PASS \n");
+
+    debugger::budget_exhausted();
+    println!("BUDGET EXHAUSTED  PASSED");
+    debugger::faults();
+    println!("FAULT STOPS       PASSED");
+    debugger::breakpoint();
+    println!("BREAKPOINT        PASSED");
+    debugger::watchpoint();
+    println!("WATCHPOINT        PASSED");
+    debugger::break_trap();
+    println!("BRK TRAP          PASSED");
+    debugger::halt_requested();
+    println!("HOST HALT         PASSED");
+    debugger::parked();
+    println!("WAI/STP STOPS     PASSED");
+    println!("STOP REASONS FULL PASS \n");
+
+    tracer::disassembly();
+    println!("DISASSEMBLY       PASSED");
+    tracer::cmos_disassembly();
+    println!("CMOS DISASSEMBLY  PASSED");
+    tracer::native_disassembly();
+    println!("65C816 DISASM     PASSED");
+    tracer::trace_lines();
+    println!("TRACE LINES       PASSED");
+    tracer::address_filter();
+    println!("TRACE FILTER      PASSED");
+    println!("TRACER FULL PASS \n");
+
+    blocks::block_decoding();
+    println!("BLOCK DECODING    PASSED");
+    blocks::engines_agree();
+    println!("ENGINES AGREE     PASSED");
+    blocks::self_modifying();
+    println!("SELF-MODIFYING    PASSED");
+    blocks::host_changes();
+    println!("HOST CHANGES      PASSED");
+    blocks::breakpoint_in_block();
+    println!("BLOCK BREAKPOINT  PASSED");
+    println!("BLOCK CACHE FULL PASS \n");
+
+    recompiler::static_blocks();
+    println!("STATIC BLOCKS     PASSED");
+    recompiler::generated_up_to_date();
+    println!("GENERATED SOURCE  PASSED");
+    recompiler::command_line();
+    println!("COMMAND LINE      PASSED");
+    recompiler::compiled_test_program();
+    println!("COMPILED PROGRAM  PASSED");
+    recompiler::compiled_functional_test();
+    println!("COMPILED KLAUS    PASSED");
+    recompiler::compiled_fallback();
+    println!("COMPILED FALLBACK PASSED");
+    println!("RECOMPILER FULL PASS \n");
+}
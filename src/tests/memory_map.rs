@@ -0,0 +1,297 @@
+use super::common::*;
+use crate::cpu;
+use crate::map::*;
+use crate::mem::*;
+
+use cpu::blocks::Engine;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A status register that clears when read, behind a data register that keeps what was written.
+struct Latch {
+    status: u8,
+    data: u8,
+    reads: u32,
+}
+
+impl Device for Latch {
+    fn read(&mut self, offset: u32) -> u8 {
+        self.reads += 1;
+        match offset {
+            0 => {
+                let status: u8 = self.status;
+                self.status = 0;
+                return status;
+            }
+            _ => return self.data,
+        }
+    }
+
+    fn write(&mut self, offset: u32, data: u8) -> () {
+        match offset {
+            0 => self.status = data,
+            _ => self.data = data,
+        }
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        match offset {
+            0 => return self.status,
+            _ => return self.data,
+        }
+    }
+}
+
+/// A ROM that only answers reads, with no way to look at it from outside.
+struct ReadOnly {
+    data: Vec<u8>,
+}
+
+impl Device for ReadOnly {
+    fn read(&mut self, offset: u32) -> u8 {
+        return self.data[offset as usize];
+    }
+
+    fn write(&mut self, _offset: u32, _data: u8) -> () {}
+}
+
+/// Counts down one a cycle and holds IRQ from reaching zero until its status register is read.
+struct Timer {
+    period: u32,
+    count: u32,
+    fired: bool,
+    ticks: u64,
+}
+
+impl Timer {
+    fn new(period: u32) -> Timer {
+        return Timer {
+            period,
+            count: period,
+            fired: false,
+            ticks: 0,
+        };
+    }
+}
+
+impl Device for Timer {
+    fn read(&mut self, offset: u32) -> u8 {
+        let status: u8 = self.peek(offset);
+        if offset == 0 {
+            self.fired = false;
+        }
+        return status;
+    }
+
+    fn write(&mut self, _offset: u32, data: u8) -> () {
+        self.period = data as u32;
+        self.count = data as u32;
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        match offset {
+            0 => return (self.fired as u8) << 7,
+            _ => return self.count as u8,
+        }
+    }
+
+    fn tick(&mut self, cycles: u32) -> () {
+        self.ticks += cycles as u64;
+        for _ in 0..cycles {
+            self.count -= 1;
+            if self.count == 0 {
+                self.fired = true;
+                self.count = self.period;
+            }
+        }
+    }
+
+    fn irq(&self) -> bool {
+        return self.fired;
+    }
+}
+
+fn setup_map() -> (MemoryMap, Processor) {
+    /* 2KB of RAM mirrored over $0000-$1FFF, flat RAM everywhere else */
+    let mut map = MemoryMap::new();
+    let ram: DeviceId = map.add(Ram::new(0x800));
+    map.map(0x0000, 0x1FFF, 0x07FF, ram);
+
+    let mut processor = Processor::new(Variant::Nmos6502);
+    reset_to(&mut processor, &mut map, 0x8000);
+    return (map, processor);
+}
+
+pub fn mirrored_ram() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 3 + 4 + 4;
+    let (mut map, mut processor) = setup_map();
+
+    let program: [u8; 10] = [
+        LDA_IMMEDIATE,
+        0x42,
+        STA_ZERO_PAGE,
+        0x10,
+        LDX_ABSOLUTE,
+        0x10,
+        0x18,
+        STX_ABSOLUTE,
+        0x00,
+        0x90,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        map.poke(0x8000 + offset as u32, *byte);
+    }
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut map).cycles;
+
+    // $1810 is the fourth mirror of $0010, and nothing under the mapping reaches the flat RAM
+    verify_register(&processor, RegisterX, 0x42);
+    assert_eq!(map.peek(0x0810), 0x42);
+    assert_eq!(map.ram.data[0x0010], 0x00);
+    assert_eq!(map.ram.data[0x9000], 0x42);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+
+    // A later mapping is laid over an earlier one
+    let window: DeviceId = map.add(Ram::new(0x10));
+    map.map(0x1800, 0x180F, u32::MAX, window);
+    assert_eq!(map.peek(0x1810), 0x42);
+    assert_eq!(map.peek(0x1800), 0x00);
+    assert_eq!(map.mappings().len(), 2);
+}
+
+pub fn device_side_effects() -> () {
+    const EXPECTED_CYCLES: u32 = 4 + 4 + 4;
+    let (mut map, mut processor) = setup_map();
+    let latch = Rc::new(RefCell::new(Latch {
+        status: 0x80,
+        data: 0x00,
+        reads: 0,
+    }));
+    let id: DeviceId = map.attach(0xD000, 0xD001, latch.clone());
+
+    let program: [u8; 9] = [
+        LDA_ABSOLUTE,
+        0x00,
+        0xD0,
+        LDX_ABSOLUTE,
+        0x00,
+        0xD0,
+        STA_ABSOLUTE,
+        0x01,
+        0xD0,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        map.poke(0x8000 + offset as u32, *byte);
+    }
+
+    // Looking at the register from outside leaves it set
+    assert_eq!(map.peek(0xD000), 0x80);
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut map).cycles;
+
+    verify_register(&processor, Accumulator, 0x80);
+    verify_register(&processor, RegisterX, 0x00);
+    assert_eq!(map.device(id).peek(1), 0x80);
+    assert_eq!(latch.borrow().reads, 2);
+    assert_eq!(map.ram.data[0xD001], 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+fn run_timer(engine: Engine, budgets: &[u32]) -> (MemoryMap, Processor, Rc<RefCell<Timer>>) {
+    /*
+        A main loop that spins while the handler counts timer interrupts in $10,
+        acknowledging each by reading the status register
+    */
+    let (mut map, mut processor) = setup_map();
+    let timer = Rc::new(RefCell::new(Timer::new(100)));
+    map.attach(0xD000, 0xD001, timer.clone());
+    processor.engine = engine;
+
+    let program: [(u32, u8); 12] = [
+        (0x8000, CLI),
+        (0x8001, INX),
+        (0x8002, JMP_ABSOLUTE),
+        (0x8003, 0x01),
+        (0x8004, 0x80),
+        (0x9000, INC_ZERO_PAGE),
+        (0x9001, 0x10),
+        (0x9002, BIT_ABSOLUTE),
+        (0x9003, 0x00),
+        (0x9004, 0xD0),
+        (0x9005, RTI),
+        (0xFFFF, 0x90),
+    ];
+    for (address, byte) in program.iter() {
+        map.poke(*address, *byte);
+    }
+    map.poke(0xFFFE, 0x00);
+
+    for budget in budgets.iter() {
+        processor.cycles = *budget;
+        processor.execute(&mut map);
+    }
+    return (map, processor, timer);
+}
+
+pub fn timer_interrupts() -> () {
+    let budgets: [u32; 5] = [7, 250, 1, 333, 409];
+    let (interpreted_map, interpreted, _) = run_timer(Engine::Interpreter, &budgets);
+    let (map, processor, timer) = run_timer(Engine::Blocks, &budgets);
+
+    // Attached after reset's last read caught the bus up to cycle 6, it has seen every cycle since
+    let interrupts: u8 = map.peek(0x0010);
+    assert_eq!(interrupts, 9);
+    assert_eq!(timer.borrow().ticks, processor.bus_clock - 6);
+    assert!(processor.clock - processor.bus_clock < 8);
+
+    assert_eq!(interrupts, interpreted_map.peek(0x0010));
+    assert_eq!(processor.clock, interpreted.clock);
+    assert_eq!(processor.program_counter, interpreted.program_counter);
+    verify_register(&processor, RegisterX, interpreted.register_x);
+}
+
+fn run_read_only(engine: Engine) -> (MemoryMap, Processor) {
+    /* Counts X up to $20 through $10, from a ROM the block cache cannot peek into */
+    let (mut map, mut processor) = setup_map();
+    let program: Vec<u8> = vec![
+        LDX_IMMEDIATE,
+        0x00,
+        INX,
+        STX_ZERO_PAGE,
+        0x10,
+        CPX_IMMEDIATE,
+        0x20,
+        BNE,
+        0xF9,
+        JMP_ABSOLUTE,
+        0x00,
+        0x80,
+    ];
+    map.attach(0x8000, 0x800B, ReadOnly { data: program });
+    processor.engine = engine;
+
+    processor.cycles = 500;
+    processor.execute(&mut map);
+    return (map, processor);
+}
+
+pub fn read_only_device() -> () {
+    let (interpreted_map, interpreted) = run_read_only(Engine::Interpreter);
+    let (map, processor) = run_read_only(Engine::Blocks);
+
+    // peek() floats high on this device, so every opcode has to come from a real read
+    assert_eq!(map.peek(0x8000), 0xFF);
+    assert_eq!(processor.blocks.statistics.decoded, 0);
+
+    assert!(interpreted.register_x > 0);
+    assert_eq!(map.peek(0x0010), interpreted_map.peek(0x0010));
+    assert_eq!(processor.clock, interpreted.clock);
+    assert_eq!(processor.program_counter, interpreted.program_counter);
+    verify_register(&processor, RegisterX, interpreted.register_x);
+}
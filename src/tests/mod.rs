@@ -1,16 +1,37 @@
 pub mod arithmetic;
+pub mod banking;
+pub mod blocks;
 pub mod branches;
+pub mod bus;
+pub mod bus_accesses;
+pub mod cmos;
 pub mod common;
+pub mod control;
+pub mod debugger;
 pub mod decrement;
 pub mod flags;
 pub mod increment;
+pub mod interrupts;
+pub mod io_port;
 pub mod jumps;
 pub mod logical;
 pub mod main;
+pub mod memory_map;
+pub mod mos6507;
+pub mod open_bus;
 pub mod programs;
+pub mod recompiler;
+pub mod regions;
 pub mod registers;
 pub mod rotates;
 pub mod shifts;
 pub mod stackops;
+pub mod stepping;
 pub mod system;
+pub mod timing;
+pub mod tracer;
 pub mod transfers;
+pub mod undocumented;
+pub mod variants;
+pub mod w65c816;
+pub mod wraparound;
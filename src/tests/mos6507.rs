@@ -0,0 +1,99 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::observer::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn mirrored_addresses() -> () {
+    const EXPECTED_CYCLES: u32 = 4 + 4;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6507);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0x1F00] = LDA_ABSOLUTE; // 0xFF00 mirrors down to 0x1F00
+    memory.data[0x1F01] = 0x42;
+    memory.data[0x1F02] = 0x30;
+    memory.data[0x1F03] = STA_ABSOLUTE;
+    memory.data[0x1F04] = 0x00;
+    memory.data[0x1F05] = 0xF0;
+    memory.data[0x1042] = 0x84;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x84);
+    verify_memory(&memory, 0x1000, 0x84);
+    verify_memory(&memory, 0xF000, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn observed_addresses() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6507);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    let accesses: Rc<RefCell<Vec<BusAccess>>> = Rc::new(RefCell::new(Vec::new()));
+    let recorder = accesses.clone();
+    processor.bus_observer = Some(BusObserver::new(move |access| {
+        recorder.borrow_mut().push(access)
+    }));
+
+    memory.data[0x1F00] = INC_ABSOLUTE;
+    memory.data[0x1F01] = 0x80;
+    memory.data[0x1F02] = 0xE0;
+    memory.data[0x0080] = 0x41;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    let accesses = accesses.borrow();
+    assert_eq!(
+        accesses[0].address, 0x1F00,
+        "The opcode fetch was not masked"
+    );
+    assert!(
+        accesses.iter().all(|access| access.address <= 0x1FFF),
+        "An address above the 13 bit bus was reported"
+    );
+
+    // The NMOS core writes the unmodified value back before the result
+    let writes: Vec<&BusAccess> = accesses
+        .iter()
+        .filter(|access| access.operation == BusOperation::Write)
+        .collect();
+    assert_eq!(writes.len(), 2, "INC did not write twice");
+    for write in writes.iter() {
+        assert_eq!(
+            write.address, 0x0080,
+            "INC wrote to {:#X} instead of 0x80",
+            write.address
+        );
+    }
+    assert_eq!(
+        writes[0].data, 0x41,
+        "INC wrote {:#X} back instead of 0x41",
+        writes[0].data
+    );
+    assert_eq!(
+        writes[1].data, 0x42,
+        "INC wrote {:#X} instead of 0x42",
+        writes[1].data
+    );
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn interrupt_lines() -> () {
+    assert!(
+        Variant::Mos6507.has_interrupt_lines() == false,
+        "The 6507 has no IRQ or NMI pins"
+    );
+    assert!(
+        Variant::Nmos6502.has_interrupt_lines(),
+        "The 6502 has IRQ and NMI pins"
+    );
+}
@@ -0,0 +1,150 @@
+use super::common::*;
+use crate::cpu;
+use crate::map::*;
+use crate::mem::*;
+
+use cpu::blocks::Engine;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+fn setup_open(program: &[u8]) -> (MemoryMap, Processor) {
+    /* 4KB of RAM at $0000 and the program in 4KB at $8000, nothing else answers */
+    let mut map = MemoryMap::new();
+    map.unmapped = Some(Decay::Hold);
+    map.attach(0x0000, 0x0FFF, Ram::new(0x1000));
+    map.attach(0x8000, 0x8FFF, Ram::new(0x1000));
+
+    let mut processor = Processor::new(Variant::Nmos6502);
+    reset_to(&mut processor, &mut map, 0x8000);
+    for (offset, byte) in program.iter().enumerate() {
+        map.poke(0x8000 + offset as u32, *byte);
+    }
+    return (map, processor);
+}
+
+pub fn unmapped_reads() -> () {
+    const EXPECTED_CYCLES: u32 = 4 + 4 + 2 + 5 + 4;
+    let program: [u8; 14] = [
+        LDA_ABSOLUTE,
+        0x00,
+        0x50,
+        STA_ABSOLUTE,
+        0x00,
+        0x50,
+        LDX_IMMEDIATE,
+        0x20,
+        LDY_ABSOLUTE_X,
+        0xF0,
+        0x61,
+        LDX_ABSOLUTE,
+        0x00,
+        0xC0,
+    ];
+    let (mut map, mut processor) = setup_open(&program);
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut map).cycles;
+
+    // Each load gets the high byte of its operand back, the last thing the processor read
+    verify_register(&processor, Accumulator, 0x50);
+    verify_register(&processor, RegisterY, 0x61);
+    verify_register(&processor, RegisterX, 0xC0);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+
+    // The store went nowhere and the flat RAM under the map stays out of it
+    assert_eq!(map.peek(0x5000), 0xFF);
+    assert_eq!(map.ram.data[0x5000], 0x00);
+    assert_eq!(processor.data_bus.value, 0xC0);
+
+    // A flat Memory answers everywhere, so nothing floats
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    memory.data[0x0200] = LDA_ABSOLUTE;
+    memory.data[0x0201] = 0x00;
+    memory.data[0x0202] = 0x50;
+    memory.data[0x5000] = 0x12;
+    processor.cycles = 4;
+    processor.execute(&mut memory);
+    verify_register(&processor, Accumulator, 0x12);
+}
+
+pub fn write_leaves_value() -> () {
+    // A read-modify-write on nothing reads the operand's high byte, then its own writes are left behind
+    let program: [u8; 3] = [INC_ABSOLUTE, 0x00, 0x50];
+    let (mut map, mut processor) = setup_open(&program);
+    processor.cycles = 6;
+    processor.execute(&mut map);
+    assert_eq!(processor.data_bus.value, 0x51);
+    assert_eq!(processor.data_bus.driven, processor.clock - 1);
+
+    // A chip smaller than its mapping floats past its end
+    map.attach(0x7000, 0x7FFF, Ram::new(0x100));
+    map.poke(0x8003, LDA_ABSOLUTE);
+    map.poke(0x8004, 0x80);
+    map.poke(0x8005, 0x72);
+    processor.cycles = 4;
+    processor.execute(&mut map);
+    verify_register(&processor, Accumulator, 0x72);
+}
+
+fn run_decay(engine: Engine, decay: Decay) -> (MemoryMap, Processor) {
+    /* Two loads from a region with its own decay, one right after the operand and one indexed across a page */
+    let program: [u8; 10] = [
+        LDA_ABSOLUTE,
+        0x00,
+        0x40,
+        LDX_IMMEDIATE,
+        0x20,
+        LDY_ABSOLUTE_X,
+        0xF0,
+        0x40,
+        JMP_ABSOLUTE,
+        0x00,
+    ];
+    let (mut map, mut processor) = setup_open(&program);
+    map.poke(0x800A, 0x80);
+    map.attach(0x4000, 0x41FF, OpenBus::new(decay));
+    processor.engine = engine;
+
+    processor.cycles = 4 + 2 + 5;
+    processor.execute(&mut map);
+    return (map, processor);
+}
+
+pub fn region_decay() -> () {
+    let decay = Decay::After {
+        cycles: 2,
+        value: 0x00,
+    };
+
+    // One cycle after the operand the value holds, by the second read of the indexed load it has gone
+    for engine in [Engine::Interpreter, Engine::Blocks] {
+        let (_, processor) = run_decay(engine, decay);
+        verify_register(&processor, Accumulator, 0x40);
+        verify_register(&processor, RegisterY, 0x00);
+    }
+
+    let (_, processor) = run_decay(Engine::Interpreter, Decay::Hold);
+    verify_register(&processor, RegisterY, 0x40);
+
+    // Called with small budgets, the engines leave the same value on the bus at every stop
+    let (mut interpreted_map, mut interpreted) = run_decay(Engine::Interpreter, decay);
+    let (mut map, mut processor) = run_decay(Engine::Blocks, decay);
+    for _ in 0..20 {
+        interpreted.cycles = 3;
+        processor.cycles = 3;
+        interpreted.execute(&mut interpreted_map);
+        processor.execute(&mut map);
+        assert_eq!(processor.data_bus, interpreted.data_bus);
+    }
+
+    // A clock wound back behind the last drive, as a reset of the counter does, still holds the value
+    let bus = DataBus {
+        value: 0x40,
+        driven: 10,
+    };
+    assert_eq!(bus.float(4, decay), 0x40);
+    assert_eq!(bus.float(12, decay), 0x00);
+}
@@ -4,13 +4,13 @@ use std::io::{BufReader, Read};
 use std::path::Path;
 
 use crate::cpu::processor::Functions;
+use crate::mem::Bus;
 use crate::tests::common::*;
-use crate::Memory;
 
 // Path to the functional test binary
 
-fn read_file_to_memory(
-    memory: &mut Memory,
+fn read_file_to_memory<B: Bus>(
+    memory: &mut B,
     file_addr: String,
     memory_offset_option: Option<usize>,
 ) -> () {
@@ -22,7 +22,7 @@ fn read_file_to_memory(
 
     for index in 0..buffer.len() {
         let value = buffer[index];
-        memory.data[memory_offset + index] = value;
+        memory.poke((memory_offset + index) as u32, value);
     }
 }
 
@@ -36,17 +36,18 @@ pub fn functional_program_test() -> () {
     read_file_to_memory(&mut memory, program_path, Some(0x0A));
 
     processor.program_counter = 0x0400; // Program starts at 0x0400 minus 0x000A;
-    println!("{:#X}", memory.data[0x0A]);
+    println!("{:#X}", memory.peek(0x0A));
 
     let mut clock = 85000;
 
     while clock > 0 {
         processor.cycles = 85000;
-        clock -= processor.execute(&mut memory);
+        clock -= processor.execute(&mut memory).cycles;
     }
 
     println!(
         "{:X} | {:X}",
-        processor, memory.data[processor.program_counter as usize]
+        processor,
+        memory.peek(processor.program_counter as u32)
     );
 }
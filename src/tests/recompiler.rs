@@ -0,0 +1,260 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+use crate::programs::bundled::*;
+use crate::programs::compiled::functional_test::FunctionalTest;
+use crate::programs::compiled::test_program::TestProgram;
+use crate::programs::FUNCTIONAL_TEST_SUCCESS;
+use crate::recompiler::generator::*;
+use crate::recompiler::parse_arguments;
+
+use cpu::compiled::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::tracer::Tracer;
+use cpu::variant::Variant;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::rc::Rc;
+
+fn bundled_image(module: &str) -> Image {
+    return bundled()
+        .into_iter()
+        .find(|(name, _)| *name == module)
+        .unwrap()
+        .1;
+}
+
+fn variant(target: &Target) -> Variant {
+    // Targets go by the name the variant debug-prints as
+    return *VARIANTS
+        .iter()
+        .find(|variant| format!("{:?}", variant) == target.name)
+        .unwrap();
+}
+
+fn load(image: &Image, start: u16) -> (Memory, Processor) {
+    let (mut memory, mut processor) = setup_variant(variant(&image.target));
+    for (offset, byte) in image.bytes.iter().enumerate() {
+        memory.poke(image.load_address + offset as u32, *byte);
+    }
+    processor.program_counter = start;
+    return (memory, processor);
+}
+
+fn verify_compiled_agrees<P: CompiledProgram>(
+    image: &Image,
+    start: u16,
+    budgets: &[u32],
+    finished: fn(&Processor) -> bool,
+    change: Option<(u64, u16, u8)>,
+) -> (Memory, Processor) {
+    /*
+        Hands the interpreter and the compiled program the same budgets in turn until `finished`
+        holds for the interpreter, every call has to come back with the same cycles, instruction
+        count and stop.
+        `change` writes a byte into both memories once the clock passes the given cycle.
+    */
+    let (mut interpreted_memory, mut interpreted) = load(image, start);
+    let (mut memory, mut processor) = load(image, start);
+
+    let mut calls: usize = 0;
+    while finished(&interpreted) == false {
+        if let Some((clock, address, value)) = change {
+            if interpreted.clock >= clock && memory.data[address as usize] != value {
+                interpreted_memory.poke(address as u32, value);
+                memory.poke(address as u32, value);
+            }
+        }
+
+        let budget: u32 = budgets[calls % budgets.len()];
+        interpreted.cycles = budget;
+        processor.cycles = budget;
+        let expected: ExecutionResult = interpreted.execute(&mut interpreted_memory);
+        let result: ExecutionResult = processor.execute_compiled::<P, Memory>(&mut memory);
+        assert_eq!(
+            result, expected,
+            "call {} with a budget of {} came out differently",
+            calls, budget
+        );
+        assert_eq!(processor.clock, interpreted.clock);
+        calls += 1;
+    }
+
+    verify_program_counter(&processor, interpreted.program_counter);
+    verify_register(&processor, Accumulator, interpreted.accumulator);
+    verify_register(&processor, RegisterX, interpreted.register_x);
+    verify_register(&processor, RegisterY, interpreted.register_y);
+    assert_eq!(processor.status, interpreted.status);
+    assert_eq!(processor.stack_pointer, interpreted.stack_pointer);
+    assert!(
+        memory.data == interpreted_memory.data,
+        "the compiled program left memory different"
+    );
+
+    return (memory, processor);
+}
+
+pub fn static_blocks() -> () {
+    let blocks: BTreeMap<u32, Block> = find_blocks(&bundled_image("test_program"));
+
+    // The JMP lands on the second instruction, so the entry block is cut short there
+    assert_eq!(
+        blocks.keys().cloned().collect::<Vec<u32>>(),
+        vec![0x1000, 0x1002]
+    );
+    assert_eq!(blocks[&0x1000].end, 0x1002);
+    let opcodes = |start: u32| -> Vec<(u32, u8)> {
+        return blocks[&start]
+            .instructions
+            .iter()
+            .map(|instruction| (instruction.address, instruction.bytes[0]))
+            .collect();
+    };
+    assert_eq!(opcodes(0x1000), vec![(0x1000, LDA_IMMEDIATE)]);
+    assert_eq!(
+        opcodes(0x1002),
+        vec![
+            (0x1002, STA_ZERO_PAGE),
+            (0x1004, STA_ABSOLUTE),
+            (0x1007, EOR_IMMEDIATE),
+            (0x1009, JMP_ABSOLUTE)
+        ]
+    );
+}
+
+pub fn generated_up_to_date() -> () {
+    // The modules build.rs wrote have to be what the recompiler writes today
+    let generated: [(&str, &str); 2] = [
+        (
+            "test_program",
+            include_str!(concat!(env!("OUT_DIR"), "/test_program.rs")),
+        ),
+        (
+            "functional_test",
+            include_str!(concat!(env!("OUT_DIR"), "/functional_test.rs")),
+        ),
+    ];
+    for (module, source) in generated.iter() {
+        assert!(
+            recompile(&bundled_image(module)) == *source,
+            "the {} module build.rs wrote is stale",
+            module
+        );
+    }
+}
+
+fn arguments(text: &str) -> Vec<String> {
+    return text.split_whitespace().map(String::from).collect();
+}
+
+pub fn command_line() -> () {
+    // The test program given as a binary of its own builds the image bundled.rs does, under its file's name
+    let path = env::temp_dir().join(format!("test_program_{}.bin", std::process::id()));
+    fs::write(&path, &TEST_PROGRAM[2..]).unwrap();
+    let binary: String = path.to_str().unwrap().to_string();
+
+    let mut expected: Image = bundled_image("test_program");
+    let parsed = parse_arguments(&arguments(&format!(
+        "{} --load $1000 --variant nmos6502 --entry 0x1000",
+        binary
+    )));
+    expected.name = format!("TestProgram{}", std::process::id());
+    assert_eq!(parsed, Ok((expected.clone(), None)));
+
+    let parsed = parse_arguments(&arguments(&format!(
+        "--variant Nmos6502 --entry 1000 --name TestProgram --output out.rs --load 1000 {}",
+        binary
+    )));
+    expected.name = String::from("TestProgram");
+    assert_eq!(parsed, Ok((expected, Some(String::from("out.rs")))));
+
+    // Anything missing or malformed is reported rather than guessed at
+    let failures: [(String, &str); 6] = [
+        (
+            format!("{} --load 1000 --variant nmos6502", binary),
+            "no --entry given",
+        ),
+        (
+            format!("{} --load 1000 --entry 1000", binary),
+            "no --variant given",
+        ),
+        (
+            format!("{} --load 1000 --variant z80 --entry 1000", binary),
+            "z80 is not an NMOS variant",
+        ),
+        (
+            format!("{} --load 1000 --variant wdc65c02 --entry 1000", binary),
+            "wdc65c02 is not an NMOS variant",
+        ),
+        (
+            format!("{} --load 10G0 --variant nmos6502 --entry 1000", binary),
+            "10G0 is not a hex address",
+        ),
+        (format!("{} --load", binary), "--load needs a value"),
+    ];
+    for (text, error) in failures.iter() {
+        assert_eq!(parse_arguments(&arguments(text)), Err(error.to_string()));
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+pub fn compiled_test_program() -> () {
+    let image: Image = bundled_image("test_program");
+    let budgets: [u32; 8] = [1, 2, 3, 5, 7, 11, 20, 64];
+    let (memory, _) = verify_compiled_agrees::<TestProgram>(
+        &image,
+        0x1000,
+        &budgets,
+        |processor| processor.clock >= 10000,
+        None,
+    );
+    assert!(memory.data[0x8000] == 0xFF || memory.data[0x8000] == 0x33);
+}
+
+pub fn compiled_functional_test() -> () {
+    // The whole test, a failing case parks on a trap of its own and runs into the cycle limit instead
+    let image: Image = bundled_image("functional_test");
+    let budgets: [u32; 6] = [7, 1, 97, 3, 1000, 50000];
+    let (_, processor) = verify_compiled_agrees::<FunctionalTest>(
+        &image,
+        FUNCTIONAL_TEST_START,
+        &budgets,
+        |processor| {
+            processor.program_counter == FUNCTIONAL_TEST_SUCCESS || processor.clock >= 200_000_000
+        },
+        None,
+    );
+    verify_program_counter(&processor, FUNCTIONAL_TEST_SUCCESS);
+}
+
+pub fn compiled_fallback() -> () {
+    let image: Image = bundled_image("test_program");
+    let budgets: [u32; 4] = [3, 8, 13, 21];
+
+    // The EOR becomes an ORA partway through, the compiled block must notice and hand it back
+    let change: Option<(u64, u16, u8)> = Some((500, 0x1007, OR_IMMEDIATE));
+    let (memory, processor) = verify_compiled_agrees::<TestProgram>(
+        &image,
+        0x1000,
+        &budgets,
+        |processor| processor.clock >= 2000,
+        change,
+    );
+    verify_register(&processor, Accumulator, 0xFF);
+    verify_memory(&memory, 0x0090, 0xFF);
+
+    // A tracer sees every instruction, so nothing compiled runs while one is attached
+    let (mut memory, mut processor) = load(&image, 0x1000);
+    let traced: Rc<RefCell<u64>> = Rc::new(RefCell::new(0));
+    let counter = traced.clone();
+    processor.tracer = Some(Tracer::new(move |_| *counter.borrow_mut() += 1));
+    processor.cycles = 2 + 3 + 4 + 2;
+    let result: ExecutionResult = processor.execute_compiled::<TestProgram, Memory>(&mut memory);
+    assert_eq!(result.instructions, 4);
+    assert_eq!(*traced.borrow(), 4);
+}
@@ -0,0 +1,310 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::blocks::Engine;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::regions::*;
+use cpu::stepper::Stepping;
+use cpu::variant::Variant;
+
+pub fn rom_writes() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 4 + 6 + 4;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor
+        .regions
+        .mark(0xE000, 0xFFFF, Attribute::Rom, ViolationPolicy::Log);
+    processor
+        .regions
+        .mark(0xF000, 0xF0FF, Attribute::Ram, ViolationPolicy::Log);
+
+    // Loading the ROM goes around the regions
+    memory.poke(0xFFFA, 0x34);
+    memory.poke(0xFFFB, 0x12);
+
+    memory.data[0x0200] = LDA_IMMEDIATE;
+    memory.data[0x0201] = 0x99;
+    memory.data[0x0202] = STA_ABSOLUTE;
+    memory.data[0x0203] = 0xFA;
+    memory.data[0x0204] = 0xFF;
+    memory.data[0x0205] = INC_ABSOLUTE;
+    memory.data[0x0206] = 0xFB;
+    memory.data[0x0207] = 0xFF;
+    memory.data[0x0208] = STA_ABSOLUTE;
+    memory.data[0x0209] = 0x80;
+    memory.data[0x020A] = 0xF0;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let result: ExecutionResult = processor.execute(&mut memory);
+
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+    verify_memory(&memory, 0xFFFA, 0x34);
+    verify_memory(&memory, 0xFFFB, 0x12);
+    verify_memory(&memory, 0xF080, 0x99);
+    verify_cycles(result.cycles, EXPECTED_CYCLES as i64);
+
+    // INC writes twice, the unchanged value first
+    assert_eq!(
+        processor.regions.log,
+        vec![
+            Violation {
+                program_counter: 0x0202,
+                address: 0xFFFA,
+                attribute: Attribute::Rom,
+                data: Some(0x99)
+            },
+            Violation {
+                program_counter: 0x0205,
+                address: 0xFFFB,
+                attribute: Attribute::Rom,
+                data: Some(0x12)
+            },
+            Violation {
+                program_counter: 0x0205,
+                address: 0xFFFB,
+                attribute: Attribute::Rom,
+                data: Some(0x13)
+            },
+        ]
+    );
+
+    // Ignored violations are dropped just the same, without a word
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0202);
+    processor.accumulator = 0x99;
+    processor
+        .regions
+        .mark(0xE000, 0xFFFF, Attribute::Rom, ViolationPolicy::Ignore);
+    memory.data[0x0202] = STA_ABSOLUTE;
+    memory.data[0x0203] = 0xFA;
+    memory.data[0x0204] = 0xFF;
+    processor.cycles = 4;
+    processor.execute(&mut memory);
+    verify_memory(&memory, 0xFFFA, 0x00);
+    assert!(processor.regions.log.is_empty());
+}
+
+pub fn log_limit() -> () {
+    const PASSES: u64 = 10;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor
+        .regions
+        .mark(0xE000, 0xFFFF, Attribute::Rom, ViolationPolicy::Log);
+    processor.regions.log_limit = 4;
+
+    memory.data[0x0200] = STA_ABSOLUTE;
+    memory.data[0x0201] = 0xFA;
+    memory.data[0x0202] = 0xFF;
+    memory.data[0x0203] = JMP_ABSOLUTE;
+    memory.data[0x0204] = 0x00;
+    memory.data[0x0205] = 0x02;
+
+    // A program stuck writing to ROM keeps the latest few and counts the rest
+    processor.cycles = (PASSES * (4 + 3)) as u32;
+    processor.execute(&mut memory);
+    assert_eq!(processor.regions.log.len(), 4);
+    assert_eq!(processor.regions.dropped, PASSES - 4);
+    assert!(processor
+        .regions
+        .log
+        .iter()
+        .all(|violation| violation.address == 0xFFFA));
+}
+
+pub fn mirrored_regions() -> () {
+    /* The 6507 runs its cartridge from $F000, which is $1000 on the bus */
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6507);
+    reset_to(&mut processor, &mut memory, 0xF000);
+    processor
+        .regions
+        .mark(0xF000, 0xF0FF, Attribute::NoExecute, ViolationPolicy::Log);
+    processor
+        .regions
+        .mark(0xF800, 0xFFFF, Attribute::Rom, ViolationPolicy::Log);
+
+    memory.data[0x1000] = LDA_IMMEDIATE;
+    memory.data[0x1001] = 0x55;
+    memory.data[0x1002] = STA_ABSOLUTE;
+    memory.data[0x1003] = 0x00;
+    memory.data[0x1004] = 0x19;
+
+    // Fetches and writes are matched whichever mirror they come through
+    processor.cycles = 2 + 4;
+    processor.execute(&mut memory);
+    verify_memory(&memory, 0x1900, 0x00);
+    assert_eq!(
+        processor.regions.log,
+        vec![
+            Violation {
+                program_counter: 0x1000,
+                address: 0x1000,
+                attribute: Attribute::NoExecute,
+                data: None
+            },
+            Violation {
+                program_counter: 0x1002,
+                address: 0x1002,
+                attribute: Attribute::NoExecute,
+                data: None
+            },
+            Violation {
+                program_counter: 0x1002,
+                address: 0x1900,
+                attribute: Attribute::Rom,
+                data: Some(0x55)
+            },
+        ]
+    );
+
+    // A stopping region refuses the first instruction before it is fetched
+    let (mut memory, mut processor) = setup_variant(Variant::Mos6507);
+    reset_to(&mut processor, &mut memory, 0xF000);
+    processor
+        .regions
+        .mark(0xF000, 0xF0FF, Attribute::NoExecute, ViolationPolicy::Stop);
+    memory.data[0x1000] = LDA_IMMEDIATE;
+    memory.data[0x1001] = 0x55;
+    processor.cycles = 2;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.instructions, 0);
+    assert_eq!(
+        result.stop,
+        StopReason::AccessViolation {
+            violation: Violation {
+                program_counter: 0x1000,
+                address: 0x1000,
+                attribute: Attribute::NoExecute,
+                data: None
+            }
+        }
+    );
+    verify_register(&processor, Accumulator, 0x00);
+}
+
+fn load_write_once(memory: &mut Memory) -> () {
+    memory.data[0x0200] = LDA_IMMEDIATE;
+    memory.data[0x0201] = 0x11;
+    memory.data[0x0202] = STA_ABSOLUTE;
+    memory.data[0x0203] = 0x00;
+    memory.data[0x0204] = 0x03;
+    memory.data[0x0205] = LDA_IMMEDIATE;
+    memory.data[0x0206] = 0x22;
+    memory.data[0x0207] = STA_ABSOLUTE;
+    memory.data[0x0208] = 0x00;
+    memory.data[0x0209] = 0x03;
+    memory.data[0x020A] = NOP;
+}
+
+pub fn write_once_stop() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor
+        .regions
+        .mark(0x0300, 0x03FF, Attribute::WriteOnce, ViolationPolicy::Stop);
+    load_write_once(&mut memory);
+
+    let expected = Violation {
+        program_counter: 0x0207,
+        address: 0x0300,
+        attribute: Attribute::WriteOnce,
+        data: Some(0x22),
+    };
+
+    // The store that breaks the rule finishes, execute() stops in front of the next instruction
+    processor.cycles = 100;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(
+        result.stop,
+        StopReason::AccessViolation {
+            violation: expected
+        }
+    );
+    assert_eq!(result.instructions, 4);
+    verify_program_counter(&processor, 0x020A);
+    verify_memory(&memory, 0x0300, 0x11);
+
+    // The fault holds until the host clears it
+    processor.cycles = 100;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.instructions, 0);
+    processor.fault = None;
+    processor.cycles = 2;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    assert_eq!(result.stop, StopReason::BudgetExhausted);
+    verify_program_counter(&processor, 0x020B);
+
+    // Ticked through cycle by cycle, the fault is raised on the boundary after the store
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor
+        .regions
+        .mark(0x0300, 0x03FF, Attribute::WriteOnce, ViolationPolicy::Stop);
+    load_write_once(&mut memory);
+    for _ in 0..(2 + 4 + 2 + 4 + 1) {
+        processor.tick(&mut memory);
+    }
+    assert_eq!(
+        processor.fault,
+        Some(Fault::AccessViolation {
+            violation: expected
+        })
+    );
+    verify_program_counter(&processor, 0x020A);
+    verify_memory(&memory, 0x0300, 0x11);
+}
+
+fn run_data_jump(engine: Engine, policy: ViolationPolicy) -> (Memory, Processor, ExecutionResult) {
+    /* A JMP into a table marked non-executable, which happens to hold LDX #$77; NOP */
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0x0200);
+    processor.engine = engine;
+    processor
+        .regions
+        .mark(0x2000, 0x2FFF, Attribute::NoExecute, policy);
+
+    memory.data[0x0200] = LDA_IMMEDIATE;
+    memory.data[0x0201] = 0x01;
+    memory.data[0x0202] = JMP_ABSOLUTE;
+    memory.data[0x0203] = 0x00;
+    memory.data[0x0204] = 0x20;
+    memory.data[0x2000] = LDX_IMMEDIATE;
+    memory.data[0x2001] = 0x77;
+    memory.data[0x2002] = NOP;
+
+    processor.cycles = 2 + 3 + 2 + 2;
+    let result: ExecutionResult = processor.execute(&mut memory);
+    return (memory, processor, result);
+}
+
+pub fn no_execute() -> () {
+    for engine in [Engine::Interpreter, Engine::Blocks] {
+        // Stopped before the fetch, nothing from the table has run
+        let (_, processor, result) = run_data_jump(engine, ViolationPolicy::Stop);
+        assert_eq!(
+            result.stop,
+            StopReason::AccessViolation {
+                violation: Violation {
+                    program_counter: 0x2000,
+                    address: 0x2000,
+                    attribute: Attribute::NoExecute,
+                    data: None
+                }
+            }
+        );
+        assert_eq!(result.instructions, 2);
+        verify_program_counter(&processor, 0x2000);
+        verify_register(&processor, RegisterX, 0x00);
+
+        // Logged, it runs and leaves one entry for the instruction that started there
+        let (_, processor, result) = run_data_jump(engine, ViolationPolicy::Log);
+        assert_eq!(result.stop, StopReason::BudgetExhausted);
+        verify_program_counter(&processor, 0x2003);
+        verify_register(&processor, RegisterX, 0x77);
+        assert_eq!(processor.regions.log.len(), 2);
+        assert_eq!(processor.regions.log[1].address, 0x2002);
+    }
+}
@@ -115,7 +115,7 @@ pub fn indirect_x() -> () {
     memory.data[0x8000] = 0x84;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -135,7 +135,7 @@ pub fn indirect_y() -> () {
     memory.data[0x8004] = 0x84; // 0x8000 + 0x4
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -155,7 +155,7 @@ pub fn indirect_y_overflow() -> () {
     memory.data[0x8101] = 0x84; // 0x8002 + 0xFF
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -17,7 +17,7 @@ pub fn test_register_immediate(
     memory.data[0xFFFD] = 0x84;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_register(&processor, register, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -37,7 +37,7 @@ pub fn test_register_zero_page(
     memory.data[0x0042] = 0x84;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_register(&processor, register, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -77,7 +77,7 @@ pub fn test_register_zero_page_register(
     }
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_register(&processor, register, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -98,7 +98,7 @@ pub fn test_register_absolute(
     memory.data[0x4480] = 0x84;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_register(&processor, register, 0x84);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -140,7 +140,7 @@ pub fn test_register_absolute_register(
     }
 
     processor.cycles = expected_cycles;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_register(&processor, register, 0x84);
     verify_cycles(cycles, expected_cycles as i64);
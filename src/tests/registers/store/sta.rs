@@ -43,7 +43,7 @@ pub fn absolute_x() -> () {
     memory.data[0xFFFD] = 0x00;
     memory.data[0xFFFE] = 0x80;
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&mut memory, 0x800F, 0x42);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -61,7 +61,7 @@ pub fn absolute_y() -> () {
     memory.data[0xFFFD] = 0x00;
     memory.data[0xFFFE] = 0x80;
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&mut memory, 0x800F, 0x42);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -81,7 +81,7 @@ pub fn indirect_x() -> () {
     memory.data[0x0030] = 0x80; // contains pointer to location where acculumator is stored
     memory.data[0x8000] = 0x00; // should contain 0x42 after execution
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&mut memory, 0x8000, 0x42);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -101,7 +101,7 @@ pub fn indirect_y() -> () {
     memory.data[0x0021] = 0x80; // second byte of pointer
     memory.data[0x800F] = 0x00; // result of register_x + u16(pointers above)
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&mut memory, 0x800F, 0x42);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -23,7 +23,7 @@ pub fn test_register_zero_page(
     memory.data[0xFFFD] = 0x80;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_memory(memory, 0x80, 0x2F);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -55,7 +55,7 @@ pub fn test_register_zero_page_register(
     memory.data[0x008F] = 0x80;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_memory(memory, 0x008F, 0x42);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -80,7 +80,7 @@ pub fn test_register_absolute(
     memory.data[0xFFFE] = 0x80;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(memory);
+    let cycles = processor.execute(memory).cycles;
 
     verify_memory(memory, 0x8080, 0x2F);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -9,14 +9,14 @@ use cpu::processor::Functions;
 pub fn rotate_left_accumulator() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.set_status(CarryFlag, true);
 
     memory.data[0xFF00] = ROL_ACCUMULATOR;
 
     processor.accumulator = 0b01;
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0b11);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -28,14 +28,14 @@ pub fn rotate_left_accumulator() -> () {
 pub fn rotate_left_zero_page() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = ROL_ZERO_PAGE;
     memory.data[0xFF01] = 0x42;
     memory.data[0x0042] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0042, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -47,7 +47,7 @@ pub fn rotate_left_zero_page() -> () {
 pub fn rotate_left_zero_page_x() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROL_ZERO_PAGE_X;
@@ -55,7 +55,7 @@ pub fn rotate_left_zero_page_x() -> () {
     memory.data[0x0042 + 5] = 0b0;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0047, 0b0);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -67,7 +67,7 @@ pub fn rotate_left_zero_page_x() -> () {
 pub fn rotate_left_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROL_ABSOLUTE;
@@ -76,7 +76,7 @@ pub fn rotate_left_absolute() -> () {
     memory.data[0x8000] = 0b11001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8000, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -86,9 +86,9 @@ pub fn rotate_left_absolute() -> () {
 }
 
 pub fn rotate_left_absolute_x() -> () {
-    const EXPECTED_CYCLES: u32 = 6;
+    const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROL_ABSOLUTE_X;
@@ -97,7 +97,7 @@ pub fn rotate_left_absolute_x() -> () {
     memory.data[0x8000 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8005, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -109,14 +109,14 @@ pub fn rotate_left_absolute_x() -> () {
 pub fn rotate_right_accumulator() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = ROR_ACCUMULATOR;
 
     processor.accumulator = 0b0;
     processor.set_status(CarryFlag, true);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0b10000000);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -128,14 +128,14 @@ pub fn rotate_right_accumulator() -> () {
 pub fn rotate_right_zero_page() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = ROR_ZERO_PAGE;
     memory.data[0xFF01] = 0x42;
     memory.data[0x0042] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0042, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -144,10 +144,31 @@ pub fn rotate_right_zero_page() -> () {
     verify_flag(&processor, NegativeFlag, false);
 }
 
+pub fn rotate_right_carry_in() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = ROR_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0x0042] = 0b01001100;
+
+    // The memory forms rotate the old carry into bit 7 just as the accumulator form does
+    processor.set_status(CarryFlag, true);
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0b10100110);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+    verify_flag(&processor, CarryFlag, false);
+    verify_flag(&processor, ZeroFlag, false);
+    verify_flag(&processor, NegativeFlag, true);
+}
+
 pub fn rotate_right_zero_page_x() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROR_ZERO_PAGE_X;
@@ -155,7 +176,7 @@ pub fn rotate_right_zero_page_x() -> () {
     memory.data[0x0042 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0047, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -167,7 +188,7 @@ pub fn rotate_right_zero_page_x() -> () {
 pub fn rotate_right_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROR_ABSOLUTE;
@@ -176,7 +197,7 @@ pub fn rotate_right_absolute() -> () {
     memory.data[0x8000] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8000, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -186,9 +207,9 @@ pub fn rotate_right_absolute() -> () {
 }
 
 pub fn rotate_right_absolute_x() -> () {
-    const EXPECTED_CYCLES: u32 = 6;
+    const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ROR_ABSOLUTE_X;
@@ -197,7 +218,7 @@ pub fn rotate_right_absolute_x() -> () {
     memory.data[0x8000 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8005, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -9,13 +9,13 @@ use cpu::processor::Functions;
 pub fn shift_left_accumulator() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = ASL_ACCUMULATOR;
 
     processor.accumulator = 0b01;
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0b10);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -27,14 +27,14 @@ pub fn shift_left_accumulator() -> () {
 pub fn shift_left_zero_page() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = ASL_ZERO_PAGE;
     memory.data[0xFF01] = 0x42;
     memory.data[0x0042] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0042, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -46,7 +46,7 @@ pub fn shift_left_zero_page() -> () {
 pub fn shift_left_zero_page_x() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ASL_ZERO_PAGE_X;
@@ -54,7 +54,7 @@ pub fn shift_left_zero_page_x() -> () {
     memory.data[0x0042 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0047, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -66,7 +66,7 @@ pub fn shift_left_zero_page_x() -> () {
 pub fn shift_left_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ASL_ABSOLUTE;
@@ -75,7 +75,7 @@ pub fn shift_left_absolute() -> () {
     memory.data[0x8000] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8000, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -85,9 +85,9 @@ pub fn shift_left_absolute() -> () {
 }
 
 pub fn shift_left_absolute_x() -> () {
-    const EXPECTED_CYCLES: u32 = 6;
+    const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = ASL_ABSOLUTE_X;
@@ -96,7 +96,7 @@ pub fn shift_left_absolute_x() -> () {
     memory.data[0x8000 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8005, 0b10011010);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -108,13 +108,13 @@ pub fn shift_left_absolute_x() -> () {
 pub fn shift_right_accumulator() -> () {
     const EXPECTED_CYCLES: u32 = 2;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = LSR_ACCUMULATOR;
 
     processor.accumulator = 0b01;
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0b0);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -126,14 +126,14 @@ pub fn shift_right_accumulator() -> () {
 pub fn shift_right_zero_page() -> () {
     const EXPECTED_CYCLES: u32 = 5;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
 
     memory.data[0xFF00] = LSR_ZERO_PAGE;
     memory.data[0xFF01] = 0x42;
     memory.data[0x0042] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0042, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -145,7 +145,7 @@ pub fn shift_right_zero_page() -> () {
 pub fn shift_right_zero_page_x() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = LSR_ZERO_PAGE_X;
@@ -153,7 +153,7 @@ pub fn shift_right_zero_page_x() -> () {
     memory.data[0x0042 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x0047, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -165,7 +165,7 @@ pub fn shift_right_zero_page_x() -> () {
 pub fn shift_right_absolute() -> () {
     const EXPECTED_CYCLES: u32 = 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = LSR_ABSOLUTE;
@@ -174,7 +174,7 @@ pub fn shift_right_absolute() -> () {
     memory.data[0x8000] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8000, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -184,9 +184,9 @@ pub fn shift_right_absolute() -> () {
 }
 
 pub fn shift_right_absolute_x() -> () {
-    const EXPECTED_CYCLES: u32 = 6;
+    const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.register_x = 5;
 
     memory.data[0xFF00] = LSR_ABSOLUTE_X;
@@ -195,7 +195,7 @@ pub fn shift_right_absolute_x() -> () {
     memory.data[0x8000 + 5] = 0b01001101;
 
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_memory(&memory, 0x8005, 0b00100110);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -17,7 +17,7 @@ pub fn transfer_stack_to_x() -> () {
     processor.stack_pointer = 0x01;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterX, 0x01);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -34,7 +34,7 @@ pub fn transfer_stack_to_x_flag() -> () {
     processor.stack_pointer = 0x0;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterX, 0x00);
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
@@ -51,7 +51,7 @@ pub fn transfer_x_to_stack() -> () {
     processor.register_x = 0x15;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     assert_eq!(
         processor.stack_pointer, processor.register_x,
@@ -71,7 +71,7 @@ pub fn push_accumulator_to_stack() -> () {
     processor.accumulator = 0x15;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     assert_eq!(
         memory.data[processor.stack_pointer_to_address() as usize + 1], // function decrements stack pointer so value on stack is at sp+1
@@ -89,7 +89,7 @@ pub fn push_status_to_stack() -> () {
     processor.status = 0b10010110;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     let memory_at_stack_pointer = memory.data[processor.stack_pointer_to_address() as usize + 1]; // function decrements stack pointer so value on stack is at sp+1
 
@@ -117,14 +117,14 @@ pub fn push_status_to_stack() -> () {
 pub fn pull_accumulator_from_stack() -> () {
     const EXPECTED_CYCLES: u32 = 4;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.stack_pointer = 0xFE;
     processor.cycles = EXPECTED_CYCLES;
 
     memory.data[0xFF00] = PLA;
     memory.data[0x1FF] = 0x20; // Sets highest value on stack (0xFF) to 0x20
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     assert_eq!(
         processor.accumulator, 0x20,
@@ -136,7 +136,7 @@ pub fn pull_accumulator_from_stack() -> () {
 pub fn pull_status_from_stack() -> () {
     const EXPECTED_CYCLES: u32 = 4;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.stack_pointer = 0xFE;
     processor.cycles = EXPECTED_CYCLES;
 
@@ -146,7 +146,7 @@ pub fn pull_status_from_stack() -> () {
 
     // Status should be  0b01000001 as end result - the 4th and 5th bit of the status should be unchanged
 
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     assert_eq!(
         processor.status, 0b01000001,
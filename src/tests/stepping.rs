@@ -0,0 +1,152 @@
+use super::common::*;
+use crate::cpu;
+use crate::Memory;
+
+use cpu::observer::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::stepper::Stepping;
+
+pub fn store_cycle() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = STA_ABSOLUTE;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = 0x02;
+
+    let expected: [(u32, BusOperation, bool); 6] = [
+        (0xFF00, BusOperation::Read, true),
+        (0xFF01, BusOperation::Read, false),
+        (0xFF02, BusOperation::Read, true),
+        (0xFF03, BusOperation::Read, false),
+        (0xFF04, BusOperation::Read, false),
+        (0x0200, BusOperation::Write, false),
+    ];
+
+    for (cycle, (address, operation, sync)) in expected.iter().enumerate() {
+        if cycle == 1 {
+            // LDA has not finished until its last cycle ticks
+            verify_register(&processor, Accumulator, 0x00);
+        }
+        if cycle == 5 {
+            verify_register(&processor, Accumulator, 0x42);
+            verify_memory(&memory, 0x0200, 0x00); // Nothing lands before the store cycle
+        }
+
+        let access: BusAccess = processor.tick(&mut memory);
+        assert_eq!(
+            access.address, *address,
+            "Cycle {} addressed the wrong location",
+            cycle
+        );
+        assert_eq!(
+            access.operation, *operation,
+            "Cycle {} has the wrong direction",
+            cycle
+        );
+        assert_eq!(access.sync, *sync, "Cycle {} has SYNC wrong", cycle);
+    }
+
+    verify_memory(&memory, 0x0200, 0x42);
+    verify_program_counter(&processor, 0xFF05);
+    assert_eq!(processor.stepper.elapsed(), 6);
+    assert!(processor.stepper.mid_instruction() == false);
+}
+
+pub fn live_reads() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LDA_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x03;
+    memory.data[0x0300] = 0x11;
+
+    for _ in 0..3 {
+        processor.tick(&mut memory);
+    }
+
+    // A device changing the location before the read cycle is seen by the read
+    memory.data[0x0300] = 0x55;
+    let access: BusAccess = processor.tick(&mut memory);
+
+    assert_eq!(access.data, 0x55);
+    verify_register(&processor, Accumulator, 0x55);
+}
+
+pub fn internal_cycles() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x05;
+
+    memory.data[0xFF00] = INC_ZERO_PAGE;
+    memory.data[0xFF01] = 0x10;
+    memory.data[0xFF02] = INX;
+    memory.data[0x0010] = 0x7F;
+
+    let mut accesses: Vec<BusAccess> = Vec::new();
+    for _ in 0..7 {
+        accesses.push(processor.tick(&mut memory));
+    }
+
+    verify_memory(&memory, 0x0010, 0x80);
+    verify_register(&processor, RegisterX, 0x06);
+
+    // Each write lands on its own cycle, the unmodified value first
+    let writes: Vec<u8> = accesses
+        .iter()
+        .filter(|access| access.operation == BusOperation::Write)
+        .map(|access| access.data)
+        .collect();
+    assert_eq!(writes, vec![0x7F, 0x80]);
+    assert_eq!(accesses[6].operation, BusOperation::Internal); // INX has no bus access of its own
+}
+
+fn load_subroutine_program(memory: &mut Memory) -> () {
+    memory.data[0xFF00] = LDX_IMMEDIATE;
+    memory.data[0xFF01] = 0x03;
+    memory.data[0xFF02] = DEX; // Loops back here three times
+    memory.data[0xFF03] = BNE;
+    memory.data[0xFF04] = 0xFD;
+    memory.data[0xFF05] = JSR;
+    memory.data[0xFF06] = 0x00;
+    memory.data[0xFF07] = 0xFE;
+    memory.data[0xFF08] = PHA;
+    memory.data[0xFF09] = PLA;
+
+    memory.data[0xFE00] = INC_ZERO_PAGE;
+    memory.data[0xFE01] = 0x10;
+    memory.data[0xFE02] = RTS;
+}
+
+pub fn matches_execute() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 14 + 6 + 5 + 6 + 3 + 4;
+
+    let (mut executed_memory, mut executed) = setup();
+    reset_to(&mut executed, &mut executed_memory, 0xFF00);
+    load_subroutine_program(&mut executed_memory);
+    executed.cycles = EXPECTED_CYCLES;
+    let cycles: i64 = executed.execute(&mut executed_memory).cycles;
+
+    let (mut stepped_memory, mut stepped) = setup();
+    reset_to(&mut stepped, &mut stepped_memory, 0xFF00);
+    load_subroutine_program(&mut stepped_memory);
+    for _ in 0..cycles {
+        stepped.tick(&mut stepped_memory);
+    }
+
+    assert!(
+        stepped.stepper.mid_instruction() == false,
+        "Ticks and cycles disagree"
+    );
+    verify_program_counter(&stepped, 0xFF0A);
+    assert_eq!(stepped.program_counter, executed.program_counter);
+    assert_eq!(stepped.stack_pointer, executed.stack_pointer);
+    assert_eq!(stepped.register_x, executed.register_x);
+    assert_eq!(stepped.status, executed.status);
+    verify_memory(&stepped_memory, 0x0010, executed_memory.data[0x0010]);
+}
@@ -2,15 +2,17 @@ use super::common::*;
 use crate::cpu;
 use crate::fetch_bit;
 use crate::set_bit;
+use crate::RamPattern;
 
 use cpu::opcodes::ProcessorStatus::*;
 use cpu::opcodes::*;
 use cpu::processor::Functions;
+use cpu::variant::Variant;
 
 pub fn force_interrupt() -> () {
     const EXPECTED_CYCLES: u32 = 7;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.cycles = EXPECTED_CYCLES;
 
     memory.data[0xFF00] = BRK;
@@ -21,7 +23,7 @@ pub fn force_interrupt() -> () {
     let mut original_processor_status = processor.status;
     original_processor_status = set_bit(original_processor_status, 4, true);
     original_processor_status = set_bit(original_processor_status, 5, true);
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0x8000);
     verify_memory(&memory, (0x100 | original_stack_pointer) - 0, 0xFF);
@@ -39,7 +41,7 @@ pub fn force_interrupt() -> () {
 pub fn return_from_interrupt() -> () {
     const EXPECTED_CYCLES: u32 = 7 + 6;
     let (mut memory, mut processor) = setup();
-    processor.reset(&mut memory, 0xFF00);
+    reset_to(&mut processor, &mut memory, 0xFF00);
     processor.cycles = EXPECTED_CYCLES;
 
     memory.data[0xFF00] = BRK;
@@ -49,7 +51,7 @@ pub fn return_from_interrupt() -> () {
 
     let original_stack_pointer: u16 = processor.stack_pointer.into();
     let original_processor_status = processor.status;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_program_counter(&processor, 0xFF02);
     assert_eq!(
@@ -62,3 +64,76 @@ pub fn return_from_interrupt() -> () {
     );
     verify_cycles(cycles, EXPECTED_CYCLES as i64);
 }
+
+pub fn reset_sequence() -> () {
+    const EXPECTED_CYCLES: u32 = 7;
+    let (mut memory, mut processor) = setup();
+    processor.accumulator = 0x42;
+    processor.stack_pointer = 0x40;
+    processor.program_counter = 0x1234;
+    processor.cycles = 10;
+
+    memory.data[0x0300] = 0x99;
+    memory.data[0xFFFC] = 0x00;
+    memory.data[0xFFFD] = 0xC0;
+
+    processor.reset(&mut memory);
+
+    verify_program_counter(&processor, 0xC000);
+    verify_register(&processor, cpu::opcodes::Registers::Accumulator, 0x42);
+    assert_eq!(
+        processor.stack_pointer, 0x3D,
+        "Reset did not drop the stack pointer by 3"
+    );
+    verify_flag(&processor, InterruptDisable, true);
+    verify_memory(&memory, 0x0300, 0x99); // Memory survives a reset
+    verify_memory(&memory, 0x0140, 0x00); // The stack accesses are reads
+    assert_eq!(
+        processor.cycles, 10,
+        "The {} reset cycles came out of the budget",
+        EXPECTED_CYCLES
+    );
+}
+
+pub fn cmos_reset() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C02);
+    processor.set_status(DecimalMode, true);
+
+    processor.reset(&mut memory);
+
+    verify_flag(&processor, DecimalMode, false);
+    verify_flag(&processor, InterruptDisable, true);
+}
+
+pub fn power_on_patterns() -> () {
+    let (mut memory, mut processor) = setup();
+    memory.data[0xFFFC] = 0x00;
+    memory.data[0xFFFD] = 0xC0;
+
+    processor.power_on(&mut memory, RamPattern::Ones);
+    verify_memory(&memory, 0x0200, 0xFF);
+    verify_program_counter(&processor, 0xFFFF); // The vector itself was overwritten
+    assert_eq!(processor.stack_pointer, 0xFD);
+
+    processor.power_on(&mut memory, RamPattern::AlternatingPages);
+    verify_memory(&memory, 0x0000, 0x00);
+    verify_memory(&memory, 0x01FF, 0xFF);
+    verify_memory(&memory, 0x0200, 0x00);
+
+    processor.power_on(&mut memory, RamPattern::Random(1234));
+    let first: Vec<u8> = memory.data[0x0000..0x0100].to_vec();
+    processor.power_on(&mut memory, RamPattern::Random(1234));
+    assert_eq!(
+        first,
+        memory.data[0x0000..0x0100].to_vec(),
+        "The same seed gave different RAM"
+    );
+    assert!(
+        first.iter().any(|byte| *byte != first[0]),
+        "Random RAM came out uniform"
+    );
+
+    processor.power_on(&mut memory, RamPattern::Zero);
+    verify_memory(&memory, 0x0200, 0x00);
+    verify_program_counter(&processor, 0x0000);
+}
@@ -0,0 +1,97 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::stepper::Stepping;
+use cpu::variant::*;
+
+const ORIGIN: u16 = 0x0200;
+
+fn load_operands(memory: &mut Memory, opcode: u8, crossing: bool) -> () {
+    /*
+        The same three bytes serve every addressing mode. Without a crossing nothing leaves its
+        page, with one every index carries, $F0 sends branches back across $0200 and $80 does
+        the same for BBR and BBS.
+    */
+    let (low, high): (u8, u8) = if crossing { (0xF0, 0x80) } else { (0x10, 0x20) };
+    memory.data[ORIGIN as usize] = opcode;
+    memory.data[ORIGIN as usize + 1] = low;
+    memory.data[ORIGIN as usize + 2] = high;
+
+    // Zero page pointers for (zp),Y and (zp), the crossing one ends on the last byte of its page
+    memory.data[0x0010] = 0x00;
+    memory.data[0x0011] = 0x30;
+    memory.data[0x00F0] = 0xF0;
+    memory.data[0x00F1] = 0x30;
+}
+
+fn count_cycles(processor: &mut Processor, memory: &mut Memory) -> u32 {
+    let mut cycles: u32 = 0;
+    loop {
+        processor.tick(memory);
+        cycles += 1;
+        if processor.stepper.mid_instruction() == false || cycles > 16 {
+            return cycles;
+        }
+    }
+}
+
+fn verify_opcode(variant: Variant, opcode: u8, crossing: bool, status: u8) -> () {
+    let (mut memory, mut processor) = setup_variant(variant);
+    reset_to(&mut processor, &mut memory, ORIGIN);
+    load_operands(&mut memory, opcode, crossing);
+    processor.status = status;
+    processor.stack_pointer = 0xF0;
+    processor.register_x = if crossing { 0xFF } else { 0x00 };
+    processor.register_y = processor.register_x;
+
+    let cycles: u32 = count_cycles(&mut processor, &mut memory);
+
+    // A branch has only left the instruction behind when it lands outside $0200 to $0203
+    let taken: bool = processor.program_counter < ORIGIN || processor.program_counter > ORIGIN + 3;
+    let expected: u8 = variant.timing(opcode).cycles(crossing, taken);
+    assert_eq!(
+        cycles, expected as u32,
+        "{:?} spent {} cycles on {:#04X} (crossing {}, status {:#04X}) when its table lists {}",
+        variant, cycles, opcode, crossing, status, expected
+    );
+}
+
+pub fn every_opcode() -> () {
+    for variant in VARIANTS {
+        for opcode in 0..=0xFF {
+            if variant.cycles(opcode) == 0 {
+                continue; // JAM never finishes
+            }
+
+            // Both flag patterns leave D clear so the CMOS decimal cycle stays out of the count
+            for status in [0x00, 0xF7] {
+                verify_opcode(variant, opcode, false, status);
+                verify_opcode(variant, opcode, true, status);
+            }
+        }
+    }
+}
+
+pub fn penalty_rules() -> () {
+    let nmos: Variant = Variant::Nmos6502;
+    let cmos: Variant = Variant::Wdc65C02;
+
+    // Reads pay for a crossing, stores and NMOS read-modify-writes have the fix-up built in
+    assert_eq!(nmos.timing(LDA_ABSOLUTE_X).cycles(true, false), 5);
+    assert_eq!(nmos.timing(STA_ABSOLUTE_X).cycles(true, false), 5);
+    assert_eq!(nmos.timing(ASL_ABSOLUTE_X).cycles(false, false), 7);
+    assert_eq!(nmos.timing(ASL_ABSOLUTE_X).cycles(true, false), 7);
+
+    // The 65C02 only charges its shifts for the fix-up when the page changes
+    assert_eq!(cmos.timing(ASL_ABSOLUTE_X).cycles(false, false), 6);
+    assert_eq!(cmos.timing(ASL_ABSOLUTE_X).cycles(true, false), 7);
+    assert_eq!(cmos.timing(INC_ABSOLUTE_X).cycles(true, false), 7);
+
+    assert_eq!(nmos.timing(BNE).cycles(true, false), 2);
+    assert_eq!(nmos.timing(BNE).cycles(false, true), 3);
+    assert_eq!(nmos.timing(BNE).cycles(true, true), 4);
+    assert_eq!(cmos.timing(BRA).cycles(true, true), 4);
+}
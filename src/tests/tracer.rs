@@ -0,0 +1,172 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::disassembler::*;
+use cpu::native::registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::tracer::*;
+use cpu::variant::Variant;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn record(processor: &mut Processor) -> Rc<RefCell<Vec<TraceRecord>>> {
+    let records: Rc<RefCell<Vec<TraceRecord>>> = Rc::new(RefCell::new(Vec::new()));
+    let recorder = records.clone();
+    processor.tracer = Some(Tracer::new(move |record| {
+        recorder.borrow_mut().push(record.clone())
+    }));
+    return records;
+}
+
+fn verify_disassembly(processor: &Processor, memory: &Memory, address: u32, expected: &str) -> () {
+    let text: String = disassemble(processor, memory, address).to_string();
+    assert_eq!(text, expected, "{:#X} disassembled wrongly", address);
+}
+
+pub fn disassembly() -> () {
+    let (mut memory, processor) = setup();
+    #[rustfmt::skip]
+    let program: [u8; 12] = [
+        LDA_IMMEDIATE, 0x42,
+        STA_INDIRECT_Y, 0x10,
+        JMP_INDIRECT, 0x34, 0x12,
+        BNE, 0xF9,
+        LAX_ZERO_PAGE_Y, 0x80,
+        ROL_ACCUMULATOR,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        memory.data[0xFF00 + offset] = *byte;
+    }
+
+    verify_disassembly(&processor, &memory, 0xFF00, "LDA #$42");
+    verify_disassembly(&processor, &memory, 0xFF02, "STA ($10),Y");
+    verify_disassembly(&processor, &memory, 0xFF04, "JMP ($1234)");
+    verify_disassembly(&processor, &memory, 0xFF07, "BNE $FF02");
+    verify_disassembly(&processor, &memory, 0xFF09, "LAX $80,Y");
+    verify_disassembly(&processor, &memory, 0xFF0B, "ROL A");
+
+    // Operands past the end of memory wrap around to the zero page
+    memory.data[0xFFFF] = LDA_ABSOLUTE;
+    memory.data[0x0000] = 0x00;
+    memory.data[0x0001] = 0x80;
+    let wrapped: Disassembly = disassemble(&processor, &memory, 0xFFFF);
+    assert_eq!(wrapped.bytes, vec![LDA_ABSOLUTE, 0x00, 0x80]);
+    assert_eq!(wrapped.to_string(), "LDA $8000");
+}
+
+pub fn cmos_disassembly() -> () {
+    let (mut memory, processor) = setup_variant(Variant::Wdc65C02);
+    #[rustfmt::skip]
+    let program: [u8; 12] = [
+        BBR3, 0x12, 0xFD,
+        JMP_ABSOLUTE_INDEXED_INDIRECT, 0x00, 0x20,
+        STZ_ZERO_PAGE_X, 0x10,
+        LDA_ZERO_PAGE_INDIRECT, 0x20,
+        WAI,
+        0x5C,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        memory.data[0xFF00 + offset] = *byte;
+    }
+
+    verify_disassembly(&processor, &memory, 0xFF00, "BBR3 $12,$FF00");
+    verify_disassembly(&processor, &memory, 0xFF03, "JMP ($2000,X)");
+    verify_disassembly(&processor, &memory, 0xFF06, "STZ $10,X");
+    verify_disassembly(&processor, &memory, 0xFF08, "LDA ($20)");
+    verify_disassembly(&processor, &memory, 0xFF0A, "WAI");
+    assert_eq!(
+        disassemble(&processor, &memory, 0xFF0B).bytes.len(),
+        3,
+        "$5C is a three byte NOP"
+    );
+
+    let (_, rockwell) = setup_variant(Variant::Rockwell65C02);
+    verify_disassembly(&rockwell, &memory, 0xFF0A, "NOP");
+}
+
+pub fn native_disassembly() -> () {
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    processor.set_emulation(false);
+    processor.set_native_status(INDEX_WIDTH);
+
+    memory.poke(0x01_8000, LDA_IMMEDIATE);
+    memory.poke(0x01_8001, 0x34);
+    memory.poke(0x01_8002, 0x12);
+    memory.poke(0x01_8003, LDX_IMMEDIATE);
+    memory.poke(0x01_8004, 0x56);
+    memory.poke(0x01_8005, MVN);
+    memory.poke(0x01_8006, 0x02);
+    memory.poke(0x01_8007, 0x01);
+    memory.poke(0x01_8008, 0x22); // JSL
+    memory.poke(0x01_8009, 0x56);
+    memory.poke(0x01_800A, 0x34);
+    memory.poke(0x01_800B, 0x12);
+    memory.poke(0x01_800C, LDA_STACK_RELATIVE);
+    memory.poke(0x01_800D, 0x03);
+
+    // The accumulator is 16 bits wide and the index registers are not
+    verify_disassembly(&processor, &memory, 0x01_8000, "LDA #$1234");
+    verify_disassembly(&processor, &memory, 0x01_8003, "LDX #$56");
+    verify_disassembly(&processor, &memory, 0x01_8005, "MVN $01,$02");
+    verify_disassembly(&processor, &memory, 0x01_8008, "JSL $123456");
+    verify_disassembly(&processor, &memory, 0x01_800C, "LDA $03,S");
+
+    processor.set_native_status(ACCUMULATOR_WIDTH | INDEX_WIDTH);
+    verify_disassembly(&processor, &memory, 0x01_8000, "LDA #$34");
+}
+
+pub fn trace_lines() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    let records = record(&mut processor);
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = STA_ABSOLUTE_X;
+    memory.data[0xFF03] = 0x00;
+    memory.data[0xFF04] = 0x02;
+
+    processor.cycles = 2 + 5;
+    processor.execute(&mut memory);
+
+    // Registers are shown before the instruction, cycles are what it spent
+    let records = records.borrow();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].to_string(),
+        "FF00  A9 42       LDA #$42        A:00 X:00 Y:00 P:00 SP:FF CYC:2"
+    );
+    assert_eq!(
+        records[1].to_string(),
+        "FF02  9D 00 02    STA $0200,X     A:42 X:00 Y:00 P:00 SP:FF CYC:5"
+    );
+}
+
+pub fn address_filter() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    let records = record(&mut processor);
+    processor.tracer.as_mut().unwrap().restrict(0x0200, 0x02FF);
+
+    memory.data[0xFF00] = JSR;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x02;
+    memory.data[0xFF03] = NOP;
+    memory.data[0x0200] = INX;
+    memory.data[0x0201] = RTS;
+
+    processor.cycles = 6 + 2 + 6 + 2;
+    processor.execute(&mut memory);
+
+    // Only the subroutine is inside the range
+    let addresses: Vec<u32> = records
+        .borrow()
+        .iter()
+        .map(|record| record.disassembly.address)
+        .collect();
+    assert_eq!(addresses, vec![0x0200, 0x0201]);
+    assert_eq!(records.borrow()[1].register_x, 0x01);
+}
@@ -14,7 +14,7 @@ pub fn transfer_accumulator_to_x() -> () {
     processor.accumulator = 0x84;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterX, 0x84);
     verify_flag(&processor, ZeroFlag, false);
@@ -31,7 +31,7 @@ pub fn transfer_accumulator_to_y() -> () {
     processor.accumulator = 0x84;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, RegisterY, 0x84);
     verify_flag(&processor, ZeroFlag, false);
@@ -48,7 +48,7 @@ pub fn transfer_y_to_accumulator() -> () {
     processor.register_y = 0x84;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0x84);
     verify_flag(&processor, ZeroFlag, false);
@@ -65,7 +65,7 @@ pub fn transfer_x_to_accumulator() -> () {
     processor.register_x = 0x84;
     processor.program_counter = processor.load_program(&mut memory, &PROGRAM);
     processor.cycles = EXPECTED_CYCLES;
-    let cycles = processor.execute(&mut memory);
+    let cycles = processor.execute(&mut memory).cycles;
 
     verify_register(&processor, Accumulator, 0x84);
     verify_flag(&processor, ZeroFlag, false);
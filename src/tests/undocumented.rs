@@ -0,0 +1,389 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+
+pub fn lax_zero_page() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = LAX_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0x0042] = 0x84;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x84);
+    verify_register(&processor, RegisterX, 0x84);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_flag(&processor, ZeroFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn lax_indirect_y_page_cross() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_y = 0x10;
+
+    memory.data[0xFF00] = LAX_INDIRECT_Y;
+    memory.data[0xFF01] = 0x02;
+    memory.data[0x0002] = 0xF8;
+    memory.data[0x0003] = 0x80; // 0x80F8 + 0x10 = 0x8108
+    memory.data[0x8108] = 0x37;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x37);
+    verify_register(&processor, RegisterX, 0x37);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn sax_absolute() -> () {
+    const EXPECTED_CYCLES: u32 = 4;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0b1100_1100;
+    processor.register_x = 0b1010_1010;
+
+    memory.data[0xFF00] = SAX_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8000, 0b1000_1000);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn slo_absolute_x() -> () {
+    const EXPECTED_CYCLES: u32 = 7;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x01;
+    processor.register_x = 0x02;
+
+    memory.data[0xFF00] = SLO_ABSOLUTE_X;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8002] = 0b1100_0000;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8002, 0b1000_0000);
+    verify_register(&processor, Accumulator, 0b1000_0001);
+    verify_flag(&processor, CarryFlag, true);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn rra_zero_page() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x10;
+    processor.set_status(CarryFlag, true);
+
+    memory.data[0xFF00] = RRA_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0x0042] = 0x03; // ROR with carry in gives 0x81 and carry out, then 0x10 + 0x81 + 1
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0x81);
+    verify_register(&processor, Accumulator, 0x92);
+    verify_flag(&processor, CarryFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn dcp_zero_page() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x41;
+
+    memory.data[0xFF00] = DCP_ZERO_PAGE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0x0042] = 0x42;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0042, 0x41);
+    verify_flag(&processor, ZeroFlag, true);
+    verify_flag(&processor, CarryFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn isc_indirect_y() -> () {
+    const EXPECTED_CYCLES: u32 = 8;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x10;
+    processor.register_y = 0x04;
+    processor.set_status(CarryFlag, true);
+
+    memory.data[0xFF00] = ISC_INDIRECT_Y;
+    memory.data[0xFF01] = 0x02;
+    memory.data[0x0002] = 0x00;
+    memory.data[0x0003] = 0x80;
+    memory.data[0x8004] = 0x04;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8004, 0x05);
+    verify_register(&processor, Accumulator, 0x0B);
+    verify_flag(&processor, CarryFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn immediate_operations() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup();
+
+    // ANC copies the negative flag into carry
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0xF0;
+    memory.data[0xFF00] = ANC_IMMEDIATE;
+    memory.data[0xFF01] = 0x80;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0x80);
+    verify_flag(&processor, CarryFlag, true);
+
+    // ALR is AND followed by LSR of the accumulator
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0xFF;
+    memory.data[0xFF00] = ALR_IMMEDIATE;
+    memory.data[0xFF01] = 0x03;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0x01);
+    verify_flag(&processor, CarryFlag, true);
+
+    // ARR takes carry from bit 6 and overflow from bit 6 XOR bit 5 of the result
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0xFF;
+    processor.set_status(CarryFlag, true);
+    memory.data[0xFF00] = ARR_IMMEDIATE;
+    memory.data[0xFF01] = 0x80;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0xC0);
+    verify_flag(&processor, CarryFlag, true);
+    verify_flag(&processor, OverflowFlag, true);
+    verify_flag(&processor, NegativeFlag, true);
+
+    // SBX subtracts without borrow from A AND X
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x0F;
+    processor.register_x = 0x3C;
+    memory.data[0xFF00] = SBX_IMMEDIATE;
+    memory.data[0xFF01] = 0x02;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, RegisterX, 0x0A);
+    verify_flag(&processor, CarryFlag, true);
+
+    // 0xEB behaves exactly like SBC immediate
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x10;
+    processor.set_status(CarryFlag, true);
+    memory.data[0xFF00] = SBC_IMMEDIATE_EB;
+    memory.data[0xFF01] = 0x01;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0x0F);
+}
+
+pub fn unstable_magic() -> () {
+    const EXPECTED_CYCLES: u32 = 2;
+    let (mut memory, mut processor) = setup();
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.unstable_magic = 0xEE;
+    processor.accumulator = 0x01;
+    processor.register_x = 0xFF;
+    memory.data[0xFF00] = XAA_IMMEDIATE;
+    memory.data[0xFF01] = 0xFF;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0xEF); // (0x01 | 0xEE) & 0xFF & 0xFF
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.unstable_magic = 0xFF;
+    memory.data[0xFF00] = LXA_IMMEDIATE;
+    memory.data[0xFF01] = 0x5A;
+    processor.cycles = EXPECTED_CYCLES;
+    verify_cycles(
+        processor.execute(&mut memory).cycles,
+        EXPECTED_CYCLES as i64,
+    );
+    verify_register(&processor, Accumulator, 0x5A);
+    verify_register(&processor, RegisterX, 0x5A);
+}
+
+pub fn shx_page_cross() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x0F;
+    processor.register_y = 0x02;
+
+    memory.data[0xFF00] = SHX_ABSOLUTE_Y;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0x12; // 0x12FF + 2 crosses a page, so the stored value 0x0F & 0x13 becomes the high byte
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x0301, 0x03);
+    verify_memory(&memory, 0x1301, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn ahx_absolute_y() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0xFF;
+    processor.register_x = 0xF3;
+    processor.register_y = 0x01;
+
+    memory.data[0xFF00] = AHX_ABSOLUTE_Y;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80; // stored value is 0xFF & 0xF3 & 0x81
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8001, 0x81);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn multi_byte_nops() -> () {
+    let (mut memory, mut processor) = setup();
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    memory.data[0xFF00] = NOP_IMMEDIATE_80;
+    memory.data[0xFF01] = 0x42;
+    processor.cycles = 2;
+    verify_cycles(processor.execute(&mut memory).cycles, 2);
+    verify_program_counter(&processor, 0xFF02);
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x01;
+    memory.data[0xFF00] = NOP_ABSOLUTE_X_1C;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0x80; // crosses from 0x80FF into 0x8100
+    processor.cycles = 5;
+    verify_cycles(processor.execute(&mut memory).cycles, 5);
+    verify_program_counter(&processor, 0xFF03);
+
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    memory.data[0xFF00] = NOP_IMPLIED_1A;
+    processor.cycles = 2;
+    verify_cycles(processor.execute(&mut memory).cycles, 2);
+    verify_program_counter(&processor, 0xFF01);
+}
+
+pub fn jam() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = JAM_02;
+    memory.data[0xFF01] = LDA_IMMEDIATE;
+    memory.data[0xFF02] = 0x42;
+
+    processor.cycles = 10;
+    processor.execute(&mut memory);
+
+    assert_eq!(
+        processor.fault,
+        Some(Fault::Jammed {
+            opcode: JAM_02,
+            address: 0xFF00
+        }),
+        "Processor did not jam"
+    );
+    verify_program_counter(&processor, 0xFF00);
+    verify_register(&processor, Accumulator, 0x00);
+
+    processor.cycles = 10;
+    verify_cycles(processor.execute(&mut memory).cycles, 10);
+    verify_program_counter(&processor, 0xFF00);
+}
+
+pub fn policy_nop() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.illegal_opcode_policy = IllegalOpcodePolicy::Nop;
+    processor.accumulator = 0x01;
+
+    memory.data[0xFF00] = SLO_ABSOLUTE;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8000] = 0x40;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_memory(&memory, 0x8000, 0x40);
+    verify_register(&processor, Accumulator, 0x01);
+    verify_program_counter(&processor, 0xFF03);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn policy_halt() -> () {
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.illegal_opcode_policy = IllegalOpcodePolicy::Halt;
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0x42;
+    memory.data[0xFF02] = LAX_ABSOLUTE;
+
+    processor.cycles = 10;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert_eq!(
+        processor.fault,
+        Some(Fault::IllegalOpcode {
+            opcode: LAX_ABSOLUTE,
+            address: 0xFF02
+        }),
+        "Processor did not stop at the illegal opcode"
+    );
+    verify_program_counter(&processor, 0xFF02);
+    verify_register(&processor, Accumulator, 0x42);
+    verify_cycles(cycles, 3);
+}
@@ -0,0 +1,120 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::*;
+
+pub fn construction() -> () {
+    for variant in VARIANTS {
+        let processor = Processor::new(variant);
+        assert_eq!(
+            processor.variant, variant,
+            "{:?} was not kept by the constructor",
+            variant
+        );
+    }
+
+    assert_eq!(Variant::Ricoh2A03.instruction_set(), InstructionSet::Nmos);
+    assert_eq!(
+        Variant::Nmos6502RevA.instruction_set(),
+        InstructionSet::Nmos
+    );
+    assert_eq!(
+        Variant::Rockwell65C02.instruction_set(),
+        InstructionSet::Cmos
+    );
+    assert_eq!(
+        Variant::Ricoh2A03.decimal_arithmetic(),
+        DecimalArithmetic::Ignored
+    );
+}
+
+pub fn rev_a_rotate_right() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 5;
+    let (mut memory, mut processor) = setup_variant(Variant::Nmos6502RevA);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x81;
+    processor.set_status(CarryFlag, true);
+
+    memory.data[0xFF00] = ROR_ACCUMULATOR;
+    memory.data[0xFF01] = ROR_ZERO_PAGE;
+    memory.data[0xFF02] = 0x42;
+    memory.data[0x0042] = 0xC0;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x02);
+    verify_memory(&memory, 0x0042, 0x80);
+    verify_flag(&processor, CarryFlag, true);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn indirect_jump_page_wrap() -> () {
+    for variant in [Variant::Nmos6502, Variant::Wdc65C02] {
+        let (mut memory, mut processor) = setup_variant(variant);
+        reset_to(&mut processor, &mut memory, 0xFF00);
+
+        memory.data[0xFF00] = JMP_INDIRECT;
+        memory.data[0xFF01] = 0xFF;
+        memory.data[0xFF02] = 0x80;
+        memory.data[0x80FF] = 0x00;
+        memory.data[0x8000] = 0x70;
+        memory.data[0x8100] = 0x90;
+
+        let expected_cycles: u32 = variant.cycles(JMP_INDIRECT) as u32;
+        processor.cycles = expected_cycles;
+        let cycles = processor.execute(&mut memory).cycles;
+
+        match variant.has_quirk(Quirk::IndirectJumpPageWrap) {
+            true => verify_program_counter(&processor, 0x7000),
+            false => verify_program_counter(&processor, 0x9000),
+        }
+        verify_cycles(cycles, expected_cycles as i64);
+    }
+}
+
+pub fn cycle_tables() -> () {
+    /* Operands are zero, so none of these cross a page or take a branch */
+    let opcodes: [u8; 12] = [
+        LDA_IMMEDIATE,
+        LDA_ZERO_PAGE,
+        LDA_ABSOLUTE,
+        STA_ZERO_PAGE_X,
+        INC_ZERO_PAGE,
+        DEC_ABSOLUTE,
+        JMP_ABSOLUTE,
+        PHA,
+        TAX,
+        NOP,
+        BEQ,
+        JMP_INDIRECT,
+    ];
+
+    for variant in VARIANTS {
+        for opcode in opcodes {
+            let (mut memory, mut processor) = setup_variant(variant);
+            reset_to(&mut processor, &mut memory, 0xFF00);
+            memory.data[(0xFF00 & variant.address_mask()) as usize] = opcode;
+
+            let expected_cycles: u32 = variant.cycles(opcode) as u32;
+            processor.cycles = expected_cycles;
+            let cycles = processor.execute(&mut memory).cycles;
+
+            assert_eq!(
+                cycles, expected_cycles as i64,
+                "{:?} spent {} cycles on {:#X} when its table lists {}",
+                variant, cycles, opcode, expected_cycles
+            );
+        }
+    }
+
+    assert_eq!(Variant::Wdc65C02.cycles(WAI), 3);
+    assert_eq!(Variant::Rockwell65C02.cycles(WAI), 1);
+    assert_eq!(Variant::Nmos6502.cycles(BRA), 2); // A two byte NOP on NMOS parts
+    assert_eq!(Variant::Wdc65C02.cycles(BRA), 3);
+}
@@ -0,0 +1,274 @@
+use super::common::*;
+use crate::cpu;
+use crate::mem::*;
+
+use cpu::native::registers::*;
+use cpu::opcodes::ProcessorStatus::*;
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+use cpu::variant::Variant;
+
+fn setup_native() -> (Memory, Processor) {
+    /* A 65C816 already switched to native mode with 16-bit accumulator and index registers */
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.set_emulation(false);
+    processor.set_native_status(0x00);
+
+    return (memory, processor);
+}
+
+fn verify_wide_register(
+    processor: &Processor,
+    register: cpu::opcodes::Registers,
+    expected: u16,
+) -> () {
+    let value: u16 = processor.wide_register(register);
+    assert_eq!(
+        value, expected,
+        "the wide register is equal to {:#X} when it should be equal to {:#X}",
+        value, expected
+    );
+}
+
+pub fn exchange_carry_and_emulation() -> () {
+    const EXPECTED_CYCLES: u32 = 2 + 2 + 3 + 3;
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = CLC;
+    memory.data[0xFF01] = XCE;
+    memory.data[0xFF02] = REP;
+    memory.data[0xFF03] = ACCUMULATOR_WIDTH | INDEX_WIDTH;
+    memory.data[0xFF04] = LDA_IMMEDIATE;
+    memory.data[0xFF05] = 0x34;
+    memory.data[0xFF06] = 0x12;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert!(
+        processor.native.emulation == false,
+        "XCE did not leave emulation mode"
+    );
+    verify_flag(&processor, CarryFlag, true); // The old emulation bit
+    verify_wide_register(&processor, Accumulator, 0x1234);
+    verify_program_counter(&processor, 0xFF07);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn register_widths() -> () {
+    const EXPECTED_CYCLES: u32 = 3 + 2 + 2;
+    let (mut memory, mut processor) = setup_native();
+    processor.native.register_x_high = 0x12;
+    processor.register_x = 0x34;
+    processor.native.accumulator_high = 0xAB;
+
+    memory.data[0xFF00] = SEP;
+    memory.data[0xFF01] = ACCUMULATOR_WIDTH | INDEX_WIDTH;
+    memory.data[0xFF02] = LDA_IMMEDIATE;
+    memory.data[0xFF03] = 0x80;
+    memory.data[0xFF04] = TXA;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    // Narrowing X drops its high byte, the accumulator keeps its hidden B half
+    verify_wide_register(&processor, RegisterX, 0x0034);
+    verify_wide_register(&processor, Accumulator, 0xAB34);
+    verify_flag(&processor, NegativeFlag, false);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn wide_arithmetic() -> () {
+    const EXPECTED_CYCLES: u32 = 3 + 3 + 2 + 3;
+    let (mut memory, mut processor) = setup_native();
+
+    memory.data[0xFF00] = LDA_IMMEDIATE;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0x12;
+    memory.data[0xFF03] = ADC_IMMEDIATE;
+    memory.data[0xFF04] = 0x01;
+    memory.data[0xFF05] = 0x00;
+    memory.data[0xFF06] = SEC;
+    memory.data[0xFF07] = SBC_IMMEDIATE;
+    memory.data[0xFF08] = 0x01;
+    memory.data[0xFF09] = 0x13;
+
+    processor.cycles = 6;
+    processor.execute(&mut memory);
+    verify_wide_register(&processor, Accumulator, 0x1300);
+    verify_flag(&processor, CarryFlag, false);
+
+    processor.cycles = EXPECTED_CYCLES - 6;
+    processor.execute(&mut memory);
+    verify_wide_register(&processor, Accumulator, 0xFFFF);
+    verify_flag(&processor, CarryFlag, false);
+    verify_flag(&processor, NegativeFlag, true);
+    verify_flag(&processor, ZeroFlag, false);
+}
+
+pub fn long_addressing() -> () {
+    const EXPECTED_CYCLES: u32 = 6 + 7;
+    let (mut memory, mut processor) = setup_native();
+    processor.register_y = 0x02;
+
+    memory.data[0xFF00] = LDA_ABSOLUTE_LONG;
+    memory.data[0xFF01] = 0x56;
+    memory.data[0xFF02] = 0x34;
+    memory.data[0xFF03] = 0x12;
+    memory.data[0xFF04] = STA_DIRECT_INDIRECT_LONG_Y;
+    memory.data[0xFF05] = 0x10;
+    memory.data[0x0010] = 0x00;
+    memory.data[0x0011] = 0x80;
+    memory.data[0x0012] = 0x7E;
+    memory.poke(0x12_3456, 0xCD);
+    memory.poke(0x12_3457, 0xAB);
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_wide_register(&processor, Accumulator, 0xABCD);
+    assert_eq!(
+        memory.peek(0x7E_8002),
+        0xCD,
+        "[d],Y did not store the low byte in bank 0x7E"
+    );
+    assert_eq!(
+        memory.peek(0x7E_8003),
+        0xAB,
+        "[d],Y did not store the high byte in bank 0x7E"
+    );
+    verify_memory(&memory, 0x8002, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn stack_relative() -> () {
+    const EXPECTED_CYCLES: u32 = 5 + 5;
+    let (mut memory, mut processor) = setup_native();
+    processor.set_wide_stack_pointer(0x1FFF);
+
+    memory.data[0xFF00] = PEA;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x30;
+    memory.data[0xFF03] = LDA_STACK_RELATIVE;
+    memory.data[0xFF04] = 0x01;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_wide_register(&processor, Accumulator, 0x3000);
+    assert_eq!(
+        processor.wide_stack_pointer(),
+        0x1FFD,
+        "PEA did not push two bytes"
+    );
+    verify_memory(&memory, 0x1FFF, 0x30);
+    verify_memory(&memory, 0x1FFE, 0x00);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn block_move() -> () {
+    const EXPECTED_CYCLES: u32 = 3 * 7;
+    let (mut memory, mut processor) = setup_native();
+    processor.set_wide_register(Accumulator, 0x0002);
+    processor.set_wide_register(RegisterX, 0x1000);
+    processor.set_wide_register(RegisterY, 0x2000);
+
+    memory.data[0xFF00] = MVN;
+    memory.data[0xFF01] = 0x02; // Destination bank comes first
+    memory.data[0xFF02] = 0x01;
+    memory.poke(0x01_1000, 0x11);
+    memory.poke(0x01_1001, 0x22);
+    memory.poke(0x01_1002, 0x33);
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    assert_eq!(memory.peek(0x02_2000), 0x11);
+    assert_eq!(memory.peek(0x02_2001), 0x22);
+    assert_eq!(memory.peek(0x02_2002), 0x33);
+    verify_wide_register(&processor, Accumulator, 0xFFFF);
+    verify_wide_register(&processor, RegisterX, 0x1003);
+    verify_wide_register(&processor, RegisterY, 0x2003);
+    assert_eq!(
+        processor.native.data_bank, 0x02,
+        "MVN did not load the destination bank into DB"
+    );
+    verify_program_counter(&processor, 0xFF03);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn native_interrupts() -> () {
+    const EXPECTED_CYCLES: u32 = 8 + 7;
+    let (mut memory, mut processor) = setup_native();
+    processor.native.program_bank = 0x03;
+    processor.set_wide_stack_pointer(0x01FF);
+    processor.program_counter = 0x4000;
+
+    memory.poke(0x03_4000, BRK);
+    memory.poke(0x03_4001, 0xEA); // Signature byte
+    memory.data[0xFFE6] = 0x00;
+    memory.data[0xFFE7] = 0x90;
+    memory.data[0x9000] = RTI;
+
+    processor.cycles = 8;
+    let cycles = processor.execute(&mut memory).cycles;
+    verify_program_counter(&processor, 0x9000);
+    assert_eq!(
+        processor.native.program_bank, 0x00,
+        "BRK did not clear the program bank"
+    );
+    verify_memory(&memory, 0x01FF, 0x03);
+    verify_flag(&processor, InterruptDisable, true);
+    verify_cycles(cycles, 8);
+
+    processor.cycles = EXPECTED_CYCLES - 8;
+    let cycles = processor.execute(&mut memory).cycles;
+    verify_program_counter(&processor, 0x4002);
+    assert_eq!(
+        processor.native.program_bank, 0x03,
+        "RTI did not restore the program bank"
+    );
+    verify_cycles(cycles, (EXPECTED_CYCLES - 8) as i64);
+}
+
+pub fn exchange_accumulator() -> () {
+    const EXPECTED_CYCLES: u32 = 3;
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.accumulator = 0x12;
+    processor.native.accumulator_high = 0x80;
+
+    memory.data[0xFF00] = XBA;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x80);
+    assert_eq!(
+        processor.native.accumulator_high, 0x12,
+        "XBA did not move A into B"
+    );
+    verify_flag(&processor, NegativeFlag, true);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn relocated_direct_page() -> () {
+    const EXPECTED_CYCLES: u32 = 4;
+    let (mut memory, mut processor) = setup_variant(Variant::Wdc65C816);
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.native.direct_page = 0x0201;
+
+    memory.data[0xFF00] = LDA_ZERO_PAGE;
+    memory.data[0xFF01] = 0x10;
+    memory.data[0x0211] = 0x5A;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    // An unaligned direct page costs a cycle even in emulation mode
+    verify_register(&processor, Accumulator, 0x5A);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
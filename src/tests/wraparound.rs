@@ -0,0 +1,138 @@
+use super::common::*;
+use crate::cpu;
+
+use cpu::opcodes::Registers::*;
+use cpu::opcodes::*;
+use cpu::processor::*;
+
+pub fn indirect_jump_page() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+
+    memory.data[0xFF00] = JMP_INDIRECT;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0x30;
+    memory.data[0x30FF] = 0x80;
+    memory.data[0x3000] = 0x50; // The high byte comes from the start of the same page
+    memory.data[0x3100] = 0x40;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x5080);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn indirect_x_wrap() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x10;
+
+    memory.data[0xFF00] = LDA_INDIRECT_X;
+    memory.data[0xFF01] = 0xEF; // $EF + $10 puts the pointer on the last byte of the zero page
+    memory.data[0x00FF] = 0x34;
+    memory.data[0x0000] = 0x12; // High byte of the pointer wraps to $00
+    memory.data[0x0100] = 0x56;
+    memory.data[0x1234] = 0x99;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x99);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+
+    // An index that carries out of the zero page wraps back into it
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0xF0;
+    memory.data[0xFF01] = 0x20;
+    memory.data[0x0010] = 0x34;
+    memory.data[0x0011] = 0x12;
+
+    processor.cycles = EXPECTED_CYCLES;
+    processor.execute(&mut memory);
+    verify_register(&processor, Accumulator, 0x99);
+}
+
+pub fn indirect_y_wrap() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_y = 0x02;
+
+    memory.data[0xFF00] = LDA_INDIRECT_Y;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0x00FF] = 0xFE;
+    memory.data[0x0000] = 0xFF; // The pointer is $FFFE, its high byte fetched from $00
+    memory.data[0x0100] = 0x20;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    // $FFFE + 2 carries past the top of memory to $0000, which holds the pointer's high byte
+    verify_register(&processor, Accumulator, 0xFF);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn absolute_index_wrap() -> () {
+    const EXPECTED_CYCLES: u32 = 5;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.register_x = 0x03;
+
+    memory.data[0xFF00] = LDA_ABSOLUTE_X;
+    memory.data[0xFF01] = 0xFF;
+    memory.data[0xFF02] = 0xFF;
+    memory.data[0x0002] = 0x77;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_register(&processor, Accumulator, 0x77);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}
+
+pub fn stack_wrap() -> () {
+    const EXPECTED_CYCLES: u32 = 6 + 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.stack_pointer = 0x00;
+
+    memory.data[0xFF00] = JSR;
+    memory.data[0xFF01] = 0x00;
+    memory.data[0xFF02] = 0x80;
+    memory.data[0x8000] = RTS;
+
+    processor.cycles = 6;
+    processor.execute(&mut memory);
+
+    // The low byte of the return address goes to $01FF, not $00FF
+    verify_memory(&memory, 0x0100, 0xFF);
+    verify_memory(&memory, 0x01FF, 0x02);
+    verify_memory(&memory, 0x00FF, 0x00);
+    assert_eq!(processor.stack_pointer, 0xFE);
+
+    processor.cycles = EXPECTED_CYCLES - 6;
+    processor.execute(&mut memory);
+
+    verify_program_counter(&processor, 0xFF03);
+    assert_eq!(processor.stack_pointer, 0x00);
+}
+
+pub fn return_address_wrap() -> () {
+    const EXPECTED_CYCLES: u32 = 6;
+    let (mut memory, mut processor) = setup();
+    reset_to(&mut processor, &mut memory, 0xFF00);
+    processor.stack_pointer = 0xFD;
+
+    memory.data[0xFF00] = RTS;
+    memory.data[0x01FE] = 0xFF;
+    memory.data[0x01FF] = 0xFF;
+
+    processor.cycles = EXPECTED_CYCLES;
+    let cycles = processor.execute(&mut memory).cycles;
+
+    verify_program_counter(&processor, 0x0000);
+    verify_cycles(cycles, EXPECTED_CYCLES as i64);
+}